@@ -0,0 +1,16 @@
+-- Best-of-N match series. Every round is still stored in rps_games and
+-- linked to its match; rps_results is bumped once per match.
+CREATE TABLE rps_matches (
+    id          BIGSERIAL PRIMARY KEY,
+    player1     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    best_of     SMALLINT NOT NULL,
+    score1      SMALLINT NOT NULL,
+    score2      SMALLINT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE rps_games
+    ADD COLUMN match_id BIGINT REFERENCES rps_matches(id) ON DELETE CASCADE;
+
+CREATE INDEX rps_games_match_id_idx ON rps_games (match_id);
@@ -0,0 +1,7 @@
+-- Bots play from regular user rows (role 'bot') so games keep their foreign keys.
+-- Games against bots are stored but flagged as unranked.
+ALTER TABLE rps_matches
+    ADD COLUMN ranked BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE rps_games
+    ADD COLUMN ranked BOOLEAN NOT NULL DEFAULT TRUE;
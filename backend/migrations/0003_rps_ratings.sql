@@ -0,0 +1,24 @@
+-- Glicko-2 ratings, updated in the same transaction that records a ranked game.
+CREATE TABLE rps_ratings (
+    user_id     UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    rating      DOUBLE PRECISION NOT NULL DEFAULT 1500,
+    deviation   DOUBLE PRECISION NOT NULL DEFAULT 350,
+    volatility  DOUBLE PRECISION NOT NULL DEFAULT 0.06,
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- One row per player and rated game. Matches link to rps_matches, single games to rps_games.
+CREATE TABLE rps_rating_history (
+    id                BIGSERIAL PRIMARY KEY,
+    user_id           UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    match_id          BIGINT REFERENCES rps_matches(id) ON DELETE CASCADE,
+    game_id           BIGINT REFERENCES rps_games(id) ON DELETE CASCADE,
+    rating_before     DOUBLE PRECISION NOT NULL,
+    deviation_before  DOUBLE PRECISION NOT NULL,
+    rating_after      DOUBLE PRECISION NOT NULL,
+    deviation_after   DOUBLE PRECISION NOT NULL,
+    volatility_after  DOUBLE PRECISION NOT NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX rps_rating_history_user_id_idx ON rps_rating_history (user_id, created_at);
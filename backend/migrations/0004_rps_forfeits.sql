@@ -0,0 +1,3 @@
+-- Player who lost the match by missing the move deadline, NULL for matches played out.
+ALTER TABLE rps_matches
+    ADD COLUMN forfeited_by UUID REFERENCES users(id) ON DELETE SET NULL;
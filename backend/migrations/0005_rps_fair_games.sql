@@ -0,0 +1,19 @@
+-- Commit-reveal games. Commitments and nonces are kept so anyone can recompute
+-- sha256("<move>:<nonce>") and check it against the published hash.
+CREATE TABLE rps_fair_games (
+    id            BIGSERIAL PRIMARY KEY,
+    player1       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    commitment1   TEXT,
+    commitment2   TEXT,
+    move1         TEXT,
+    move2         TEXT,
+    nonce1        TEXT,
+    nonce2        TEXT,
+    forfeited_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    ranked        BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at    TIMESTAMPTZ NOT NULL
+);
+
+ALTER TABLE rps_rating_history
+    ADD COLUMN fair_game_id BIGINT REFERENCES rps_fair_games(id) ON DELETE CASCADE;
@@ -0,0 +1,15 @@
+-- Games in progress, kept only when the persistent game service is enabled so that
+-- they survive a restart. `state` is the serialized game, `kind` tells game types apart.
+CREATE TABLE active_games (
+    id          BIGSERIAL PRIMARY KEY,
+    kind        TEXT NOT NULL,
+    player1     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    state       JSONB NOT NULL,
+    deadline    TIMESTAMPTZ NOT NULL,
+    ready       BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX active_games_player1_idx ON active_games (kind, player1);
+CREATE INDEX active_games_player2_idx ON active_games (kind, player2);
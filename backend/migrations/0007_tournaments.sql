@@ -0,0 +1,45 @@
+-- Tournaments take sign-ups until starts_at, then the server pairs and starts every match.
+CREATE TABLE tournaments (
+    id             BIGSERIAL PRIMARY KEY,
+    name           TEXT NOT NULL,
+    format         TEXT NOT NULL,          -- 'single_elimination' | 'swiss'
+    best_of        SMALLINT NOT NULL,
+    status         TEXT NOT NULL DEFAULT 'open',
+    created_by     UUID REFERENCES users(id) ON DELETE SET NULL,
+    starts_at      TIMESTAMPTZ NOT NULL,
+    rounds         INT NOT NULL DEFAULT 0,
+    current_round  INT NOT NULL DEFAULT 0,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE INDEX tournaments_status_idx ON tournaments (status, starts_at);
+
+-- Standings. Seeds are set from the ratings once the tournament starts.
+CREATE TABLE tournament_players (
+    tournament_id  BIGINT NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    user_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    seed           INT NOT NULL DEFAULT 0,
+    wins           INT NOT NULL DEFAULT 0,
+    draws          INT NOT NULL DEFAULT 0,
+    losses         INT NOT NULL DEFAULT 0,
+    eliminated     BOOLEAN NOT NULL DEFAULT FALSE,
+    joined_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (tournament_id, user_id)
+);
+
+-- player2 is NULL for a bye. winner stays NULL for a draw.
+CREATE TABLE tournament_matches (
+    id             BIGSERIAL PRIMARY KEY,
+    tournament_id  BIGINT NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    round          INT NOT NULL,
+    slot           INT NOT NULL,
+    player1        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2        UUID REFERENCES users(id) ON DELETE CASCADE,
+    winner         UUID REFERENCES users(id) ON DELETE SET NULL,
+    status         TEXT NOT NULL DEFAULT 'pending',  -- 'pending' | 'playing' | 'done'
+    attempts       INT NOT NULL DEFAULT 0,
+    since          TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (tournament_id, round, slot)
+);
+
+CREATE INDEX tournament_matches_status_idx ON tournament_matches (status);
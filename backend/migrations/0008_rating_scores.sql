@@ -0,0 +1,37 @@
+-- Result of every rated game from the player's side (1 win, 0.5 draw, 0 loss), so
+-- standings can be counted over a time window. rps_results only keeps all-time totals.
+ALTER TABLE rps_rating_history
+    ADD COLUMN score DOUBLE PRECISION;
+
+UPDATE rps_rating_history h
+SET score = CASE
+        WHEN m.forfeited_by IS NOT NULL THEN (m.forfeited_by <> h.user_id)::int
+        WHEN m.score1 = m.score2 THEN 0.5
+        WHEN (m.score1 > m.score2) = (h.user_id = m.player1) THEN 1
+        ELSE 0
+    END
+FROM rps_matches m
+WHERE h.match_id = m.id;
+
+UPDATE rps_rating_history h
+SET score = CASE
+        WHEN g.move1 = g.move2 THEN 0.5
+        WHEN ((g.move1, g.move2) IN (('Rock', 'Scissors'), ('Paper', 'Rock'), ('Scissors', 'Paper')))
+             = (h.user_id = g.player1) THEN 1
+        ELSE 0
+    END
+FROM rps_games g
+WHERE h.game_id = g.id;
+
+UPDATE rps_rating_history h
+SET score = CASE
+        WHEN g.forfeited_by IS NOT NULL THEN (g.forfeited_by <> h.user_id)::int
+        WHEN g.move1 IS NULL OR g.move2 IS NULL OR g.move1 = g.move2 THEN 0.5
+        WHEN ((g.move1, g.move2) IN (('Rock', 'Scissors'), ('Paper', 'Rock'), ('Scissors', 'Paper')))
+             = (h.user_id = g.player1) THEN 1
+        ELSE 0
+    END
+FROM rps_fair_games g
+WHERE h.fair_game_id = g.id;
+
+CREATE INDEX rps_rating_history_created_at_idx ON rps_rating_history (created_at);
@@ -0,0 +1,15 @@
+-- Finished tic-tac-toe games. The board is stored row by row as nine characters
+-- ('X', 'O' or '.'); player1 always plays X. These games do not affect RPS ratings.
+CREATE TABLE ttt_games (
+    id            BIGSERIAL PRIMARY KEY,
+    player1       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    board         TEXT NOT NULL,
+    winner        UUID REFERENCES users(id) ON DELETE SET NULL,
+    forfeited_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    ranked        BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at    TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX ttt_games_player1_idx ON ttt_games (player1);
+CREATE INDEX ttt_games_player2_idx ON ttt_games (player2);
@@ -0,0 +1,18 @@
+-- Battle royale games with a placement per player. Players knocked out in the same
+-- round share a place; eliminated_in is NULL for whoever was still in at the end.
+CREATE TABLE royale_games (
+    id          BIGSERIAL PRIMARY KEY,
+    rounds      INT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE royale_placements (
+    game_id        BIGINT NOT NULL REFERENCES royale_games(id) ON DELETE CASCADE,
+    user_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    place          INT NOT NULL,
+    eliminated_in  INT,
+    PRIMARY KEY (game_id, user_id)
+);
+
+CREATE INDEX royale_placements_user_idx ON royale_placements (user_id);
@@ -0,0 +1,10 @@
+-- Named game rooms. Members and the winner-stays-on rotation are kept in memory;
+-- a room with a password_hash can only be joined with the password.
+CREATE TABLE rooms (
+    id             BIGSERIAL PRIMARY KEY,
+    name           TEXT NOT NULL UNIQUE,
+    owner          UUID REFERENCES users(id) ON DELETE SET NULL,
+    password_hash  TEXT,
+    best_of        SMALLINT NOT NULL,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,8 @@
+-- Achievements unlocked by each user. What it takes to unlock one is defined in code,
+-- the key being the achievement's stable id from there.
+CREATE TABLE user_achievements (
+    user_id      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    achievement  TEXT NOT NULL,
+    unlocked_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, achievement)
+);
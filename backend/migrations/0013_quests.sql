@@ -0,0 +1,25 @@
+-- Daily and weekly quests, generated when their period starts. Every user works on
+-- the quests of the current periods; user_quests only has rows once they made progress.
+CREATE TABLE quests (
+    id         BIGSERIAL PRIMARY KEY,
+    period     TEXT NOT NULL,
+    -- Position within the period, so the quests of a period are only generated once.
+    slot       SMALLINT NOT NULL,
+    goal       TEXT NOT NULL,
+    -- Move for 'win_with' goals, NULL otherwise.
+    mv         TEXT,
+    target     INTEGER NOT NULL,
+    starts_at  TIMESTAMPTZ NOT NULL,
+    ends_at    TIMESTAMPTZ NOT NULL,
+    UNIQUE (period, starts_at, slot)
+);
+
+CREATE INDEX quests_ends_at_idx ON quests (ends_at);
+
+CREATE TABLE user_quests (
+    user_id       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    quest_id      BIGINT NOT NULL REFERENCES quests(id) ON DELETE CASCADE,
+    progress      INTEGER NOT NULL DEFAULT 0,
+    completed_at  TIMESTAMPTZ,
+    PRIMARY KEY (user_id, quest_id)
+);
@@ -0,0 +1,35 @@
+-- Competitive seasons. Once ends_at passes the server archives the final standings,
+-- sets archived_at and pulls every rating back toward the mean.
+CREATE TABLE seasons (
+    id           BIGSERIAL PRIMARY KEY,
+    name         TEXT NOT NULL,
+    starts_at    TIMESTAMPTZ NOT NULL,
+    ends_at      TIMESTAMPTZ NOT NULL,
+    archived_at  TIMESTAMPTZ,
+    created_by   UUID REFERENCES users(id) ON DELETE SET NULL,
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX seasons_ends_at_idx ON seasons (ends_at);
+
+-- Ranked results within a season, counted next to the all-time ones in rps_results.
+CREATE TABLE season_results (
+    season_id     BIGINT NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    user_id       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    win_counter   INT NOT NULL DEFAULT 0,
+    lose_counter  INT NOT NULL DEFAULT 0,
+    draw_counter  INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (season_id, user_id)
+);
+
+-- Final standings, written once when the season is archived.
+CREATE TABLE season_standings (
+    season_id  BIGINT NOT NULL REFERENCES seasons(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    rank       INT NOT NULL,
+    rating     DOUBLE PRECISION NOT NULL,
+    wins       INT NOT NULL,
+    losses     INT NOT NULL,
+    draws      INT NOT NULL,
+    PRIMARY KEY (season_id, user_id)
+);
@@ -0,0 +1,24 @@
+-- Pairs of players the collusion analysis found suspicious, player1 < player2.
+-- Moderators dismiss a flag or exclude the pair's games from ratings.
+CREATE TABLE collusion_flags (
+    id            BIGSERIAL PRIMARY KEY,
+    player1       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    games         INT NOT NULL,
+    wins1         INT NOT NULL,
+    wins2         INT NOT NULL,
+    head_to_head  DOUBLE PRECISION NOT NULL,
+    move_chi2     DOUBLE PRECISION NOT NULL,
+    signals       TEXT[] NOT NULL,
+    status        TEXT NOT NULL DEFAULT 'open',   -- 'open' | 'dismissed' | 'excluded'
+    flagged_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    reviewed_by   UUID REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at   TIMESTAMPTZ,
+    UNIQUE (player1, player2),
+    CHECK (player1 < player2)
+);
+
+CREATE INDEX collusion_flags_status_idx ON collusion_flags (status, flagged_at);
+
+-- Rating changes taken back after their pair was excluded.
+ALTER TABLE rps_rating_history ADD COLUMN excluded BOOLEAN NOT NULL DEFAULT FALSE;
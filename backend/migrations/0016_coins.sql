@@ -0,0 +1,39 @@
+-- Virtual coins. coin_accounts holds each balance; every change to it is a row in
+-- coin_ledger along with the balance it left, so balances can be audited.
+CREATE TABLE coin_accounts (
+    user_id  UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    balance  BIGINT NOT NULL DEFAULT 0 CHECK (balance >= 0)
+);
+
+-- Stakes of a ranked game, held from its start until it is recorded or abandoned.
+CREATE TABLE coin_wagers (
+    id          BIGSERIAL PRIMARY KEY,
+    player1     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    player2     UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    stake       INT NOT NULL CHECK (stake > 0),
+    status      TEXT NOT NULL DEFAULT 'open',   -- 'open' | 'settled' | 'refunded'
+    -- NULL for a draw or a refund.
+    winner      UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    settled_at  TIMESTAMPTZ
+);
+
+-- A pair only ever plays one wagered game at a time.
+CREATE UNIQUE INDEX coin_wagers_open_idx
+    ON coin_wagers (LEAST(player1, player2), GREATEST(player1, player2))
+    WHERE status = 'open';
+
+CREATE TABLE coin_ledger (
+    id             BIGSERIAL PRIMARY KEY,
+    user_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    amount         BIGINT NOT NULL,
+    balance        BIGINT NOT NULL,
+    reason         TEXT NOT NULL,               -- 'allowance' | 'stake' | 'payout' | 'refund'
+    wager_id       BIGINT REFERENCES coin_wagers(id) ON DELETE SET NULL,
+    -- Set for allowances, so a user gets one a day.
+    allowance_day  DATE,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (user_id, allowance_day)
+);
+
+CREATE INDEX coin_ledger_user_id_idx ON coin_ledger (user_id, created_at);
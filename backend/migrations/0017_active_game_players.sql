@@ -0,0 +1,22 @@
+-- One active game per player and game kind. `active_games` keeps the players in two
+-- columns, which a unique index can't span, so every player also gets a row here.
+CREATE TABLE active_game_players (
+    kind       TEXT NOT NULL,
+    player_id  UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    game_id    BIGINT NOT NULL REFERENCES active_games(id) ON DELETE CASCADE,
+    PRIMARY KEY (kind, player_id)
+);
+
+-- Of games that already break the rule, only the oldest per player is kept.
+DELETE FROM active_games g
+WHERE EXISTS (
+    SELECT 1 FROM active_games older
+    WHERE older.kind = g.kind
+      AND older.id < g.id
+      AND (older.player1 IN (g.player1, g.player2) OR older.player2 IN (g.player1, g.player2))
+);
+
+INSERT INTO active_game_players (kind, player_id, game_id)
+SELECT kind, player1, id FROM active_games
+UNION ALL
+SELECT kind, player2, id FROM active_games;
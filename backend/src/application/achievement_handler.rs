@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use tokio::sync::mpsc::UnboundedReceiver;
+use uuid::Uuid;
+
+use crate::domain::{
+    achievement_model::{earned, AchievementService},
+    game_model::{FinishedGame, GameNotifier, GameOutcome},
+};
+use shared::{game::GameError, ws_messages::ServerMsg};
+
+/// Unlocks achievements as games get recorded and forum posts get liked, and lets the
+/// player know about each new one.
+#[derive(Clone)]
+pub struct AchievementHandler
+{
+    pub achievement_service: Arc<dyn AchievementService>,
+    pub notifier: Arc<dyn GameNotifier>,
+}
+
+impl AchievementHandler
+{
+    pub fn new(achievement_service: Arc<dyn AchievementService>,
+               notifier: Arc<dyn GameNotifier>)
+               -> Self
+    {
+        Self { achievement_service,
+               notifier }
+    }
+
+    /// Check both players of every finished game in the background.
+    pub fn follow<F>(&self, mut results: UnboundedReceiver<GameOutcome<F>>)
+        where F: FinishedGame + 'static
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            while let Some(outcome) = results.recv().await {
+                if let GameOutcome::Finished(fin_game) = outcome {
+                    for user_id in fin_game.players() {
+                        let _ = handler.check(user_id).await;
+                    }
+                }
+            }
+        });
+    }
+
+    pub async fn post_liked(&self, post_id: i64) -> Result<(), GameError>
+    {
+        match self.achievement_service.post_author(post_id).await? {
+            Some(author) => self.check(author).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Unlock whatever the user's stats now reach and notify them of the new ones.
+    async fn check(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        let stats = self.achievement_service.stats(user_id).await?;
+        let achievements = earned(&stats);
+        if achievements.is_empty() {
+            return Ok(());
+        }
+
+        let unlocked = self.achievement_service
+                           .unlock(user_id, &achievements)
+                           .await?;
+        for achievement in unlocked {
+            self.notifier
+                .notify(user_id, ServerMsg::AchievementMsg(achievement))
+                .await;
+        }
+        Ok(())
+    }
+}
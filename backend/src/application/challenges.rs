@@ -0,0 +1,211 @@
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::application::game_handler::GameHandler;
+use crate::domain::game_model::ActiveGame;
+use shared::{
+    challenge::{ChallengeEvent, ChallengeReq},
+    game::{GameEnvelope, GameError},
+    ws_messages::ServerMsg,
+};
+
+/// Direct invitation from one player to another, bypassing the matchmaking queue.
+#[derive(Clone)]
+pub struct Challenge<S>
+{
+    pub to: Uuid,
+    pub settings: S,
+    pub expires_at: Instant,
+}
+
+impl<G> GameHandler<G> where G: ActiveGame + 'static
+{
+    pub async fn handle_challenge(&self,
+                                  user_id: Uuid,
+                                  req: ChallengeReq<G::Settings>)
+                                  -> Result<(), GameError>
+    {
+        match req {
+            ChallengeReq::Send { username, settings } => {
+                self.challenge(user_id, &username, settings).await
+            }
+            ChallengeReq::Accept { from } => self.accept_challenge(user_id, &from).await,
+            ChallengeReq::Decline { from } => self.decline_challenge(user_id, &from).await,
+            ChallengeReq::Cancel => self.cancel_challenge(user_id).await,
+        }
+    }
+
+    pub async fn challenge(&self,
+                           user_id: Uuid,
+                           username: &str,
+                           settings: G::Settings)
+                           -> Result<(), GameError>
+    {
+        let opp_id = self.notifier
+                         .find_online(username)
+                         .await
+                         .ok_or(GameError::UserUnavailable)?;
+
+        if opp_id == user_id || self.game_service.has_active_game(opp_id).await {
+            return Err(GameError::UserUnavailable);
+        }
+        if self.game_service.has_active_game(user_id).await {
+            return Err(GameError::AlreadyInGame);
+        }
+
+        let player_name = self.notifier
+                              .get_name(user_id)
+                              .await
+                              .ok_or(GameError::Disconnected)?;
+
+        let expires_at = Instant::now() + self.challenge_ttl;
+        let challenge = Challenge { to: opp_id,
+                                    settings: settings.clone(),
+                                    expires_at };
+        let replaced = self.challenges.lock().await.insert(user_id, challenge);
+
+        if let Some(old) = replaced
+           && old.to != opp_id
+        {
+            let msg = ChallengeEvent::Cancelled { by: player_name.clone() };
+            self.notifier.notify(old.to, challenge_msg::<G>(msg)).await;
+        }
+
+        let expires_in = self.challenge_ttl.as_secs();
+
+        let incoming = ChallengeEvent::Incoming { from: player_name,
+                                                  settings: settings.clone(),
+                                                  expires_in };
+        self.notifier.notify(opp_id, challenge_msg::<G>(incoming)).await;
+
+        let sent = ChallengeEvent::Sent { to: username.to_string(),
+                                          settings,
+                                          expires_in };
+        self.notifier.notify(user_id, challenge_msg::<G>(sent)).await;
+
+        let handler = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep_until(expires_at).await;
+            handler.expire_challenge(user_id, expires_at).await;
+        });
+
+        Ok(())
+    }
+
+    pub async fn accept_challenge(&self, user_id: Uuid, from: &str) -> Result<(), GameError>
+    {
+        if self.game_service.has_active_game(user_id).await {
+            return Err(GameError::AlreadyInGame);
+        }
+
+        let player = self.tag_of(user_id).await.ok_or(GameError::Disconnected)?;
+
+        let (from_id, challenge) = self.take_challenge(user_id, from).await?;
+        let opp = self.tag_of(from_id).await.ok_or(GameError::UserUnavailable)?;
+
+        if self.game_service.has_active_game(from_id).await {
+            return Err(GameError::UserUnavailable);
+        }
+
+        // accepting means you are no longer waiting for anything else
+        let _ = self.cancel_challenge(user_id).await;
+        self.withdraw_rematch(user_id).await;
+        self.withdraw_rematch(from_id).await;
+        for player_queue in self.player_queues.values() {
+            player_queue.remove(user_id).await;
+            player_queue.remove(from_id).await;
+        }
+
+        let active_game = self.game_service
+                              .start(from_id, user_id, challenge.settings)
+                              .await?;
+
+        let accepted = ChallengeEvent::Accepted { by: player.name.clone() };
+        self.notifier.notify(from_id, challenge_msg::<G>(accepted)).await;
+
+        let msg = active_game.to_msg(user_id, &player, &opp);
+        self.notifier.notify(user_id, msg.clone()).await;
+        self.notifier.notify(from_id, msg).await;
+
+        Ok(())
+    }
+
+    pub async fn decline_challenge(&self, user_id: Uuid, from: &str) -> Result<(), GameError>
+    {
+        let (from_id, _) = self.take_challenge(user_id, from).await?;
+
+        if let Some(player_name) = self.notifier.get_name(user_id).await {
+            let msg = ChallengeEvent::Declined { by: player_name };
+            self.notifier.notify(from_id, challenge_msg::<G>(msg)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Remove the live challenge `from` sent to `user_id`.
+    async fn take_challenge(&self,
+                            user_id: Uuid,
+                            from: &str)
+                            -> Result<(Uuid, Challenge<G::Settings>), GameError>
+    {
+        let from_id = self.notifier
+                          .find_online(from)
+                          .await
+                          .ok_or(GameError::NotFound)?;
+
+        let mut challenges = self.challenges.lock().await;
+        let is_live = challenges.get(&from_id)
+                                .is_some_and(|c| c.to == user_id && c.expires_at > Instant::now());
+        if !is_live {
+            return Err(GameError::NotFound);
+        }
+
+        let challenge = challenges.remove(&from_id).ok_or(GameError::NotFound)?;
+        Ok((from_id, challenge))
+    }
+
+    async fn expire_challenge(&self, user_id: Uuid, expires_at: Instant)
+    {
+        let expired = {
+            let mut challenges = self.challenges.lock().await;
+            match challenges.get(&user_id) {
+                Some(c) if c.expires_at == expires_at => challenges.remove(&user_id),
+                _ => None,
+            }
+        };
+
+        let Some(challenge) = expired else {
+            return;
+        };
+
+        if let Some(opp_name) = self.notifier.get_name(challenge.to).await {
+            let msg = ChallengeEvent::Expired { with: opp_name };
+            self.notifier.notify(user_id, challenge_msg::<G>(msg)).await;
+        }
+        if let Some(player_name) = self.notifier.get_name(user_id).await {
+            let msg = ChallengeEvent::Expired { with: player_name };
+            self.notifier.notify(challenge.to, challenge_msg::<G>(msg)).await;
+        }
+    }
+
+    pub async fn cancel_challenge(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        let challenge = self.challenges
+                            .lock()
+                            .await
+                            .remove(&user_id)
+                            .ok_or(GameError::NotFound)?;
+
+        if let Some(player_name) = self.notifier.get_name(user_id).await {
+            let msg = ChallengeEvent::Cancelled { by: player_name };
+            self.notifier.notify(challenge.to, challenge_msg::<G>(msg)).await;
+        }
+
+        Ok(())
+    }
+}
+
+fn challenge_msg<G: ActiveGame>(event: ChallengeEvent<G::Settings>) -> ServerMsg
+{
+    ServerMsg::ChallengeMsg(GameEnvelope::challenge::<G::Kind>(&event))
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::Duration;
+
+use crate::domain::{auth_model::User, collusion_model::*};
+use shared::collusion::*;
+
+/// How often ranked games are analysed.
+const TICK: Duration = Duration::from_secs(60 * 60);
+/// Games older than this are left out of the analysis.
+const WINDOW: chrono::Duration = chrono::Duration::days(30);
+
+/// Looks for pairs of players trading wins on the ladder and lets moderators review them.
+#[derive(Clone)]
+pub struct CollusionHandler
+{
+    pub collusion_service: Arc<dyn CollusionService>,
+}
+
+fn is_staff(user: &User) -> bool
+{
+    user.role == "admin" || user.role == "moderator"
+}
+
+impl CollusionHandler
+{
+    /// Analyse the recent ranked games in the background.
+    pub fn run(&self)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let _ = handler.analyse().await;
+                tokio::time::sleep(TICK).await;
+            }
+        });
+    }
+
+    async fn analyse(&self) -> Result<(), CollusionError>
+    {
+        let since = Utc::now() - WINDOW;
+        let counts = self.collusion_service.game_counts(since).await?;
+
+        for pair in self.collusion_service.pair_games(since).await? {
+            let totals = pair.players
+                             .map(|id| counts.get(&id).copied().unwrap_or_default());
+            if let Some(suspicion) = analyse(&pair, totals) {
+                self.collusion_service.flag(&suspicion).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn list(&self, user: &User) -> Result<Vec<CollusionFlag>, CollusionError>
+    {
+        if !is_staff(user) {
+            return Err(CollusionError::Forbidden);
+        }
+        self.collusion_service.list().await
+    }
+
+    pub async fn dismiss(&self, user: &User, id: i64) -> Result<(), CollusionError>
+    {
+        if !is_staff(user) {
+            return Err(CollusionError::Forbidden);
+        }
+        self.collusion_service.dismiss(id, user.id).await
+    }
+
+    pub async fn exclude(&self, user: &User, id: i64) -> Result<(), CollusionError>
+    {
+        if !is_staff(user) {
+            return Err(CollusionError::Forbidden);
+        }
+        self.collusion_service.exclude(id, user.id).await
+    }
+}
@@ -0,0 +1,13 @@
+use crate::domain::fair_rps_model::FairGameArchive;
+use shared::{fair_rps::FairRpsRecord, game::GameError};
+use std::sync::Arc;
+
+pub struct FairPlayHandler {
+    pub archive: Arc<dyn FairGameArchive>,
+}
+
+impl FairPlayHandler {
+    pub async fn get_game(&self, id: i64) -> Result<Option<FairRpsRecord>, GameError> {
+        self.archive.fair_game(id).await
+    }
+}
@@ -1,131 +1,503 @@
+use rand::seq::IndexedRandom;
+use std::collections::HashMap;
 use std::sync::Arc;
+use tokio::{
+    sync::{
+        mpsc::{UnboundedReceiver, UnboundedSender},
+        Mutex,
+    },
+    time::Duration,
+};
 use uuid::Uuid;
 
-use crate::domain::game_model::{
-    ActiveGame, FinishedGame, GameNotifier, GameRecorder, GameService, PlayerQueue,
+use crate::application::{
+    challenges::Challenge, reconnects::Away, rematches::Rematch, spectators::Watchers,
+};
+use crate::domain::{
+    game_model::{
+        ActiveGame, Bot, FinishedGame, GameNotifier, GameOutcome, GameRecorder, GameService,
+        PlayerQueue, PlayerTag, QueuedPlayer, RatingProvider,
+    },
+    wallet_model::WalletService,
+};
+use shared::{
+    game::GameError,
+    wallet::{WalletError, WalletEvent},
+    ws_messages::ServerMsg,
 };
-use shared::{game::GameError, ws_messages::ServerMsg};
 
 /// RPS-specific application orchestrator that enriches messages with usernames via AuthHandler.
+#[derive(Clone)]
 pub struct GameHandler<G>
     where G: ActiveGame
 {
-    pub player_queue: Arc<dyn PlayerQueue>,
+    /// One matchmaking queue per game settings, so only players who picked the same
+    /// settings (e.g. series length) get paired.
+    pub player_queues: HashMap<G::Settings, Arc<dyn PlayerQueue>>,
     pub game_service: Arc<dyn GameService<G>>,
     pub notifier: Arc<dyn GameNotifier>,
     pub recorder: Arc<dyn GameRecorder<G>>,
+    pub ratings: Option<Arc<dyn RatingProvider>>,
+    /// Holds the stakes of queued games; without one nobody can stake coins.
+    pub wallet: Option<Arc<dyn WalletService>>,
+    /// Stake each player asked to play for, see `set_stake`.
+    pub stakes: Arc<Mutex<HashMap<Uuid, u32>>>,
+    /// Opponent of each player's latest finished game, used to vary matchmaking.
+    pub last_opps: Arc<Mutex<HashMap<Uuid, Uuid>>>,
+    pub bots: Vec<Arc<dyn Bot<G>>>,
+    /// How long a player waits in the queue before being matched with a bot.
+    pub bot_wait: Option<Duration>,
+    /// Pending direct challenges keyed by the challenger.
+    pub challenges: Arc<Mutex<HashMap<Uuid, Challenge<G::Settings>>>>,
+    pub challenge_ttl: Duration,
+    /// Open rematch windows keyed by each of the two players.
+    pub rematches: Arc<Mutex<HashMap<Uuid, Rematch<G::Settings>>>>,
+    pub rematch_window: Duration,
+    /// Spectators of live games, see `Watchers`.
+    pub watchers: Arc<Mutex<HashMap<Uuid, Watchers>>>,
+    /// Players holding an active game while disconnected.
+    pub away: Arc<Mutex<HashMap<Uuid, Away>>>,
+    pub reconnect_grace: Duration,
+    /// Where every game outcome is published, e.g. for tournaments and rooms.
+    pub results: Vec<UnboundedSender<GameOutcome<G::FinishedGame>>>,
 }
 
-impl<G> GameHandler<G> where G: ActiveGame
+impl<G> GameHandler<G> where G: ActiveGame + 'static
 {
     pub fn new(game_service: Arc<dyn GameService<G>>,
-               player_queue: Arc<dyn PlayerQueue>,
+               player_queues: HashMap<G::Settings, Arc<dyn PlayerQueue>>,
                notifier: Arc<dyn GameNotifier>,
                recorder: Arc<dyn GameRecorder<G>>)
                -> Self
     {
         Self { game_service,
-               player_queue,
+               player_queues,
                notifier,
-               recorder }
+               recorder,
+               ratings: None,
+               wallet: None,
+               stakes: Arc::new(Mutex::new(HashMap::new())),
+               last_opps: Arc::new(Mutex::new(HashMap::new())),
+               bots: Vec::new(),
+               bot_wait: None,
+               challenges: Arc::new(Mutex::new(HashMap::new())),
+               challenge_ttl: Duration::from_secs(60),
+               rematches: Arc::new(Mutex::new(HashMap::new())),
+               rematch_window: Duration::from_secs(30),
+               watchers: Arc::new(Mutex::new(HashMap::new())),
+               away: Arc::new(Mutex::new(HashMap::new())),
+               reconnect_grace: Duration::from_secs(30),
+               results: Vec::new() }
     }
 
-    pub async fn join(&self, user_id: Uuid) -> Result<(), GameError>
+    pub fn with_ratings(mut self, ratings: Arc<dyn RatingProvider>) -> Self
     {
-        if self.game_service.has_active_game(user_id).await {
-            if let Some(game) = self.game_service.get_game(user_id).await {
-                let opp_id = game.get_opp(&user_id).ok_or(GameError::NotFound)?;
-
-                let player_name = match self.notifier.get_name(user_id).await {
-                    None => {
-                        self.game_service.drop_for(user_id).await?;
-                        return Err(GameError::Disconnected);
-                    }
-                    Some(name) => name,
-                };
-
-                if let Some(opp_name) = self.notifier.get_name(opp_id).await {
-                    let msg = game.into_msg(user_id, &player_name, &opp_name);
-
-                    self.notifier.notify(user_id, msg).await;
-                    return Err(GameError::AlreadyInGame);
-                } else {
-                    self.game_service.drop_for(user_id).await?;
-                };
-            }
+        self.ratings = Some(ratings);
+        self
+    }
+
+    pub fn with_wallet(mut self, wallet: Arc<dyn WalletService>) -> Self
+    {
+        self.wallet = Some(wallet);
+        self
+    }
+
+    pub fn with_reconnect_grace(mut self, reconnect_grace: Duration) -> Self
+    {
+        self.reconnect_grace = reconnect_grace;
+        self
+    }
+
+    pub fn with_results(mut self, results: UnboundedSender<GameOutcome<G::FinishedGame>>) -> Self
+    {
+        self.results.push(results);
+        self
+    }
+
+    pub fn with_bots(mut self, bots: Vec<Arc<dyn Bot<G>>>, bot_wait: Duration) -> Self
+    {
+        self.bots = bots;
+        self.bot_wait = Some(bot_wait);
+        self
+    }
+
+    pub(crate) fn bot(&self, user_id: Uuid) -> Option<Arc<dyn Bot<G>>>
+    {
+        self.bots.iter().find(|b| b.id() == user_id).cloned()
+    }
+
+    /// Name and rating of a connected player, a player within their reconnect grace
+    /// period or a bot. Bots are never rated.
+    pub(crate) async fn tag_of(&self, user_id: Uuid) -> Option<PlayerTag>
+    {
+        if let Some(bot) = self.bot(user_id) {
+            return Some(PlayerTag { name: bot.name(),
+                                    rating: None });
         }
 
-        while let Some(opp_id) = self.player_queue.try_take().await {
-            if !self.notifier.is_online(opp_id).await || opp_id == user_id {
-                continue;
-            }
+        let name = match self.notifier.get_name(user_id).await {
+            Some(name) => name,
+            None => self.away_name(user_id).await?,
+        };
+        let rating = match &self.ratings {
+            Some(ratings) => ratings.rating(user_id).await,
+            None => None,
+        };
+
+        Some(PlayerTag { name, rating })
+    }
+
+    /// Record a finished game and publish its outcome.
+    pub(crate) async fn conclude(&self,
+                                 fin_game: &mut G::FinishedGame,
+                                 ranked: bool)
+                                 -> Result<(), GameError>
+    {
+        let recorded = self.recorder.record(fin_game, ranked).await;
+        self.publish(GameOutcome::Finished(fin_game.clone()));
+        recorded
+    }
+
+    pub(crate) fn publish(&self, outcome: GameOutcome<G::FinishedGame>)
+    {
+        for results in &self.results {
+            let _ = results.send(outcome.clone());
+        }
+    }
+
+    pub async fn join(&self, user_id: Uuid, settings: G::Settings) -> Result<(), GameError>
+    {
+        let player_queue = self.player_queues.get(&settings).ok_or(GameError::NotFound)?;
 
-            let active_game = self.game_service.start(user_id, opp_id).await;
+        // going back into the queue turns down any pending rematch
+        self.withdraw_rematch(user_id).await;
 
-            let player_name = match self.notifier.get_name(user_id).await {
+        if self.game_service.has_active_game(user_id).await
+           && let Some(game) = self.game_service.get_game(user_id).await
+        {
+            let opp_id = game.get_opp(&user_id).ok_or(GameError::NotFound)?;
+
+            let player = match self.tag_of(user_id).await {
                 None => {
                     self.game_service.drop_for(user_id).await?;
                     return Err(GameError::Disconnected);
                 }
-                Some(name) => name,
+                Some(tag) => tag,
             };
 
-            let opp_name = match self.notifier.get_name(opp_id).await {
-                None => {
-                    self.game_service.drop_for(user_id).await?;
-                    continue;
-                }
-                Some(name) => name,
+            if let Some(opp) = self.tag_of(opp_id).await {
+                let msg = game.to_msg(user_id, &player, &opp);
+
+                self.notifier.notify(user_id, msg).await;
+                return Err(GameError::AlreadyInGame);
+            } else {
+                self.game_service.drop_for(user_id).await?;
             };
+        }
+
+        player_queue.add(self.queued(user_id).await).await;
+
+        if let Some(bot_wait) = self.bot_wait {
+            let handler = self.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(bot_wait).await;
+                handler.match_with_bot(user_id, settings).await;
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Matchmaking metadata for a player about to enter a queue.
+    async fn queued(&self, user_id: Uuid) -> QueuedPlayer
+    {
+        let rating = match &self.ratings {
+            Some(ratings) => ratings.rating(user_id).await,
+            None => None,
+        };
+        let last_opp = self.last_opps.lock().await.get(&user_id).copied();
 
-            let msg = active_game.into_msg(user_id, &player_name, &opp_name);
+        QueuedPlayer { user_id,
+                       rating: rating.unwrap_or_default().rating,
+                       last_opp,
+                       stake: self.affordable_stake(user_id).await }
+    }
 
+    /// Stake coins on the next queued games. Bots and arranged games are never wagered.
+    pub async fn set_stake(&self, user_id: Uuid, stake: u32) -> Result<(), WalletError>
+    {
+        let wallet = self.wallet.as_ref().ok_or(WalletError::NoWagers)?;
+        if stake > 0 && wallet.balance(user_id).await? < stake as i64 {
+            return Err(WalletError::InsufficientFunds);
+        }
+
+        self.stakes.lock().await.insert(user_id, stake);
+        Ok(())
+    }
+
+    /// The player's stake, or none once their balance no longer covers it.
+    async fn affordable_stake(&self, user_id: Uuid) -> u32
+    {
+        let stake = self.stakes.lock().await.get(&user_id).copied().unwrap_or(0);
+        let Some(wallet) = &self.wallet else {
+            return 0;
+        };
+
+        match wallet.balance(user_id).await {
+            Ok(balance) if stake > 0 && balance >= stake as i64 => stake,
+            _ => 0,
+        }
+    }
+
+    /// Take the stake a matched pair queued with into escrow and tell them about it.
+    /// The game goes on without a wager if either can't pay it any more.
+    async fn hold_stakes(&self, pair: [Uuid; 2])
+    {
+        let Some(wallet) = &self.wallet else {
+            return;
+        };
+        let stakes = self.stakes.lock().await;
+        let stake = pair.iter()
+                        .map(|id| stakes.get(id).copied().unwrap_or(0))
+                        .min()
+                        .unwrap_or(0);
+        drop(stakes);
+
+        if stake == 0 || wallet.open_wager(pair, stake).await.is_err() {
+            return;
+        }
+        for id in pair {
+            if let Ok(balance) = wallet.balance(id).await {
+                let msg = ServerMsg::WalletMsg(WalletEvent::Staked { stake, balance });
+                self.notifier.notify(id, msg).await;
+            }
+        }
+    }
+
+    /// Start games for the pairs a matchmaking queue forms for the given settings.
+    pub fn listen_matches(&self, settings: G::Settings, mut matches: UnboundedReceiver<[Uuid; 2]>)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            while let Some(pair) = matches.recv().await {
+                handler.start_matched(pair, settings.clone()).await;
+            }
+        });
+    }
+
+    async fn start_matched(&self, pair: [Uuid; 2], settings: G::Settings)
+    {
+        let [user_id, opp_id] = pair;
+
+        let mut available = Vec::with_capacity(2);
+        for id in pair {
+            if self.notifier.is_online(id).await && !self.game_service.has_active_game(id).await {
+                available.push(id);
+            }
+        }
+
+        if available.len() < 2 {
+            // put whoever is still waiting back into the queue
+            if let Some(player_queue) = self.player_queues.get(&settings) {
+                for id in available {
+                    player_queue.add(self.queued(id).await).await;
+                }
+            }
+            return;
+        }
+
+        let (Some(player), Some(opp)) = (self.tag_of(user_id).await, self.tag_of(opp_id).await)
+        else {
+            return;
+        };
+
+        let active_game = match self.game_service.start(user_id, opp_id, settings).await {
+            Ok(game) => game,
+            Err(err) => {
+                let msg = ServerMsg::GameErrorMsg(err);
+                self.notifier.notify(user_id, msg.clone()).await;
+                self.notifier.notify(opp_id, msg).await;
+                return;
+            }
+        };
+        self.hold_stakes(pair).await;
+        let msg = active_game.to_msg(user_id, &player, &opp);
+
+        self.notifier.notify(user_id, msg.clone()).await;
+        self.notifier.notify(opp_id, msg).await;
+    }
+
+    /// Start a game between two players arranged outside the queues, e.g. by a tournament.
+    /// Both have to be online and free; they are taken out of anything else they wait for.
+    pub async fn start_arranged(&self,
+                                user_id: Uuid,
+                                opp_id: Uuid,
+                                settings: G::Settings)
+                                -> Result<G, GameError>
+    {
+        for id in [user_id, opp_id] {
+            if !self.notifier.is_online(id).await || self.game_service.has_active_game(id).await {
+                return Err(GameError::UserUnavailable);
+            }
+        }
+
+        let (Some(player), Some(opp)) = (self.tag_of(user_id).await, self.tag_of(opp_id).await)
+        else {
+            return Err(GameError::UserUnavailable);
+        };
+
+        for id in [user_id, opp_id] {
+            let _ = self.cancel_challenge(id).await;
+            self.withdraw_rematch(id).await;
+            for player_queue in self.player_queues.values() {
+                player_queue.remove(id).await;
+            }
+        }
+
+        let active_game = self.game_service.start(user_id, opp_id, settings).await?;
+        let msg = active_game.to_msg(user_id, &player, &opp);
+
+        self.notifier.notify(user_id, msg.clone()).await;
+        self.notifier.notify(opp_id, msg).await;
+
+        Ok(active_game)
+    }
+
+    /// Periodically end games whose move deadline has passed.
+    pub fn watch_deadlines(&self)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                for game in handler.game_service.take_timed_out().await {
+                    handler.finish_timed_out(game).await;
+                }
+            }
+        });
+    }
+
+    async fn finish_timed_out(&self, game: G)
+    {
+        let [user_id, opp_id] = game.players();
+
+        let Some(mut fin_game) = game.time_out() else {
+            let msg = ServerMsg::GameErrorMsg(GameError::TimedOut);
             self.notifier.notify(user_id, msg.clone()).await;
             self.notifier.notify(opp_id, msg).await;
-            return Ok(());
+            self.end_watching(&game).await;
+            self.publish(GameOutcome::Abandoned(game.players()));
+            return;
+        };
+
+        let ranked = self.bot(user_id).is_none() && self.bot(opp_id).is_none();
+        let _ = self.conclude(&mut fin_game, ranked).await;
+
+        if let (Some(player), Some(opp)) = (self.tag_of(user_id).await, self.tag_of(opp_id).await)
+        {
+            let msg = fin_game.to_msg(user_id, &player, &opp);
+            self.notifier.notify(user_id, msg.clone()).await;
+            self.notifier.notify(opp_id, msg.clone()).await;
+            self.broadcast_to_watchers(&game, &msg).await;
         }
+        self.end_watching(&game).await;
+    }
 
-        self.player_queue.add(user_id).await;
-        Ok(())
+    /// Pair a player who is still waiting in the queue with a random bot.
+    async fn match_with_bot(&self, user_id: Uuid, settings: G::Settings)
+    {
+        let Some(player_queue) = self.player_queues.get(&settings) else {
+            return;
+        };
+        if !player_queue.contains(user_id).await {
+            return;
+        }
+
+        // a bot plays one game at a time; with all of them busy the player waits for a person
+        let mut free_bots = Vec::new();
+        for bot in &self.bots {
+            if !self.game_service.has_active_game(bot.id()).await {
+                free_bots.push(bot.clone());
+            }
+        }
+        let Some(bot) = free_bots.choose(&mut rand::rng()).cloned() else {
+            return;
+        };
+
+        player_queue.remove(user_id).await;
+        if self.game_service.has_active_game(user_id).await {
+            return;
+        }
+
+        let (Some(player), Some(opp)) = (self.tag_of(user_id).await, self.tag_of(bot.id()).await)
+        else {
+            return;
+        };
+
+        let msg = match self.game_service.start(user_id, bot.id(), settings).await {
+            Ok(active_game) => active_game.to_msg(user_id, &player, &opp),
+            Err(err) => ServerMsg::GameErrorMsg(err),
+        };
+
+        self.notifier.notify(user_id, msg).await;
     }
 
     pub async fn submit(&self, user_id: Uuid, mv: G::Move) -> Result<(), GameError>
     {
-        let curr_game = self.game_service.submit_move(user_id, mv).await?;
+        let mut curr_game = self.game_service.submit_move(user_id, mv).await?;
         let opp_id = curr_game.get_opp(&user_id).ok_or(GameError::NotFound)?;
 
-        let player_name = match self.notifier.get_name(user_id).await {
+        let opp_bot = self.bot(opp_id);
+        if let Some(bot) = &opp_bot
+           && !curr_game.is_ready()
+           && curr_game.turn().is_none_or(|id| id == opp_id)
+        {
+            let bot_mv = bot.pick_move(&curr_game);
+            curr_game = self.game_service.submit_move(opp_id, bot_mv).await?;
+        }
+
+        let player = match self.tag_of(user_id).await {
             None => {
                 self.game_service.drop_for(user_id).await?;
                 return Err(GameError::Disconnected);
             }
-            Some(name) => name,
+            Some(tag) => tag,
         };
 
-        let opp_name = match self.notifier.get_name(opp_id).await {
+        let opp = match self.tag_of(opp_id).await {
             None => {
                 self.game_service.drop_for(user_id).await?;
                 return Err(GameError::Disconnected);
             }
-            Some(name) => name,
+            Some(tag) => tag,
         };
 
         if curr_game.is_ready() {
-            let fin_game = self.game_service
-                               .try_resolve(user_id)
-                               .await
-                               .ok_or(GameError::NotFound)?;
-            let _ = self.recorder.record(fin_game.clone()).await?;
+            let mut fin_game = self.game_service
+                                   .try_resolve(user_id)
+                                   .await
+                                   .ok_or(GameError::NotFound)?;
+            self.conclude(&mut fin_game, opp_bot.is_none()).await?;
+
+            let msg = fin_game.to_msg(user_id, &player, &opp);
 
-            let msg = fin_game.into_msg(user_id, &player_name, &opp_name);
+            let mut last_opps = self.last_opps.lock().await;
+            last_opps.insert(user_id, opp_id);
+            last_opps.insert(opp_id, user_id);
+            drop(last_opps);
 
             self.notifier.notify(user_id, msg.clone()).await;
-            self.notifier.notify(opp_id, msg).await;
+            self.notifier.notify(opp_id, msg.clone()).await;
+            self.broadcast_to_watchers(&curr_game, &msg).await;
+            self.end_watching(&curr_game).await;
+
+            self.open_rematch(user_id, opp_id, curr_game.settings()).await;
         } else {
-            let msg = curr_game.into_msg(user_id, &player_name, &opp_name);
+            let msg = curr_game.to_msg(user_id, &player, &opp);
 
             self.notifier.notify(user_id, msg.clone()).await;
-            self.notifier.notify(opp_id, msg).await;
+            self.notifier.notify(opp_id, msg.clone()).await;
+            self.broadcast_to_watchers(&curr_game, &msg).await;
         }
 
         Ok(())
@@ -133,15 +505,13 @@ impl<G> GameHandler<G> where G: ActiveGame
 
     pub async fn leave(&self, user_id: Uuid) -> Result<(), GameError>
     {
-        if let Some(game) = self.game_service.get_game(user_id).await {
-            let opp_id = game.get_opp(&user_id).ok_or(GameError::NotFound)?;
-
-            self.game_service.drop_for(user_id).await?;
-
-            let msg = ServerMsg::GameErrorMsg(GameError::Disconnected);
-            self.notifier.notify(opp_id, msg.clone()).await;
+        // leaving a running game loses it, so a losing player can't walk away with their stake
+        if self.game_service.has_active_game(user_id).await {
+            self.forfeit(user_id).await?;
         } else {
-            self.player_queue.remove(user_id).await;
+            for player_queue in self.player_queues.values() {
+                player_queue.remove(user_id).await;
+            }
         }
 
         Ok(())
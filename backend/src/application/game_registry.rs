@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use shared::{
+    game::{GameEnvelope, GameError, GameKind, GameReq},
+    room::RoomReq,
+    wallet::WalletEvent,
+    ws_messages::ServerMsg,
+};
+use uuid::Uuid;
+
+use crate::application::game_handler::GameHandler;
+use crate::domain::game_model::ActiveGame;
+
+/// What the websocket needs from a registered game, whatever its types.
+#[async_trait]
+pub trait GameEndpoint: Send + Sync
+{
+    async fn handle(&self, user_id: Uuid, envelope: &GameEnvelope) -> Result<(), GameError>;
+    async fn reconnected(&self, user_id: Uuid);
+    async fn disconnected(&self, user_id: Uuid, name: &str);
+}
+
+/// Rooms playing a registered game, with settings `S` for every game in a room.
+#[async_trait]
+pub trait RoomEndpoint<S>: Send + Sync
+{
+    /// Answer with room events, errors included.
+    async fn handle(&self, user_id: Uuid, req: RoomReq<S>);
+    async fn leave(&self, user_id: Uuid);
+}
+
+/// A `GameHandler` speaking the messages of its game's kind.
+struct KindEndpoint<G>
+    where G: ActiveGame
+{
+    handler: GameHandler<G>,
+    rooms: Option<Arc<dyn RoomEndpoint<G::Settings>>>,
+}
+
+#[async_trait]
+impl<G> GameEndpoint for KindEndpoint<G> where G: ActiveGame + 'static
+{
+    async fn handle(&self, user_id: Uuid, envelope: &GameEnvelope) -> Result<(), GameError>
+    {
+        let req = envelope.open_request::<G::Kind>().ok_or(GameError::InvalidMove)?;
+
+        match req {
+            GameReq::Start(settings) => self.handler.join(user_id, settings).await,
+            GameReq::Submit(mv) => self.handler.submit(user_id, mv).await,
+            GameReq::Leave => self.handler.leave(user_id).await,
+            GameReq::Rematch => self.handler.rematch(user_id).await,
+            GameReq::DeclineRematch => self.handler.decline_rematch(user_id).await,
+            GameReq::Challenge(req) => self.handler.handle_challenge(user_id, req).await,
+            GameReq::Spectate(req) => self.handler.handle_spectate(user_id, req).await,
+            GameReq::SetStake(stake) => {
+                let event = match self.handler.set_stake(user_id, stake).await {
+                    Ok(()) => WalletEvent::StakeSet(stake),
+                    Err(err) => WalletEvent::Error(err),
+                };
+                self.handler
+                    .notifier
+                    .notify(user_id, ServerMsg::WalletMsg(event))
+                    .await;
+                Ok(())
+            }
+            GameReq::Room(req) => {
+                let rooms = self.rooms.as_ref().ok_or(GameError::NotFound)?;
+                rooms.handle(user_id, req).await;
+                Ok(())
+            }
+        }
+    }
+
+    async fn reconnected(&self, user_id: Uuid)
+    {
+        self.handler.reconnected(user_id).await;
+    }
+
+    async fn disconnected(&self, user_id: Uuid, name: &str)
+    {
+        self.handler.disconnected(user_id, name).await;
+        if let Some(rooms) = &self.rooms {
+            rooms.leave(user_id).await;
+        }
+    }
+}
+
+/// Every game played through `GameMsg`, keyed by its `GameKind::KIND`. A game is added by
+/// registering its handler, which already holds its queues and recorder.
+#[derive(Clone, Default)]
+pub struct GameRegistry
+{
+    games: HashMap<&'static str, Arc<dyn GameEndpoint>>,
+}
+
+impl GameRegistry
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    pub fn register<G>(self, handler: GameHandler<G>) -> Self
+        where G: ActiveGame + 'static
+    {
+        let endpoint = KindEndpoint { handler,
+                                      rooms: None };
+        self.register_endpoint::<G::Kind>(Arc::new(endpoint))
+    }
+
+    /// Register a game that can also be played in rooms.
+    pub fn register_with_rooms<G>(self,
+                                  handler: GameHandler<G>,
+                                  rooms: Arc<dyn RoomEndpoint<G::Settings>>)
+                                  -> Self
+        where G: ActiveGame + 'static
+    {
+        let endpoint = KindEndpoint { handler,
+                                      rooms: Some(rooms) };
+        self.register_endpoint::<G::Kind>(Arc::new(endpoint))
+    }
+
+    /// Register a game that isn't played in pairs through a `GameHandler`.
+    pub fn register_endpoint<K: GameKind>(mut self, endpoint: Arc<dyn GameEndpoint>) -> Self
+    {
+        self.games.insert(K::KIND, endpoint);
+        self
+    }
+
+    pub async fn handle(&self, user_id: Uuid, envelope: &GameEnvelope) -> Result<(), GameError>
+    {
+        let game = self.games
+                       .get(envelope.kind.as_str())
+                       .ok_or(GameError::NotFound)?;
+        game.handle(user_id, envelope).await
+    }
+
+    pub async fn reconnected(&self, user_id: Uuid)
+    {
+        for game in self.games.values() {
+            game.reconnected(user_id).await;
+        }
+    }
+
+    /// Drop the user from every queue, lobby and room. Games are held until the grace
+    /// period ends.
+    pub async fn disconnected(&self, user_id: Uuid, name: &str)
+    {
+        for game in self.games.values() {
+            game.disconnected(user_id, name).await;
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::domain::rps_model::RpsArchive;
+use shared::{
+    game::GameError,
+    history::{HistoryPage, HistoryQuery},
+};
+use std::sync::Arc;
+
+const PAGE_SIZE: u32 = 25;
+
+pub struct HistoryHandler {
+    pub archive: Arc<dyn RpsArchive>,
+}
+
+impl HistoryHandler {
+    /// One page of `username`'s games, `None` if the user doesn't exist.
+    pub async fn history(
+        &self,
+        username: &str,
+        filter: &HistoryQuery,
+    ) -> Result<Option<HistoryPage>, GameError> {
+        // One extra row tells whether there is another page.
+        let Some(mut entries) = self.archive.history(username, filter, PAGE_SIZE + 1).await? else {
+            return Ok(None);
+        };
+
+        let next = if entries.len() > PAGE_SIZE as usize {
+            entries.truncate(PAGE_SIZE as usize);
+            entries.last().map(|e| e.id)
+        } else {
+            None
+        };
+
+        Ok(Some(HistoryPage { entries, next }))
+    }
+}
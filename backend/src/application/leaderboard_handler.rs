@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use shared::leaderboard::*;
+use uuid::Uuid;
+
+use crate::domain::leaderboard_model::LeaderboardService;
+
+const PAGE_SIZE: u32 = 20;
+/// Games a player needs before they are ranked by win rate, unless the query asks otherwise.
+const DEFAULT_MIN_GAMES: u32 = 10;
+
+pub struct LeaderboardHandler
+{
+    pub leaderboard_service: Arc<dyn LeaderboardService>,
+}
+
+fn window_start(window: LeaderboardWindow) -> Option<DateTime<Utc>>
+{
+    let span = match window {
+        LeaderboardWindow::Day => Duration::days(1),
+        LeaderboardWindow::Week => Duration::weeks(1),
+        LeaderboardWindow::Month => Duration::days(30),
+        LeaderboardWindow::AllTime => return None,
+    };
+    Some(Utc::now() - span)
+}
+
+impl LeaderboardHandler
+{
+    pub async fn page(&self,
+                      query: LeaderboardQuery,
+                      user_id: Option<Uuid>)
+                      -> Result<LeaderboardPage, LeaderboardError>
+    {
+        let min_games = query.min_games.unwrap_or(DEFAULT_MIN_GAMES).max(1);
+        let ranking = self.leaderboard_service
+                          .ranking(query.sort,
+                                   window_start(query.window),
+                                   min_games,
+                                   query.page.saturating_mul(PAGE_SIZE),
+                                   PAGE_SIZE,
+                                   user_id)
+                          .await?;
+
+        Ok(LeaderboardPage { entries: ranking.entries,
+                             page: query.page,
+                             pages: ranking.total.div_ceil(PAGE_SIZE),
+                             min_games,
+                             me: ranking.me })
+    }
+}
@@ -1,3 +1,20 @@
+pub mod achievement_handler;
 pub mod auth_handler;
+pub mod challenges;
+pub mod collusion_handler;
+pub mod fair_play_handler;
 pub mod forum_handler;
 pub mod game_handler;
+pub mod game_registry;
+pub mod history_handler;
+pub mod leaderboard_handler;
+pub mod profile_handler;
+pub mod quest_handler;
+pub mod reconnects;
+pub mod rematches;
+pub mod room_handler;
+pub mod royale_handler;
+pub mod season_handler;
+pub mod spectators;
+pub mod tournament_handler;
+pub mod wallet_handler;
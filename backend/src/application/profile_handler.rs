@@ -0,0 +1,46 @@
+use crate::domain::{
+    profile_model::{ProfileService, move_analytics},
+    rps_model::RpsArchive,
+};
+use shared::{
+    game::GameError,
+    history::HistoryQuery,
+    profile::{MoveAnalytics, PlayerProfile},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Recent games shown on a profile.
+const RECENT_GAMES: u32 = 10;
+/// Rounds move analytics look back over; older habits are likely gone.
+const ANALYSED_ROUNDS: u32 = 1000;
+
+pub struct ProfileHandler {
+    pub profile_service: Arc<dyn ProfileService>,
+    pub archive: Arc<dyn RpsArchive>,
+}
+
+impl ProfileHandler {
+    pub async fn profile(&self, username: &str) -> Result<Option<PlayerProfile>, GameError> {
+        let Some(mut profile) = self.profile_service.profile(username).await? else {
+            return Ok(None);
+        };
+
+        profile.recent = self
+            .archive
+            .history(username, &HistoryQuery::default(), RECENT_GAMES)
+            .await?
+            .unwrap_or_default();
+
+        Ok(Some(profile))
+    }
+
+    pub async fn move_analytics(&self, user_id: Uuid) -> Result<MoveAnalytics, GameError> {
+        let rounds = self
+            .profile_service
+            .rounds(user_id, ANALYSED_ROUNDS)
+            .await?;
+
+        Ok(move_analytics(&rounds))
+    }
+}
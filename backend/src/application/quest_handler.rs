@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{sync::Mutex, time::Duration};
+use uuid::Uuid;
+
+use crate::domain::{
+    game_model::{ActiveGame, FinishedGame, GameNotifier, GameRecorder},
+    quest_model::*,
+    rps_model::RpsWins,
+};
+use shared::{
+    game::{GameError, GameResult},
+    quest::*,
+    rps_game::RpsMove,
+    ws_messages::ServerMsg,
+};
+
+/// How often the quests of a new day or week are looked for.
+const TICK: Duration = Duration::from_secs(60);
+
+/// Generates the daily and weekly quests and moves players along on them.
+#[derive(Clone)]
+pub struct QuestHandler
+{
+    pub quest_service: Arc<dyn QuestService>,
+    pub notifier: Arc<dyn GameNotifier>,
+    /// Start of the latest period quests were generated for.
+    generated: Arc<Mutex<HashMap<QuestPeriod, DateTime<Utc>>>>,
+}
+
+impl QuestHandler
+{
+    pub fn new(quest_service: Arc<dyn QuestService>, notifier: Arc<dyn GameNotifier>) -> Self
+    {
+        Self { quest_service,
+               notifier,
+               generated: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// Generate the quests of every new period in the background.
+    pub fn run(&self)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                handler.generate().await;
+                tokio::time::sleep(TICK).await;
+            }
+        });
+    }
+
+    async fn generate(&self)
+    {
+        let now = Utc::now();
+        let mut generated = self.generated.lock().await;
+
+        for period in [QuestPeriod::Daily, QuestPeriod::Weekly] {
+            let bounds = period_bounds(period, now);
+            if generated.get(&period) == Some(&bounds.0) {
+                continue;
+            }
+
+            let quests = generate(period);
+            if self.quest_service
+                   .create(period, bounds, &quests)
+                   .await
+                   .is_ok()
+            {
+                generated.insert(period, bounds.0);
+            }
+        }
+    }
+
+    /// Wrap a game recorder so every game it records counts towards quests.
+    pub fn recorder<G>(&self, inner: Arc<dyn GameRecorder<G>>) -> Arc<dyn GameRecorder<G>>
+        where G: ActiveGame + 'static
+    {
+        Arc::new(QuestRecorder { inner,
+                                 quests: self.clone(),
+                                 won_with: |_| Default::default() })
+    }
+
+    /// Like `recorder`, for games played with RPS moves, which also count towards
+    /// quests about winning with a given move.
+    pub fn rps_recorder<G>(&self, inner: Arc<dyn GameRecorder<G>>) -> Arc<dyn GameRecorder<G>>
+        where G: ActiveGame + 'static,
+              G::FinishedGame: RpsWins
+    {
+        Arc::new(QuestRecorder { inner,
+                                 quests: self.clone(),
+                                 won_with: RpsWins::winning_moves })
+    }
+
+    pub async fn quests(&self, user_id: Uuid) -> Result<Vec<QuestInfo>, GameError>
+    {
+        self.quest_service.current(user_id).await
+    }
+
+    pub async fn posted(&self, user_id: Uuid)
+    {
+        let _ = self.progress(user_id, &[QuestAction::Posted]).await;
+    }
+
+    /// Count a recorded game towards the quests of both players.
+    /// `won_with` are the moves each player won an RPS round with.
+    pub async fn game_recorded<F: FinishedGame>(&self, fin_game: &F, won_with: [Vec<RpsMove>; 2])
+    {
+        let result = fin_game.resolve();
+        let results = [result, result.reverse()];
+
+        for ((user_id, result), won_with) in fin_game.players()
+                                                     .into_iter()
+                                                     .zip(results)
+                                                     .zip(won_with)
+        {
+            let mut actions = vec![QuestAction::Played];
+            if result == GameResult::Win {
+                actions.push(QuestAction::Won);
+            }
+            actions.extend(won_with.into_iter().map(QuestAction::WonWith));
+
+            let _ = self.progress(user_id, &actions).await;
+        }
+    }
+
+    async fn progress(&self, user_id: Uuid, actions: &[QuestAction]) -> Result<(), GameError>
+    {
+        for quest in self.quest_service.current(user_id).await? {
+            let amount = actions.iter()
+                                .filter(|action| counts(quest.goal, **action))
+                                .count() as u32;
+            if quest.completed || amount == 0 {
+                continue;
+            }
+
+            let (progress, completed) = self.quest_service
+                                            .advance(user_id, &quest, amount)
+                                            .await?;
+            let quest = QuestInfo { progress,
+                                    completed,
+                                    ..quest };
+            let event = if completed {
+                QuestEvent::Completed(quest)
+            } else {
+                QuestEvent::Progress(quest)
+            };
+            self.notifier
+                .notify(user_id, ServerMsg::QuestMsg(event))
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Records games as usual, then counts them towards the players' quests.
+struct QuestRecorder<G>
+    where G: ActiveGame
+{
+    inner: Arc<dyn GameRecorder<G>>,
+    quests: QuestHandler,
+    won_with: fn(&G::FinishedGame) -> [Vec<RpsMove>; 2],
+}
+
+#[async_trait]
+impl<G> GameRecorder<G> for QuestRecorder<G> where G: ActiveGame + 'static
+{
+    async fn record(&self, game: &mut G::FinishedGame, ranked: bool) -> Result<(), GameError>
+    {
+        self.inner.record(game, ranked).await?;
+        self.quests.game_recorded(game, (self.won_with)(game)).await;
+        Ok(())
+    }
+}
@@ -0,0 +1,130 @@
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::application::game_handler::GameHandler;
+use crate::domain::game_model::{ActiveGame, FinishedGame, GameOutcome};
+use shared::{
+    game::{ConnectionEvent, GameError},
+    ws_messages::ServerMsg,
+};
+
+/// A player whose socket dropped in the middle of a game.
+#[derive(Clone)]
+pub struct Away
+{
+    /// Kept so the game can still be presented while they are offline.
+    pub name: String,
+    pub expires_at: Instant,
+}
+
+impl<G> GameHandler<G> where G: ActiveGame + 'static
+{
+    /// Take a player who lost their last connection out of every queue and hold their
+    /// active game for `reconnect_grace`.
+    pub async fn disconnected(&self, user_id: Uuid, name: &str)
+    {
+        self.withdraw_rematch(user_id).await;
+        self.unwatch(user_id).await;
+        for player_queue in self.player_queues.values() {
+            player_queue.remove(user_id).await;
+        }
+
+        let Some(opp_id) = self.game_service.opponent_for(user_id).await else {
+            return;
+        };
+
+        let expires_at = Instant::now() + self.reconnect_grace;
+        self.away.lock().await.insert(user_id,
+                                      Away { name: name.to_string(),
+                                             expires_at });
+
+        let msg = ConnectionEvent::OpponentAway { grace_secs: self.reconnect_grace.as_secs() };
+        self.notifier.notify(opp_id, ServerMsg::ConnectionMsg(msg)).await;
+
+        let handler = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep_until(expires_at).await;
+            handler.expire_away(user_id, expires_at).await;
+        });
+    }
+
+    /// Push the full state of a held game to a player who came back in time.
+    pub async fn reconnected(&self, user_id: Uuid)
+    {
+        if self.away.lock().await.remove(&user_id).is_none() {
+            return;
+        }
+
+        let Some(game) = self.game_service.get_game(user_id).await else {
+            return;
+        };
+        let Some(opp_id) = game.get_opp(&user_id) else {
+            return;
+        };
+
+        if let (Some(player), Some(opp)) = (self.tag_of(user_id).await, self.tag_of(opp_id).await)
+        {
+            self.notifier
+                .notify(user_id, game.to_msg(user_id, &player, &opp))
+                .await;
+        }
+
+        let msg = ServerMsg::ConnectionMsg(ConnectionEvent::OpponentBack);
+        self.notifier.notify(opp_id, msg).await;
+    }
+
+    /// Name of a player who is offline but still within their grace period.
+    pub(crate) async fn away_name(&self, user_id: Uuid) -> Option<String>
+    {
+        self.away.lock().await.get(&user_id).map(|a| a.name.clone())
+    }
+
+    async fn expire_away(&self, user_id: Uuid, expires_at: Instant)
+    {
+        let still_away = self.away
+                             .lock()
+                             .await
+                             .get(&user_id)
+                             .is_some_and(|a| a.expires_at == expires_at);
+        if !still_away {
+            return;
+        }
+
+        // the name is still needed to present the forfeit to the opponent
+        let _ = self.forfeit(user_id).await;
+        self.away.lock().await.remove(&user_id);
+    }
+
+    /// End `user_id`'s game as lost by them, or abandon it if the game has no forfeit.
+    pub(crate) async fn forfeit(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        let game = self.game_service
+                       .get_game(user_id)
+                       .await
+                       .ok_or(GameError::NotFound)?;
+        let opp_id = game.get_opp(&user_id).ok_or(GameError::NotFound)?;
+
+        self.game_service.drop_for(user_id).await?;
+
+        let Some(mut fin_game) = game.forfeit(&user_id) else {
+            let msg = ServerMsg::GameErrorMsg(GameError::Disconnected);
+            self.notifier.notify(opp_id, msg).await;
+            self.end_watching(&game).await;
+            self.publish(GameOutcome::Abandoned(game.players()));
+            return Ok(());
+        };
+
+        let ranked = self.bot(opp_id).is_none();
+        let _ = self.conclude(&mut fin_game, ranked).await;
+
+        if let (Some(player), Some(opp)) = (self.tag_of(user_id).await, self.tag_of(opp_id).await)
+        {
+            let msg = fin_game.to_msg(opp_id, &opp, &player);
+            self.notifier.notify(opp_id, msg.clone()).await;
+            self.broadcast_to_watchers(&game, &msg).await;
+        }
+        self.end_watching(&game).await;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::application::game_handler::GameHandler;
+use crate::domain::game_model::ActiveGame;
+use shared::{game::GameError, game::RematchEvent, ws_messages::ServerMsg};
+
+/// One side of a rematch window opened after a game resolves.
+#[derive(Clone)]
+pub struct Rematch<S>
+{
+    pub opp: Uuid,
+    pub settings: S,
+    pub ready: bool,
+    pub expires_at: Instant,
+}
+
+impl<G> GameHandler<G> where G: ActiveGame + 'static
+{
+    /// Keep the pair together for a short while so they can agree on a rematch.
+    pub(crate) async fn open_rematch(&self, player: Uuid, opp: Uuid, settings: G::Settings)
+    {
+        let expires_at = Instant::now() + self.rematch_window;
+
+        {
+            let mut rematches = self.rematches.lock().await;
+            rematches.insert(player,
+                             Rematch { opp,
+                                       settings: settings.clone(),
+                                       ready: false,
+                                       expires_at });
+            rematches.insert(opp,
+                             Rematch { opp: player,
+                                       settings,
+                                       ready: false,
+                                       expires_at });
+        }
+
+        let handler = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep_until(expires_at).await;
+            handler.expire_rematch(player, expires_at).await;
+        });
+    }
+
+    /// Offer a rematch, or start it right away if the opponent already offered one.
+    pub async fn rematch(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        let (opp_id, settings, opp_ready) = {
+            let mut rematches = self.rematches.lock().await;
+
+            let entry = rematches.get_mut(&user_id)
+                                 .filter(|r| r.expires_at > Instant::now())
+                                 .ok_or(GameError::NotFound)?;
+            entry.ready = true;
+            let (opp_id, settings) = (entry.opp, entry.settings.clone());
+
+            let opp_ready = self.bot(opp_id).is_some()
+                            || rematches.get(&opp_id)
+                                        .is_some_and(|r| r.opp == user_id && r.ready);
+            if opp_ready {
+                rematches.remove(&user_id);
+                rematches.remove(&opp_id);
+            }
+
+            (opp_id, settings, opp_ready)
+        };
+
+        if !opp_ready {
+            let msg = ServerMsg::RematchMsg(RematchEvent::Offered);
+            self.notifier.notify(opp_id, msg).await;
+            return Ok(());
+        }
+
+        if self.game_service.has_active_game(user_id).await
+           || self.game_service.has_active_game(opp_id).await
+        {
+            return Err(GameError::UserUnavailable);
+        }
+
+        let player = self.tag_of(user_id).await.ok_or(GameError::Disconnected)?;
+        let opp = self.tag_of(opp_id).await.ok_or(GameError::Disconnected)?;
+
+        for player_queue in self.player_queues.values() {
+            player_queue.remove(user_id).await;
+            player_queue.remove(opp_id).await;
+        }
+
+        let active_game = self.game_service.start(user_id, opp_id, settings).await?;
+        let msg = active_game.to_msg(user_id, &player, &opp);
+
+        self.notifier.notify(user_id, msg.clone()).await;
+        self.notifier.notify(opp_id, msg).await;
+
+        Ok(())
+    }
+
+    pub async fn decline_rematch(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        if self.withdraw_rematch(user_id).await {
+            Ok(())
+        } else {
+            Err(GameError::NotFound)
+        }
+    }
+
+    /// Drop the pending rematch of `user_id`, telling the opponent it was declined.
+    pub async fn withdraw_rematch(&self, user_id: Uuid) -> bool
+    {
+        let removed = {
+            let mut rematches = self.rematches.lock().await;
+
+            let removed = rematches.remove(&user_id);
+            if let Some(r) = &removed
+               && rematches.get(&r.opp).is_some_and(|o| o.opp == user_id)
+            {
+                rematches.remove(&r.opp);
+            }
+            removed
+        };
+
+        match removed {
+            Some(r) => {
+                let msg = ServerMsg::RematchMsg(RematchEvent::Declined);
+                self.notifier.notify(r.opp, msg).await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    async fn expire_rematch(&self, player: Uuid, expires_at: Instant)
+    {
+        let expired = {
+            let mut rematches = self.rematches.lock().await;
+
+            match rematches.get(&player) {
+                Some(r) if r.expires_at == expires_at => {
+                    let r = rematches.remove(&player);
+                    if let Some(r) = &r
+                       && rematches.get(&r.opp).is_some_and(|o| o.expires_at == expires_at)
+                    {
+                        rematches.remove(&r.opp);
+                    }
+                    r
+                }
+                _ => None,
+            }
+        };
+
+        if let Some(r) = expired {
+            let msg = ServerMsg::RematchMsg(RematchEvent::Expired);
+            self.notifier.notify(player, msg.clone()).await;
+            self.notifier.notify(r.opp, msg).await;
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use std::sync::Arc;
+
+use actix::Addr;
+use async_trait::async_trait;
+use tokio::{sync::mpsc::UnboundedReceiver, time::Duration};
+use uuid::Uuid;
+
+use crate::application::{game_handler::GameHandler, game_registry::RoomEndpoint};
+use crate::domain::{
+    game_model::{ActiveGame, FinishedGame, GameOutcome},
+    room_model::RoomService,
+    rooms_actor::{self, RoomsActor},
+};
+use shared::{
+    game::{GameEnvelope, GameResult},
+    room::*,
+    rps_game::BestOf,
+    ws_messages::ServerMsg,
+};
+
+/// How often idle rooms start their next game.
+const TICK: Duration = Duration::from_secs(2);
+const MAX_NAME_LEN: usize = 32;
+
+/// Named rooms on top of a `GameHandler`: members play each other in rotation,
+/// winner stays on. Live room state is kept by `RoomsActor`.
+#[derive(Clone)]
+pub struct RoomHandler<G>
+    where G: ActiveGame<Settings = BestOf>
+{
+    pub room_service: Arc<dyn RoomService>,
+    pub rooms: Addr<RoomsActor>,
+    pub games: GameHandler<G>,
+}
+
+impl<G> RoomHandler<G> where G: ActiveGame<Settings = BestOf> + 'static
+{
+    pub fn new(room_service: Arc<dyn RoomService>,
+               rooms: Addr<RoomsActor>,
+               games: GameHandler<G>)
+               -> Self
+    {
+        Self { room_service,
+               rooms,
+               games }
+    }
+
+    /// Load the stored rooms, then start room games and follow their outcome in the background.
+    pub async fn run(&self, mut results: UnboundedReceiver<GameOutcome<G::FinishedGame>>)
+    {
+        if let Ok(rooms) = self.room_service.rooms().await {
+            self.rooms.do_send(rooms_actor::AddRooms { rooms });
+        }
+
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                handler.tick().await;
+            }
+        });
+
+        let handler = self.clone();
+        tokio::spawn(async move {
+            while let Some(outcome) = results.recv().await {
+                handler.on_outcome(outcome).await;
+            }
+        });
+    }
+
+    pub async fn list(&self) -> Vec<RoomSummary<BestOf>>
+    {
+        self.rooms.send(rooms_actor::ListRooms).await.unwrap_or_default()
+    }
+
+    /// State of the room the user is in, e.g. when they come back to it after a game.
+    pub async fn room_of(&self, user_id: Uuid) -> Option<RoomState<BestOf>>
+    {
+        let room_id = self.rooms.send(rooms_actor::MemberOf { user_id }).await.ok()??;
+        let (state, _) = self.rooms
+                             .send(rooms_actor::GetRoomState { room_id })
+                             .await
+                             .ok()??;
+        Some(state)
+    }
+
+    pub async fn create(&self,
+                        user_id: Uuid,
+                        name: &str,
+                        password: Option<&str>,
+                        best_of: BestOf)
+                        -> Result<(), RoomError>
+    {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+            return Err(RoomError::InvalidName);
+        }
+
+        let room = self.room_service
+                       .create(user_id, name, password, best_of)
+                       .await?;
+        let room_id = room.id;
+        self.rooms.do_send(rooms_actor::AddRooms { rooms: vec![room] });
+
+        self.enter(user_id, room_id).await
+    }
+
+    pub async fn join(&self,
+                      user_id: Uuid,
+                      room_id: i64,
+                      password: Option<&str>)
+                      -> Result<(), RoomError>
+    {
+        self.room_service
+            .check_password(room_id, password.unwrap_or_default())
+            .await?;
+        self.enter(user_id, room_id).await
+    }
+
+    async fn enter(&self, user_id: Uuid, room_id: i64) -> Result<(), RoomError>
+    {
+        let username = self.games
+                           .notifier
+                           .get_name(user_id)
+                           .await
+                           .ok_or(RoomError::NotFound)?;
+
+        let left = self.rooms
+                       .send(rooms_actor::JoinRoom { room_id,
+                                                     user_id,
+                                                     username })
+                       .await
+                       .map_err(|_e| RoomError::NotFound)??;
+
+        if let Some(left) = left {
+            self.announce(left).await;
+        }
+        self.announce(room_id).await;
+        Ok(())
+    }
+
+    pub async fn leave(&self, user_id: Uuid)
+    {
+        let left = self.rooms
+                       .send(rooms_actor::LeaveRoom { user_id })
+                       .await
+                       .ok()
+                       .flatten();
+
+        if let Some(room_id) = left {
+            self.games
+                .notifier
+                .notify(user_id, room_msg::<G>(&RoomEvent::Left))
+                .await;
+            self.announce(room_id).await;
+        }
+    }
+
+    /// Send the room state to all of its members.
+    async fn announce(&self, room_id: i64)
+    {
+        let state = self.rooms.send(rooms_actor::GetRoomState { room_id }).await;
+        let Ok(Some((state, members))) = state else {
+            return;
+        };
+
+        let msg = room_msg::<G>(&RoomEvent::State(state));
+        for member in members {
+            self.games.notifier.notify(member, msg.clone()).await;
+        }
+    }
+
+    async fn tick(&self)
+    {
+        let Ok(pairings) = self.rooms.send(rooms_actor::NextPairings).await else {
+            return;
+        };
+
+        for (room_id, players, best_of) in pairings {
+            let mut busy = false;
+            for id in players {
+                if !self.games.notifier.is_online(id).await
+                   || self.games.game_service.has_active_game(id).await
+                {
+                    self.rooms.do_send(rooms_actor::SkipMember { room_id,
+                                                                 user_id: id });
+                    busy = true;
+                }
+            }
+            if busy {
+                self.announce(room_id).await;
+                continue;
+            }
+
+            let [holder, challenger] = players;
+            if self.games
+                   .start_arranged(holder, challenger, best_of)
+                   .await
+                   .is_ok()
+            {
+                self.rooms.do_send(rooms_actor::GameStarted { room_id, players });
+                self.announce(room_id).await;
+            }
+        }
+    }
+
+    async fn on_outcome(&self, outcome: GameOutcome<G::FinishedGame>)
+    {
+        let (players, winner) = match &outcome {
+            GameOutcome::Finished(fin_game) => {
+                let players = fin_game.players();
+                let winner = match fin_game.resolve() {
+                    GameResult::Win => Some(players[0]),
+                    GameResult::Defeat => Some(players[1]),
+                    GameResult::Draw => None,
+                };
+                (players, winner)
+            }
+            GameOutcome::Abandoned(players) => (*players, None),
+        };
+
+        let room_id = self.rooms
+                          .send(rooms_actor::GameOver { players, winner })
+                          .await
+                          .ok()
+                          .flatten();
+        if let Some(room_id) = room_id {
+            self.announce(room_id).await;
+        }
+    }
+}
+
+#[async_trait]
+impl<G> RoomEndpoint<BestOf> for RoomHandler<G> where G: ActiveGame<Settings = BestOf> + 'static
+{
+    async fn handle(&self, user_id: Uuid, req: RoomReq<BestOf>)
+    {
+        let result = match req {
+            RoomReq::List => {
+                let rooms = self.list().await;
+                let mut events = vec![RoomEvent::List(rooms)];
+                if let Some(state) = self.room_of(user_id).await {
+                    events.push(RoomEvent::State(state));
+                }
+                for event in events {
+                    self.games.notifier.notify(user_id, room_msg::<G>(&event)).await;
+                }
+                return;
+            }
+            RoomReq::Create { name, password, settings } => {
+                self.create(user_id, &name, password.as_deref(), settings).await
+            }
+            RoomReq::Join { room_id, password } => {
+                self.join(user_id, room_id, password.as_deref()).await
+            }
+            RoomReq::Leave => {
+                RoomHandler::leave(self, user_id).await;
+                Ok(())
+            }
+        };
+
+        if let Err(err) = result {
+            self.games
+                .notifier
+                .notify(user_id, room_msg::<G>(&RoomEvent::Error(err)))
+                .await;
+        }
+    }
+
+    async fn leave(&self, user_id: Uuid)
+    {
+        RoomHandler::leave(self, user_id).await;
+    }
+}
+
+fn room_msg<G: ActiveGame>(event: &RoomEvent<G::Settings>) -> ServerMsg
+{
+    ServerMsg::RoomMsg(GameEnvelope::room::<G::Kind>(event))
+}
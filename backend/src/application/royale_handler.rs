@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::{sync::Mutex, time::Duration};
+use uuid::Uuid;
+
+use crate::application::game_registry::GameEndpoint;
+use crate::domain::{
+    game_model::GameNotifier,
+    royale_model::{RoyaleGame, RoyaleRecorder},
+};
+use shared::{
+    game::{GameEnvelope, GameError, GameReq},
+    royale::*,
+    rps_game::RpsMove,
+    ws_messages::ServerMsg,
+};
+
+/// How often the lobby countdown and round deadlines are checked.
+const TICK: Duration = Duration::from_secs(1);
+
+#[derive(Default)]
+struct Royales
+{
+    lobby: Vec<Uuid>,
+    /// Set while enough players wait, cleared when someone leaves below the minimum.
+    starts_at: Option<DateTime<Utc>>,
+    games: HashMap<u64, RoyaleGame>,
+    /// Game each player is currently in.
+    playing: HashMap<Uuid, u64>,
+    next_id: u64,
+}
+
+impl Royales
+{
+    /// Close the round once everyone still in has thrown, or anyway when `force`d after
+    /// the deadline. Games that are over are taken out.
+    fn settle(&mut self, game_id: u64, force: bool) -> Option<RoyaleGame>
+    {
+        let game = self.games.get_mut(&game_id)?;
+        if force || game.round_complete() {
+            game.resolve_round();
+        }
+        let game = game.clone();
+
+        if game.is_over() {
+            self.games.remove(&game_id);
+            for player in &game.players {
+                if self.playing.get(&player.id) == Some(&game_id) {
+                    self.playing.remove(&player.id);
+                }
+            }
+        }
+
+        Some(game)
+    }
+}
+
+/// Runs the N-player battle royale mode: a single lobby that fills up to
+/// `ROYALE_MAX_PLAYERS` and the games started from it.
+#[derive(Clone)]
+pub struct RoyaleHandler
+{
+    pub notifier: Arc<dyn GameNotifier>,
+    pub recorder: Arc<dyn RoyaleRecorder>,
+    pub move_time: chrono::Duration,
+    /// How long the lobby waits for more players once the minimum joined.
+    pub lobby_wait: chrono::Duration,
+    royales: Arc<Mutex<Royales>>,
+}
+
+impl RoyaleHandler
+{
+    pub fn new(notifier: Arc<dyn GameNotifier>,
+               recorder: Arc<dyn RoyaleRecorder>,
+               move_time: chrono::Duration)
+               -> Self
+    {
+        Self { notifier,
+               recorder,
+               move_time,
+               lobby_wait: chrono::Duration::seconds(20),
+               royales: Arc::new(Mutex::new(Royales::default())) }
+    }
+
+    pub fn with_lobby_wait(mut self, lobby_wait: chrono::Duration) -> Self
+    {
+        self.lobby_wait = lobby_wait;
+        self
+    }
+
+    pub async fn join(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        let mut royales = self.royales.lock().await;
+
+        if let Some(game) = royales.playing
+                                   .get(&user_id)
+                                   .and_then(|id| royales.games.get(id))
+        {
+            let msg = royale_msg(&game.event());
+            drop(royales);
+            self.notifier.notify(user_id, msg).await;
+            return Err(GameError::AlreadyInGame);
+        }
+
+        if !royales.lobby.contains(&user_id) {
+            royales.lobby.push(user_id);
+        }
+
+        if royales.lobby.len() >= ROYALE_MAX_PLAYERS {
+            let game = self.start_lobby(&mut royales).await;
+            drop(royales);
+            if let Some(game) = game {
+                self.publish(&game).await;
+            }
+            return Ok(());
+        }
+
+        if royales.lobby.len() >= ROYALE_MIN_PLAYERS && royales.starts_at.is_none() {
+            royales.starts_at = Some(Utc::now() + self.lobby_wait);
+        }
+        let (lobby, starts_at) = (royales.lobby.clone(), royales.starts_at);
+        drop(royales);
+
+        self.announce_lobby(lobby, starts_at).await;
+        Ok(())
+    }
+
+    pub async fn throw(&self, user_id: Uuid, mv: RpsMove) -> Result<(), GameError>
+    {
+        let mut royales = self.royales.lock().await;
+
+        let game_id = *royales.playing.get(&user_id).ok_or(GameError::NotFound)?;
+        royales.games
+               .get_mut(&game_id)
+               .ok_or(GameError::NotFound)?
+               .throw(&user_id, mv)?;
+        let game = royales.settle(game_id, false);
+        drop(royales);
+
+        if let Some(game) = game {
+            self.publish(&game).await;
+        }
+        Ok(())
+    }
+
+    /// Leave the lobby, or give up the current game: the player is knocked out when the
+    /// round closes. Disconnecting does the same, there is no grace period.
+    pub async fn leave(&self, user_id: Uuid)
+    {
+        let mut royales = self.royales.lock().await;
+
+        if let Some(game_id) = royales.playing.remove(&user_id) {
+            if let Some(game) = royales.games.get_mut(&game_id) {
+                game.leave(&user_id);
+            }
+            let game = royales.settle(game_id, false);
+            drop(royales);
+
+            if let Some(game) = game {
+                self.publish(&game).await;
+            }
+            return;
+        }
+
+        if !royales.lobby.contains(&user_id) {
+            return;
+        }
+        royales.lobby.retain(|id| *id != user_id);
+        if royales.lobby.len() < ROYALE_MIN_PLAYERS {
+            royales.starts_at = None;
+        }
+        let (lobby, starts_at) = (royales.lobby.clone(), royales.starts_at);
+        drop(royales);
+
+        self.announce_lobby(lobby, starts_at).await;
+    }
+
+    /// Start the lobby countdown and close rounds whose deadline passed, in the background.
+    pub fn run(&self)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                handler.tick().await;
+            }
+        });
+    }
+
+    async fn tick(&self)
+    {
+        let now = Utc::now();
+        let mut royales = self.royales.lock().await;
+        let mut updated = Vec::new();
+
+        if royales.starts_at.is_some_and(|t| t <= now) {
+            updated.extend(self.start_lobby(&mut royales).await);
+        }
+
+        let due: Vec<u64> = royales.games
+                                   .iter()
+                                   .filter(|(_, g)| g.deadline <= now)
+                                   .map(|(id, _)| *id)
+                                   .collect();
+        for game_id in due {
+            updated.extend(royales.settle(game_id, true));
+        }
+        drop(royales);
+
+        for game in updated {
+            self.publish(&game).await;
+        }
+    }
+
+    /// Turn the lobby into a game with everyone who is still online.
+    async fn start_lobby(&self, royales: &mut Royales) -> Option<RoyaleGame>
+    {
+        let lobby = std::mem::take(&mut royales.lobby);
+        royales.starts_at = None;
+
+        let mut players = Vec::with_capacity(lobby.len());
+        for id in lobby {
+            if let Some(name) = self.notifier.get_name(id).await {
+                players.push((id, name));
+            }
+        }
+
+        if players.len() < ROYALE_MIN_PLAYERS {
+            royales.lobby = players.into_iter().map(|(id, _)| id).collect();
+            let lobby = royales.lobby.clone();
+            let handler = self.clone();
+            tokio::spawn(async move { handler.announce_lobby(lobby, None).await });
+            return None;
+        }
+
+        let game_id = royales.next_id;
+        royales.next_id += 1;
+
+        let game = RoyaleGame::new(players, self.move_time);
+        for player in &game.players {
+            royales.playing.insert(player.id, game_id);
+        }
+        royales.games.insert(game_id, game.clone());
+
+        Some(game)
+    }
+
+    async fn announce_lobby(&self, lobby: Vec<Uuid>, starts_at: Option<DateTime<Utc>>)
+    {
+        let mut players = Vec::with_capacity(lobby.len());
+        for id in &lobby {
+            if let Some(name) = self.notifier.get_name(*id).await {
+                players.push(name);
+            }
+        }
+
+        let msg = royale_msg(&RoyaleEvent::Lobby { players, starts_at });
+        for id in lobby {
+            self.notifier.notify(id, msg.clone()).await;
+        }
+    }
+
+    /// Send the game state to everyone still following it and record it once it is over.
+    async fn publish(&self, game: &RoyaleGame)
+    {
+        let msg = royale_msg(&game.event());
+        for player in game.players.iter().filter(|p| !p.left) {
+            self.notifier.notify(player.id, msg.clone()).await;
+        }
+
+        if game.is_over() {
+            let _ = self.recorder.record_royale(game).await;
+        }
+    }
+}
+
+/// Royale is registered as its own game kind: `Start` joins the lobby, `Submit` throws.
+#[async_trait]
+impl GameEndpoint for RoyaleHandler
+{
+    async fn handle(&self, user_id: Uuid, envelope: &GameEnvelope) -> Result<(), GameError>
+    {
+        let req = envelope.open_request::<RoyaleKind>().ok_or(GameError::InvalidMove)?;
+
+        match req {
+            GameReq::Start(()) => self.join(user_id).await,
+            GameReq::Submit(mv) => self.throw(user_id, mv).await,
+            GameReq::Leave => {
+                self.leave(user_id).await;
+                Ok(())
+            }
+            _ => Err(GameError::NotFound),
+        }
+    }
+
+    async fn reconnected(&self, _user_id: Uuid) {}
+
+    async fn disconnected(&self, user_id: Uuid, _name: &str)
+    {
+        self.leave(user_id).await;
+    }
+}
+
+fn royale_msg(event: &RoyaleEvent) -> ServerMsg
+{
+    ServerMsg::GameMsg(GameEnvelope::state::<RoyaleKind>(event))
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::time::Duration;
+
+use crate::domain::{auth_model::User, season_model::*};
+use shared::season::*;
+
+/// How often ended seasons are looked for.
+const TICK: Duration = Duration::from_secs(60);
+const MAX_NAME_LEN: usize = 64;
+/// Rows of standings sent for a season.
+const MAX_STANDINGS: u32 = 100;
+
+/// Seasons set up by admins. Once one ends its standings are archived and
+/// ratings are soft-reset for the next.
+#[derive(Clone)]
+pub struct SeasonHandler
+{
+    pub season_service: Arc<dyn SeasonService>,
+}
+
+impl SeasonHandler
+{
+    /// Roll over ended seasons in the background.
+    pub fn run(&self)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let _ = handler.rollover().await;
+                tokio::time::sleep(TICK).await;
+            }
+        });
+    }
+
+    async fn rollover(&self) -> Result<(), SeasonError>
+    {
+        for id in self.season_service.ended().await? {
+            self.season_service.archive(id, SOFT_RESET).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn list(&self) -> Result<Vec<SeasonSummary>, SeasonError>
+    {
+        self.season_service.list().await
+    }
+
+    pub async fn details(&self, id: i64) -> Result<SeasonDetails, SeasonError>
+    {
+        self.season_service.details(id, MAX_STANDINGS).await
+    }
+
+    pub async fn create(&self, user: &User, mut new: NewSeason) -> Result<i64, SeasonError>
+    {
+        if user.role != "admin" {
+            return Err(SeasonError::Forbidden);
+        }
+
+        new.name = new.name.trim().to_string();
+        if new.name.is_empty() || new.name.chars().count() > MAX_NAME_LEN {
+            return Err(SeasonError::InvalidName);
+        }
+        if new.ends_at <= new.starts_at || new.ends_at <= Utc::now() {
+            return Err(SeasonError::InvalidDates);
+        }
+
+        self.season_service.create(user.id, &new).await
+    }
+}
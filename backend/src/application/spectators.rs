@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use uuid::Uuid;
+
+use crate::application::game_handler::GameHandler;
+use crate::domain::game_model::{ActiveGame, GameId};
+use shared::{
+    game::GameError,
+    spectate::{LiveGame, SpectateEvent, SpectateReq},
+    ws_messages::ServerMsg,
+};
+
+/// Spectators of one live game. Keyed by the game's first player rather than its id,
+/// since ids are reused once a game is over.
+#[derive(Clone)]
+pub struct Watchers
+{
+    pub game_id: GameId,
+    pub users: HashSet<Uuid>,
+}
+
+impl<G> GameHandler<G> where G: ActiveGame + 'static
+{
+    pub async fn handle_spectate(&self, user_id: Uuid, req: SpectateReq) -> Result<(), GameError>
+    {
+        match req {
+            SpectateReq::List => {
+                let games = self.live_games().await;
+                let msg = ServerMsg::SpectateMsg(SpectateEvent::Games(games));
+                self.notifier.notify(user_id, msg).await;
+            }
+            SpectateReq::Watch(game_id) => self.watch(user_id, game_id).await?,
+            SpectateReq::Unwatch => self.unwatch(user_id).await,
+        }
+
+        Ok(())
+    }
+
+    pub async fn live_games(&self) -> Vec<LiveGame>
+    {
+        let mut live = Vec::new();
+
+        for (game_id, game) in self.game_service.list_games().await {
+            let [p1, p2] = game.players();
+            if let (Some(p1), Some(p2)) = (self.tag_of(p1).await, self.tag_of(p2).await) {
+                live.push(LiveGame { id: game_id as u64,
+                                     players: [p1.name, p2.name] });
+            }
+        }
+
+        live
+    }
+
+    /// Subscribe to a live game and get its current state right away.
+    pub async fn watch(&self, user_id: Uuid, game_id: u64) -> Result<(), GameError>
+    {
+        let game_id = game_id as GameId;
+        let game = self.game_service
+                       .get_game_by_id(game_id)
+                       .await
+                       .ok_or(GameError::NotFound)?;
+        let [p1, p2] = game.players();
+        if game.has_player(&user_id) {
+            return Err(GameError::AlreadyInGame);
+        }
+
+        let (Some(tag1), Some(tag2)) = (self.tag_of(p1).await, self.tag_of(p2).await) else {
+            return Err(GameError::NotFound);
+        };
+
+        self.unwatch(user_id).await;
+        self.watchers
+            .lock()
+            .await
+            .entry(p1)
+            .or_insert_with(|| Watchers { game_id,
+                                          users: HashSet::new() })
+            .users
+            .insert(user_id);
+
+        let msg = game.to_msg(p1, &tag1, &tag2);
+        self.notifier.notify(user_id, spectator_msg(game_id, msg)).await;
+
+        Ok(())
+    }
+
+    pub async fn unwatch(&self, user_id: Uuid)
+    {
+        let mut watchers = self.watchers.lock().await;
+        for w in watchers.values_mut() {
+            w.users.remove(&user_id);
+        }
+        watchers.retain(|_, w| !w.users.is_empty());
+    }
+
+    /// Forward a state the players just got to everyone watching their game.
+    pub(crate) async fn broadcast_to_watchers(&self, game: &G, msg: &ServerMsg)
+    {
+        let [p1, _] = game.players();
+        let Some(w) = self.watchers.lock().await.get(&p1).cloned() else {
+            return;
+        };
+
+        let msg = spectator_msg(w.game_id, msg.clone());
+        for user_id in w.users {
+            self.notifier.notify(user_id, msg.clone()).await;
+        }
+    }
+
+    /// Tell the spectators the game is over and drop their subscription.
+    pub(crate) async fn end_watching(&self, game: &G)
+    {
+        let [p1, _] = game.players();
+        let Some(w) = self.watchers.lock().await.remove(&p1) else {
+            return;
+        };
+
+        let msg = ServerMsg::SpectateMsg(SpectateEvent::Ended { game_id: w.game_id as u64 });
+        for user_id in w.users {
+            self.notifier.notify(user_id, msg.clone()).await;
+        }
+    }
+}
+
+fn spectator_msg(game_id: GameId, msg: ServerMsg) -> ServerMsg
+{
+    ServerMsg::SpectateMsg(SpectateEvent::Update { game_id: game_id as u64,
+                                                   msg: Box::new(msg) })
+}
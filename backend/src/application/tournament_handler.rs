@@ -0,0 +1,384 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::Utc;
+use tokio::{
+    sync::{mpsc::UnboundedReceiver, Mutex},
+    time::Duration,
+};
+use uuid::Uuid;
+
+use crate::application::game_handler::GameHandler;
+use crate::domain::{
+    auth_model::User,
+    game_model::{ActiveGame, FinishedGame, GameOutcome},
+    tournament_model::*,
+};
+use shared::{
+    game::GameResult, rps_game::BestOf, tournament::*, ws_messages::ServerMsg,
+};
+
+/// How often due tournaments are started and pending matches retried.
+const TICK: Duration = Duration::from_secs(5);
+/// A player who can't be reached this long after their match is due loses it.
+const NO_SHOW: chrono::Duration = chrono::Duration::minutes(3);
+/// A match still marked as playing this long without a game left behind is replayed.
+const STALE: chrono::Duration = chrono::Duration::minutes(15);
+/// Abandoned games replayed before the match is decided without one.
+const MAX_ATTEMPTS: u32 = 3;
+const MAX_NAME_LEN: usize = 64;
+const MAX_START_DAYS: i64 = 30;
+
+/// Runs tournaments on top of a `GameHandler`: starts them once sign-up closes,
+/// schedules every match through the game service and advances players on results.
+#[derive(Clone)]
+pub struct TournamentHandler<G>
+    where G: ActiveGame<Settings = BestOf>
+{
+    pub tournament_service: Arc<dyn TournamentService>,
+    pub games: GameHandler<G>,
+    /// Keeps the scheduler and result handling from advancing a round twice.
+    busy: Arc<Mutex<()>>,
+}
+
+fn is_staff(user: &User) -> bool
+{
+    user.role == "admin" || user.role == "moderator"
+}
+
+impl<G> TournamentHandler<G> where G: ActiveGame<Settings = BestOf> + 'static
+{
+    pub fn new(tournament_service: Arc<dyn TournamentService>, games: GameHandler<G>) -> Self
+    {
+        Self { tournament_service,
+               games,
+               busy: Arc::new(Mutex::new(())) }
+    }
+
+    pub async fn list(&self) -> Result<Vec<TournamentSummary>, TournamentError>
+    {
+        self.tournament_service.list().await
+    }
+
+    pub async fn details(&self, user: &User, id: i64) -> Result<TournamentDetails, TournamentError>
+    {
+        let mut details = self.tournament_service.details(id, user.id).await?;
+        details.can_cancel |= is_staff(user);
+        details.can_cancel &= details.summary.status == TournamentStatus::Open;
+        Ok(details)
+    }
+
+    pub async fn create(&self, user_id: Uuid, mut new: NewTournament) -> Result<i64, TournamentError>
+    {
+        new.name = new.name.trim().to_string();
+        if new.name.is_empty() || new.name.chars().count() > MAX_NAME_LEN {
+            return Err(TournamentError::InvalidName);
+        }
+
+        let now = Utc::now();
+        if new.starts_at < now - chrono::Duration::minutes(1)
+           || new.starts_at > now + chrono::Duration::days(MAX_START_DAYS)
+        {
+            return Err(TournamentError::InvalidStart);
+        }
+
+        self.tournament_service.create(user_id, &new).await
+    }
+
+    pub async fn join(&self, id: i64, user_id: Uuid) -> Result<(), TournamentError>
+    {
+        self.tournament_service.join(id, user_id).await
+    }
+
+    pub async fn leave(&self, id: i64, user_id: Uuid) -> Result<(), TournamentError>
+    {
+        self.tournament_service.leave(id, user_id).await
+    }
+
+    pub async fn cancel(&self, user: &User, id: i64) -> Result<(), TournamentError>
+    {
+        let tournament = self.tournament_service.tournament(id).await?;
+
+        if tournament.created_by != Some(user.id) && !is_staff(user) {
+            return Err(TournamentError::Forbidden);
+        }
+        if tournament.status != TournamentStatus::Open {
+            return Err(TournamentError::NotOpen);
+        }
+
+        self.tournament_service
+            .set_status(id, TournamentStatus::Cancelled)
+            .await
+    }
+
+    /// Drive tournaments in the background and follow the outcome of their games.
+    pub fn run(&self, mut results: UnboundedReceiver<GameOutcome<G::FinishedGame>>)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(TICK).await;
+                let _ = handler.tick().await;
+            }
+        });
+
+        let handler = self.clone();
+        tokio::spawn(async move {
+            while let Some(outcome) = results.recv().await {
+                let _ = handler.on_outcome(outcome).await;
+            }
+        });
+    }
+
+    async fn tick(&self) -> Result<(), TournamentError>
+    {
+        let _busy = self.busy.lock().await;
+
+        for tournament in self.tournament_service.due().await? {
+            let _ = self.start_tournament(tournament).await;
+        }
+        for tournament in self.tournament_service.running().await? {
+            let _ = self.advance(&tournament).await;
+        }
+
+        Ok(())
+    }
+
+    /// Seed the players by rating and schedule the first round.
+    async fn start_tournament(&self, tournament: Tournament) -> Result<(), TournamentError>
+    {
+        let entrants = self.tournament_service.entrants(tournament.id).await?;
+        if entrants.len() < 2 {
+            return self.tournament_service
+                       .set_status(tournament.id, TournamentStatus::Cancelled)
+                       .await;
+        }
+
+        let mut rated = Vec::with_capacity(entrants.len());
+        for entrant in &entrants {
+            let rating = match &self.games.ratings {
+                Some(ratings) => ratings.rating(entrant.user_id).await,
+                None => None,
+            };
+            rated.push((entrant.user_id, rating.unwrap_or_default().rating));
+        }
+        rated.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let seeds: Vec<Uuid> = rated.into_iter().map(|(id, _)| id).collect();
+
+        let rounds = match tournament.format {
+            TournamentFormat::SingleElimination => elimination_rounds(seeds.len()),
+            TournamentFormat::Swiss => swiss_rounds(seeds.len()),
+        };
+        self.tournament_service
+            .start(tournament.id, &seeds, rounds)
+            .await?;
+
+        let msg = TournamentEvent::Started { id: tournament.id,
+                                             name: tournament.name.clone() };
+        for user_id in &seeds {
+            self.games
+                .notifier
+                .notify(*user_id, ServerMsg::TournamentMsg(msg.clone()))
+                .await;
+        }
+
+        self.schedule_round(&tournament, 1).await
+    }
+
+    async fn schedule_round(&self, tournament: &Tournament, round: u32) -> Result<(), TournamentError>
+    {
+        let entrants = self.tournament_service.entrants(tournament.id).await?;
+        let matches = self.tournament_service.matches(tournament.id).await?;
+
+        let pairings = match tournament.format {
+            TournamentFormat::SingleElimination if round == 1 => {
+                let seeded: Vec<Uuid> = entrants.iter().map(|e| e.user_id).collect();
+                elimination_first_round(&seeded)
+            }
+            TournamentFormat::SingleElimination => {
+                let winners: Vec<Uuid> = matches.iter()
+                                                .filter(|m| m.round == round - 1)
+                                                .filter_map(|m| m.winner)
+                                                .collect();
+                elimination_next_round(&winners)
+            }
+            TournamentFormat::Swiss => {
+                let played: HashSet<(Uuid, Uuid)> =
+                    matches.iter()
+                           .filter_map(|m| m.player2.map(|p2| (m.player1, p2)))
+                           .collect();
+                let had_bye: HashSet<Uuid> = matches.iter()
+                                                    .filter(|m| m.player2.is_none())
+                                                    .map(|m| m.player1)
+                                                    .collect();
+                swiss_pairings(&entrants, &played, &had_bye)
+            }
+        };
+
+        self.tournament_service
+            .add_round(tournament.id, round, &pairings)
+            .await
+    }
+
+    /// Start what can be started in the current round, or move on once it is complete.
+    async fn advance(&self, tournament: &Tournament) -> Result<(), TournamentError>
+    {
+        let matches = self.tournament_service.matches(tournament.id).await?;
+        let current: Vec<&ScheduledMatch> = matches.iter()
+                                                   .filter(|m| m.round == tournament.current_round)
+                                                   .collect();
+
+        if current.iter().all(|m| m.status == MatchStatus::Done) {
+            let winners = current.iter().filter(|m| m.winner.is_some()).count();
+            let last_round = match tournament.format {
+                TournamentFormat::SingleElimination => winners <= 1,
+                TournamentFormat::Swiss => tournament.current_round >= tournament.rounds,
+            };
+
+            return if last_round {
+                self.finish(tournament).await
+            } else {
+                self.schedule_round(tournament, tournament.current_round + 1)
+                    .await
+            };
+        }
+
+        let now = Utc::now();
+        for m in current {
+            if m.status == MatchStatus::Pending {
+                self.try_start(tournament, m).await?;
+            } else if m.status == MatchStatus::Playing
+                      && now - m.since > STALE
+                      // the game is gone without a result reaching us, e.g. to a restart
+                      && self.games.game_service.opponent_for(m.player1).await != m.player2
+            {
+                self.replay(tournament, m).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn try_start(&self, tournament: &Tournament, m: &ScheduledMatch) -> Result<(), TournamentError>
+    {
+        let Some(player2) = m.player2 else {
+            // a bye counts as a win
+            return self.tournament_service
+                       .record_result(m.id, Some(m.player1))
+                       .await;
+        };
+
+        if self.games
+               .start_arranged(m.player1, player2, tournament.best_of)
+               .await
+               .is_ok()
+        {
+            self.tournament_service.set_playing(m.id).await?;
+
+            for (user_id, opp_id) in [(m.player1, player2), (player2, m.player1)] {
+                let Some(opp) = self.games.tag_of(opp_id).await else {
+                    continue;
+                };
+                let msg = TournamentEvent::MatchReady { id: tournament.id,
+                                                        name: tournament.name.clone(),
+                                                        round: m.round,
+                                                        opponent: opp.name };
+                self.games
+                    .notifier
+                    .notify(user_id, ServerMsg::TournamentMsg(msg))
+                    .await;
+            }
+            return Ok(());
+        }
+
+        if Utc::now() - m.since < NO_SHOW {
+            return Ok(());
+        }
+
+        // someone did not show up: whoever is around wins, busy players are waited for
+        let online = [self.games.notifier.is_online(m.player1).await,
+                      self.games.notifier.is_online(player2).await];
+        let winner = match (online, tournament.format) {
+            ([true, false], _) => Some(m.player1),
+            ([false, true], _) => Some(player2),
+            ([false, false], TournamentFormat::SingleElimination) => Some(m.player1),
+            ([false, false], TournamentFormat::Swiss) => None,
+            ([true, true], _) => return Ok(()),
+        };
+
+        self.tournament_service.record_result(m.id, winner).await
+    }
+
+    async fn on_outcome(&self, outcome: GameOutcome<G::FinishedGame>) -> Result<(), TournamentError>
+    {
+        let _busy = self.busy.lock().await;
+
+        let players = match &outcome {
+            GameOutcome::Finished(fin_game) => fin_game.players(),
+            GameOutcome::Abandoned(players) => *players,
+        };
+        let Some(m) = self.tournament_service.playing_match(players).await? else {
+            return Ok(());
+        };
+        let tournament = self.tournament_service.tournament(m.tournament_id).await?;
+
+        let GameOutcome::Finished(fin_game) = outcome else {
+            return self.replay(&tournament, &m).await;
+        };
+
+        let winner = match fin_game.resolve() {
+            GameResult::Win => Some(players[0]),
+            GameResult::Defeat => Some(players[1]),
+            GameResult::Draw => None,
+        };
+
+        if winner.is_none() && tournament.format == TournamentFormat::SingleElimination {
+            return self.replay(&tournament, &m).await;
+        }
+
+        self.tournament_service.record_result(m.id, winner).await
+    }
+
+    /// Play an abandoned or undecided match again, or settle it once that happened too often.
+    async fn replay(&self, tournament: &Tournament, m: &ScheduledMatch) -> Result<(), TournamentError>
+    {
+        if m.attempts + 1 < MAX_ATTEMPTS {
+            return self.tournament_service.reschedule(m.id).await;
+        }
+
+        let winner = match (tournament.format, m.player2) {
+            (TournamentFormat::SingleElimination, Some(player2)) => {
+                Some(if rand::random_bool(0.5) { m.player1 } else { player2 })
+            }
+            (TournamentFormat::SingleElimination, None) => Some(m.player1),
+            (TournamentFormat::Swiss, _) => None,
+        };
+
+        self.tournament_service.record_result(m.id, winner).await
+    }
+
+    async fn finish(&self, tournament: &Tournament) -> Result<(), TournamentError>
+    {
+        self.tournament_service
+            .set_status(tournament.id, TournamentStatus::Finished)
+            .await?;
+
+        // standings are ordered best first, eliminated players last
+        let details = self.tournament_service
+                          .details(tournament.id, Uuid::nil())
+                          .await?;
+        let winner = details.standings.first().map(|s| s.username.clone());
+
+        let msg = TournamentEvent::Finished { id: tournament.id,
+                                              name: tournament.name.clone(),
+                                              winner };
+        for entrant in self.tournament_service.entrants(tournament.id).await? {
+            self.games
+                .notifier
+                .notify(entrant.user_id, ServerMsg::TournamentMsg(msg.clone()))
+                .await;
+        }
+
+        Ok(())
+    }
+}
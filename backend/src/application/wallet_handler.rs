@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use tokio::{sync::mpsc::UnboundedReceiver, time::Duration};
+use uuid::Uuid;
+
+use crate::domain::{
+    game_model::{FinishedGame, GameNotifier, GameOutcome},
+    wallet_model::*,
+};
+use shared::{
+    wallet::{WalletError, WalletEvent},
+    ws_messages::ServerMsg,
+};
+
+/// How often wagers left open are looked for.
+const TICK: Duration = Duration::from_secs(5 * 60);
+/// No game runs this long, so a wager still open after it lost its game, e.g. to a restart.
+const STALE: chrono::Duration = chrono::Duration::hours(1);
+
+/// Hands out the daily allowance and keeps players up to date on their balance as
+/// their wagered games end.
+#[derive(Clone)]
+pub struct WalletHandler
+{
+    pub wallet_service: Arc<dyn WalletService>,
+    pub notifier: Arc<dyn GameNotifier>,
+}
+
+impl WalletHandler
+{
+    pub fn new(wallet_service: Arc<dyn WalletService>, notifier: Arc<dyn GameNotifier>) -> Self
+    {
+        Self { wallet_service,
+               notifier }
+    }
+
+    /// Refund wagers whose game is gone in the background.
+    pub fn run(&self)
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            loop {
+                let _ = handler.wallet_service.refund_stale(STALE).await;
+                tokio::time::sleep(TICK).await;
+            }
+        });
+    }
+
+    /// Winners are paid when the game is recorded, so finished games only need the new
+    /// balances sent. Abandoned games give the stakes back.
+    pub fn follow<F>(&self, mut results: UnboundedReceiver<GameOutcome<F>>)
+        where F: FinishedGame + 'static
+    {
+        let handler = self.clone();
+        tokio::spawn(async move {
+            while let Some(outcome) = results.recv().await {
+                let players = match outcome {
+                    GameOutcome::Finished(fin_game) => fin_game.players(),
+                    GameOutcome::Abandoned(players) => {
+                        let _ = handler.wallet_service.refund_wager(players).await;
+                        players
+                    }
+                };
+                for user_id in players {
+                    handler.notify_balance(user_id).await;
+                }
+            }
+        });
+    }
+
+    /// Daily check-in: credit today's allowance unless the user already got it, and
+    /// send the balance to their open connections.
+    pub async fn check_in(&self, user_id: Uuid) -> Result<(), WalletError>
+    {
+        self.wallet_service
+            .grant_allowance(user_id, DAILY_ALLOWANCE)
+            .await?;
+        self.notify_balance(user_id).await;
+        Ok(())
+    }
+
+    pub async fn balance(&self, user_id: Uuid) -> Result<i64, WalletError>
+    {
+        self.wallet_service.balance(user_id).await
+    }
+
+    async fn notify_balance(&self, user_id: Uuid)
+    {
+        if let Ok(balance) = self.wallet_service.balance(user_id).await {
+            self.notifier
+                .notify(user_id, ServerMsg::WalletMsg(WalletEvent::Balance(balance)))
+                .await;
+        }
+    }
+}
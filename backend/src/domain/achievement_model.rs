@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use shared::{achievement::*, game::GameError};
+use uuid::Uuid;
+
+/// Numbers about a player that achievements are unlocked by.
+#[derive(Clone, Default, Debug)]
+pub struct PlayerStats
+{
+    pub wins: u32,
+    pub games: u32,
+    pub best_streak: u32,
+    /// Rounds won with each move, in `RpsMove::ALL` order.
+    pub wins_by_move: [u32; 3],
+    /// Likes on the player's most liked forum post.
+    pub top_post_likes: u32,
+}
+
+/// What it takes to unlock an achievement.
+#[derive(Clone, Copy, Debug)]
+pub enum Goal
+{
+    Wins(u32),
+    WinStreak(u32),
+    GamesPlayed(u32),
+    WinWithEachMove,
+    PostLikes(u32),
+}
+
+impl Goal
+{
+    pub fn reached(&self, stats: &PlayerStats) -> bool
+    {
+        match *self {
+            Goal::Wins(n) => stats.wins >= n,
+            Goal::WinStreak(n) => stats.best_streak >= n,
+            Goal::GamesPlayed(n) => stats.games >= n,
+            Goal::WinWithEachMove => stats.wins_by_move.iter().all(|wins| *wins > 0),
+            Goal::PostLikes(n) => stats.top_post_likes >= n,
+        }
+    }
+}
+
+pub struct Definition
+{
+    pub achievement: Achievement,
+    /// Stored with every unlock, so it must never change.
+    pub key: &'static str,
+    pub goal: Goal,
+}
+
+/// Every achievement there is. A new one only needs a line here, as long as its goal
+/// is made of the stats in `PlayerStats`.
+pub const ACHIEVEMENTS: [Definition; 5] = [
+    Definition { achievement: Achievement::FirstWin,
+                 key: "first_win",
+                 goal: Goal::Wins(1) },
+    Definition { achievement: Achievement::WinStreak,
+                 key: "win_streak_10",
+                 goal: Goal::WinStreak(10) },
+    Definition { achievement: Achievement::Veteran,
+                 key: "games_100",
+                 goal: Goal::GamesPlayed(100) },
+    Definition { achievement: Achievement::AllRounder,
+                 key: "win_each_move",
+                 goal: Goal::WinWithEachMove },
+    Definition { achievement: Achievement::CrowdFavourite,
+                 key: "post_likes_10",
+                 goal: Goal::PostLikes(10) },
+];
+
+pub fn key_of(achievement: Achievement) -> &'static str
+{
+    ACHIEVEMENTS.iter()
+                .find(|d| d.achievement == achievement)
+                .map(|d| d.key)
+                .unwrap_or_default()
+}
+
+pub fn from_key(key: &str) -> Option<Achievement>
+{
+    ACHIEVEMENTS.iter()
+                .find(|d| d.key == key)
+                .map(|d| d.achievement)
+}
+
+/// Achievements whose goal the stats reach.
+pub fn earned(stats: &PlayerStats) -> Vec<Achievement>
+{
+    ACHIEVEMENTS.iter()
+                .filter(|d| d.goal.reached(stats))
+                .map(|d| d.achievement)
+                .collect()
+}
+
+#[async_trait]
+pub trait AchievementService: Send + Sync
+{
+    async fn stats(&self, user_id: Uuid) -> Result<PlayerStats, GameError>;
+    /// Store the achievements, answering with the ones the user didn't have yet.
+    async fn unlock(&self,
+                    user_id: Uuid,
+                    achievements: &[Achievement])
+                    -> Result<Vec<UnlockedAchievement>, GameError>;
+    async fn post_author(&self, post_id: i64) -> Result<Option<Uuid>, GameError>;
+}
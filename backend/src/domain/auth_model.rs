@@ -20,6 +20,8 @@ impl From<User> for UserInfo {
             username: user.name,
             created_at: user.created_at,
             role: user.role,
+            rating: Default::default(),
+            coins: 0,
         }
     }
 }
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::{collusion::*, rps_game::RpsMove};
+use uuid::Uuid;
+
+/// Pairs with fewer ranked rounds between them are never flagged.
+const MIN_GAMES: u32 = 30;
+/// Share of a player's games against the same opponent that counts as abnormal.
+const HEAD_TO_HEAD_SHARE: f64 = 0.5;
+/// Sign test on the decided rounds, about p < 0.001 one-sided.
+const ONE_SIDED_Z: f64 = 3.1;
+/// Chi-square with 4 degrees of freedom at p < 0.001.
+const MOVE_CHI2: f64 = 18.47;
+/// A single signal happens to honest players too.
+const MIN_SIGNALS: usize = 2;
+
+/// Ranked rounds between two players, `players[0]` being the smaller id.
+pub struct PairGames
+{
+    pub players: [Uuid; 2],
+    /// Rounds by the move of each player, indexed like `RpsMove::ALL`.
+    pub moves: [[u32; 3]; 3],
+}
+
+/// A pair worth a moderator's look.
+pub struct Suspicion
+{
+    pub players: [Uuid; 2],
+    pub games: u32,
+    pub wins: [u32; 2],
+    pub head_to_head: f64,
+    pub move_chi2: f64,
+    pub signals: Vec<CollusionSignal>,
+}
+
+impl PairGames
+{
+    pub fn games(&self) -> u32
+    {
+        self.moves.iter().flatten().sum()
+    }
+
+    pub fn wins(&self) -> [u32; 2]
+    {
+        let mut wins = [0; 2];
+        for (i, first) in RpsMove::ALL.iter().enumerate() {
+            for (j, second) in RpsMove::ALL.iter().enumerate() {
+                if first.beats(second) {
+                    wins[0] += self.moves[i][j];
+                } else if second.beats(first) {
+                    wins[1] += self.moves[i][j];
+                }
+            }
+        }
+        wins
+    }
+
+    /// Pearson chi-square of the moves of one player against the other's. Players who
+    /// don't coordinate pick their moves independently, whatever their own habits are.
+    pub fn move_chi2(&self) -> f64
+    {
+        let games = self.games() as f64;
+        let rows = self.moves.map(|row| row.iter().sum::<u32>() as f64);
+        let cols: [f64; 3] =
+            std::array::from_fn(|j| self.moves.iter().map(|row| row[j]).sum::<u32>() as f64);
+
+        let mut chi2 = 0.0;
+        for (i, row) in self.moves.iter().enumerate() {
+            for (j, count) in row.iter().enumerate() {
+                let expected = rows[i] * cols[j] / games;
+                if expected > 0.0 {
+                    chi2 += (*count as f64 - expected).powi(2) / expected;
+                }
+            }
+        }
+        chi2
+    }
+}
+
+/// Check a pair against every signal, `totals` being all ranked rounds each of them played
+/// in the same window.
+pub fn analyse(pair: &PairGames, totals: [u32; 2]) -> Option<Suspicion>
+{
+    let games = pair.games();
+    if games < MIN_GAMES {
+        return None;
+    }
+
+    let head_to_head = totals.iter()
+                             .map(|total| games as f64 / (*total).max(games) as f64)
+                             .fold(0.0, f64::max);
+    let wins = pair.wins();
+    let decided = (wins[0] + wins[1]) as f64;
+    let win_z = if decided > 0.0 {
+        wins[0].abs_diff(wins[1]) as f64 / decided.sqrt()
+    } else {
+        0.0
+    };
+    let move_chi2 = pair.move_chi2();
+
+    let checks = [(CollusionSignal::HeadToHead, head_to_head >= HEAD_TO_HEAD_SHARE),
+                  (CollusionSignal::OneSided, win_z >= ONE_SIDED_Z),
+                  (CollusionSignal::MovePattern, move_chi2 >= MOVE_CHI2)];
+    let signals: Vec<_> = checks.into_iter()
+                                .filter_map(|(signal, hit)| hit.then_some(signal))
+                                .collect();
+    if signals.len() < MIN_SIGNALS {
+        return None;
+    }
+
+    Some(Suspicion { players: pair.players,
+                     games,
+                     wins,
+                     head_to_head,
+                     move_chi2,
+                     signals })
+}
+
+#[async_trait]
+pub trait CollusionService: Send + Sync
+{
+    /// Ranked RPS rounds since `since` by pair, bots left out.
+    async fn pair_games(&self, since: DateTime<Utc>) -> Result<Vec<PairGames>, CollusionError>;
+    /// Ranked RPS rounds since `since` by player.
+    async fn game_counts(&self, since: DateTime<Utc>) -> Result<HashMap<Uuid, u32>, CollusionError>;
+    /// Flag a pair, or refresh the numbers of a flag still open.
+    async fn flag(&self, suspicion: &Suspicion) -> Result<(), CollusionError>;
+    async fn list(&self) -> Result<Vec<CollusionFlag>, CollusionError>;
+    async fn dismiss(&self, id: i64, by: Uuid) -> Result<(), CollusionError>;
+    /// Take back the rating changes of every game between the pair, and mark it so
+    /// the games they play from now on are recorded unranked.
+    async fn exclude(&self, id: i64, by: Uuid) -> Result<(), CollusionError>;
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn pair(moves: [[u32; 3]; 3]) -> PairGames
+    {
+        PairGames { players: [Uuid::new_v4(), Uuid::new_v4()],
+                    moves }
+    }
+
+    #[test]
+    fn counts_wins_by_move()
+    {
+        // rows: Rock, Paper, Scissors of the first player
+        let pair = pair([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+
+        assert_eq!(pair.games(), 45);
+        assert_eq!(pair.wins(), [3 + 4 + 8, 2 + 6 + 7]);
+    }
+
+    #[test]
+    fn independent_moves_have_no_chi2()
+    {
+        let pair = pair([[2, 4, 6], [1, 2, 3], [3, 6, 9]]);
+
+        assert!(pair.move_chi2().abs() < 1e-9);
+    }
+
+    #[test]
+    fn coordinated_moves_have_a_high_chi2()
+    {
+        // the second player always throws what the first one beats
+        let pair = pair([[0, 0, 10], [10, 0, 0], [0, 10, 0]]);
+
+        assert!((pair.move_chi2() - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn few_games_are_never_flagged()
+    {
+        let pair = pair([[0, 0, 10], [10, 0, 0], [0, 9, 0]]);
+
+        assert!(analyse(&pair, [29, 29]).is_none());
+    }
+
+    #[test]
+    fn honest_rivals_are_not_flagged()
+    {
+        let pair = pair([[4, 4, 4], [4, 4, 4], [4, 4, 4]]);
+
+        assert!(analyse(&pair, [36, 36]).is_none());
+    }
+
+    #[test]
+    fn a_single_signal_is_not_enough()
+    {
+        let pair = pair([[0, 40, 0], [0, 0, 0], [0, 0, 0]]);
+
+        assert!(analyse(&pair, [1000, 1000]).is_none());
+    }
+
+    #[test]
+    fn win_trading_is_flagged()
+    {
+        // always Paper against Rock, between two players who hardly play anyone else
+        let pair = pair([[0, 0, 0], [40, 0, 0], [0, 0, 0]]);
+
+        let suspicion = analyse(&pair, [40, 50]).unwrap();
+
+        assert_eq!(suspicion.wins, [40, 0]);
+        assert_eq!(suspicion.signals,
+                   vec![CollusionSignal::HeadToHead, CollusionSignal::OneSided]);
+    }
+
+    #[test]
+    fn scripted_moves_are_flagged()
+    {
+        let pair = pair([[0, 0, 10], [10, 0, 0], [0, 10, 0]]);
+
+        let suspicion = analyse(&pair, [1000, 1000]).unwrap();
+
+        assert_eq!(suspicion.signals,
+                   vec![CollusionSignal::OneSided, CollusionSignal::MovePattern]);
+    }
+}
@@ -0,0 +1,380 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{
+    fair_rps::*,
+    game::{GameEnvelope, GameError, GameResult},
+    rps_game::RpsMove,
+    ws_messages::ServerMsg,
+};
+use uuid::Uuid;
+
+use crate::domain::{
+    game_model::{duration_ms, ActiveGame, FinishedGame, PlayerTag},
+    rps_model::RpsWins,
+};
+
+/// Read access to recorded fair games, so anyone can verify them after the fact.
+#[async_trait]
+pub trait FairGameArchive: Send + Sync
+{
+    async fn fair_game(&self, id: i64) -> Result<Option<FairRpsRecord>, GameError>;
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FairRpsPlayer
+{
+    pub id: Uuid,
+    pub commitment: Option<String>,
+    pub reveal: Option<(RpsMove, String)>,
+}
+
+/// Single RPS round played with commit-reveal: both players first publish a hash of
+/// their move and a nonce, and only then the move itself, so the server never learns
+/// a move before the opponent is bound to theirs.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FairRpsGame
+{
+    pub players: [FairRpsPlayer; 2],
+    pub created_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    #[serde(with = "duration_ms")]
+    pub move_time: Duration,
+}
+
+#[derive(Clone)]
+pub struct FinishedFairRpsGame
+{
+    pub players_id: [Uuid; 2],
+    pub commitments: [Option<String>; 2],
+    pub reveals: [Option<(RpsMove, String)>; 2],
+    pub forfeited: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Set by the recorder once the game is stored.
+    pub id: Option<i64>,
+}
+
+impl FairRpsGame
+{
+    fn player(&self, player_id: &Uuid) -> Option<&FairRpsPlayer>
+    {
+        self.players.iter().find(|p| p.id == *player_id)
+    }
+
+    fn all_committed(&self) -> bool
+    {
+        self.players.iter().all(|p| p.commitment.is_some())
+    }
+
+    fn finish(&self, forfeited: Option<Uuid>) -> FinishedFairRpsGame
+    {
+        let [p1, p2] = &self.players;
+
+        FinishedFairRpsGame { players_id: [p1.id, p2.id],
+                              commitments: [p1.commitment.clone(), p2.commitment.clone()],
+                              reveals: [p1.reveal.clone(), p2.reveal.clone()],
+                              forfeited,
+                              created_at: self.created_at,
+                              id: None }
+    }
+}
+
+impl FinishedFairRpsGame
+{
+    pub fn info(&self, players: [String; 2]) -> FairRpsInfo
+    {
+        FairRpsInfo { id: self.id,
+                      players,
+                      commitments: self.commitments.clone(),
+                      moves: self.reveals.clone().map(|r| r.map(|(mv, _)| mv)),
+                      nonces: self.reveals.clone().map(|r| r.map(|(_, nonce)| nonce)),
+                      forfeited: self.players_id.map(|id| self.forfeited == Some(id)) }
+    }
+}
+
+impl FinishedGame for FinishedFairRpsGame
+{
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
+    {
+        let players = if player_id == self.players_id[0] {
+            [player.name.clone(), opp.name.clone()]
+        } else {
+            [opp.name.clone(), player.name.clone()]
+        };
+
+        let state = FairRpsState::Finished(self.info(players));
+
+        ServerMsg::GameMsg(GameEnvelope::state::<FairRpsKind>(&state))
+    }
+    fn resolve(&self) -> GameResult
+    {
+        self.info(Default::default()).resolve()
+    }
+    fn reverse(&mut self)
+    {
+        self.players_id.reverse();
+        self.commitments.reverse();
+        self.reveals.reverse();
+    }
+    fn players(&self) -> [Uuid; 2]
+    {
+        self.players_id
+    }
+}
+
+impl RpsWins for FinishedFairRpsGame
+{
+    fn winning_moves(&self) -> [Vec<RpsMove>; 2]
+    {
+        match &self.reveals {
+            [Some((mv1, _)), Some((mv2, _))] => {
+                [mv1.beats(mv2).then_some(*mv1).into_iter().collect(),
+                 mv2.beats(mv1).then_some(*mv2).into_iter().collect()]
+            }
+            _ => Default::default(),
+        }
+    }
+}
+
+impl ActiveGame for FairRpsGame
+{
+    type Move = FairRpsMove;
+    type FinishedGame = FinishedFairRpsGame;
+    type Settings = ();
+    type Kind = FairRpsKind;
+
+    fn new(player: Uuid, opponent: Uuid, _settings: (), move_time: Duration) -> Self
+    {
+        let new_player = |id| FairRpsPlayer { id,
+                                              commitment: None,
+                                              reveal: None };
+        let created_at = Utc::now();
+
+        Self { players: [new_player(player), new_player(opponent)],
+               created_at,
+               deadline: created_at + move_time,
+               move_time }
+    }
+
+    fn settings(&self) {}
+
+    fn players(&self) -> [Uuid; 2]
+    {
+        [self.players[0].id, self.players[1].id]
+    }
+
+    fn deadline(&self) -> DateTime<Utc>
+    {
+        self.deadline
+    }
+
+    /// Whoever did not commit, or did not reveal once both committed, forfeits.
+    fn time_out(&self) -> Option<FinishedFairRpsGame>
+    {
+        let idle: Vec<Uuid> = if self.all_committed() {
+            self.players
+                .iter()
+                .filter(|p| p.reveal.is_none())
+                .map(|p| p.id)
+                .collect()
+        } else {
+            self.players
+                .iter()
+                .filter(|p| p.commitment.is_none())
+                .map(|p| p.id)
+                .collect()
+        };
+
+        let [forfeited] = idle[..] else {
+            return None;
+        };
+
+        Some(self.finish(Some(forfeited)))
+    }
+
+    fn forfeit(&self, loser: &Uuid) -> Option<FinishedFairRpsGame>
+    {
+        self.has_player(loser).then(|| self.finish(Some(*loser)))
+    }
+
+    fn validate_move(&self, player_id: &Uuid, mv: &FairRpsMove) -> Result<(), GameError>
+    {
+        let player = self.player(player_id).ok_or(GameError::NotFound)?;
+
+        match mv {
+            FairRpsMove::Commit(hash) => {
+                if player.commitment.is_some() || !is_commitment(hash) {
+                    return Err(GameError::InvalidMove);
+                }
+            }
+            FairRpsMove::Reveal { mv, nonce } => {
+                if !self.all_committed()
+                   || player.reveal.is_some()
+                   || player.commitment.as_deref() != Some(commitment(*mv, nonce).as_str())
+                {
+                    return Err(GameError::InvalidMove);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn set_move(&mut self, player_id: &Uuid, mv: FairRpsMove) -> Self
+    {
+        if self.validate_move(player_id, &mv).is_err() {
+            return self.clone();
+        }
+
+        let Some(player) = self.players.iter_mut().find(|p| p.id == *player_id) else {
+            return self.clone();
+        };
+
+        match mv {
+            FairRpsMove::Commit(hash) => {
+                player.commitment = Some(hash);
+                // both hashes are in, the reveal phase gets its own deadline
+                if self.all_committed() {
+                    self.deadline = Utc::now() + self.move_time;
+                }
+            }
+            FairRpsMove::Reveal { mv, nonce } => player.reveal = Some((mv, nonce)),
+        }
+        self.clone()
+    }
+
+    fn has_player(&self, player_id: &Uuid) -> bool
+    {
+        self.player(player_id).is_some()
+    }
+
+    fn get_opp(&self, player_id: &Uuid) -> Option<Uuid>
+    {
+        let [p1, p2] = &self.players;
+
+        if p1.id == *player_id {
+            Some(p2.id)
+        } else if p2.id == *player_id {
+            Some(p1.id)
+        } else {
+            None
+        }
+    }
+
+    fn is_ready(&self) -> bool
+    {
+        self.players.iter().all(|p| p.reveal.is_some())
+    }
+
+    fn try_resolve(&self) -> Option<FinishedFairRpsGame>
+    {
+        self.is_ready().then(|| self.finish(None))
+    }
+
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
+    {
+        if self.is_ready() {
+            return self.try_resolve()
+                       .unwrap()
+                       .to_msg(player_id, player, opp);
+        }
+
+        let tags = if self.players[0].id == player_id { [player, opp] } else { [opp, player] };
+        let players = tags.map(|t| t.name.clone());
+        let ratings = tags.map(PlayerTag::rounded_rating);
+
+        let state = if self.all_committed() {
+            FairRpsState::Reveal { players,
+                                   ratings,
+                                   commitments: self.players
+                                                    .clone()
+                                                    .map(|p| p.commitment.unwrap_or_default()),
+                                   revealed: self.players.clone().map(|p| p.reveal.is_some()),
+                                   deadline: self.deadline }
+        } else {
+            FairRpsState::Commit { players,
+                                   ratings,
+                                   committed: self.players.clone().map(|p| p.commitment.is_some()),
+                                   deadline: self.deadline }
+        };
+
+        ServerMsg::GameMsg(GameEnvelope::state::<FairRpsKind>(&state))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use shared::rps_game::RpsMove::*;
+
+    use super::*;
+
+    fn game() -> FairRpsGame
+    {
+        FairRpsGame::new(Uuid::new_v4(), Uuid::new_v4(), (), Duration::seconds(30))
+    }
+
+    fn reveal(mv: RpsMove, nonce: &str) -> [FairRpsMove; 2]
+    {
+        [FairRpsMove::Commit(commitment(mv, nonce)),
+         FairRpsMove::Reveal { mv,
+                               nonce: nonce.to_string() }]
+    }
+
+    #[test]
+    fn reveals_only_after_both_commit()
+    {
+        let mut game = game();
+        let [p1, p2] = game.players();
+        let [commit1, reveal1] = reveal(Rock, "a");
+        let [commit2, reveal2] = reveal(Scissors, "b");
+
+        game.set_move(&p1, commit1);
+        assert!(game.validate_move(&p1, &reveal1).is_err());
+
+        game.set_move(&p2, commit2);
+        game.set_move(&p1, reveal1);
+        game.set_move(&p2, reveal2);
+
+        let finished = game.try_resolve().unwrap();
+        assert_eq!(finished.resolve(), GameResult::Win);
+        assert_eq!(finished.info(Default::default()).verify(), [true, true]);
+    }
+
+    #[test]
+    fn rejects_a_reveal_that_does_not_match()
+    {
+        let mut game = game();
+        let [p1, p2] = game.players();
+        let [commit1, _] = reveal(Rock, "a");
+        let [commit2, _] = reveal(Paper, "b");
+        game.set_move(&p1, commit1);
+        game.set_move(&p2, commit2);
+
+        let cheat = FairRpsMove::Reveal { mv: Paper,
+                                          nonce: "a".to_string() };
+
+        assert!(game.validate_move(&p1, &cheat).is_err());
+    }
+
+    #[test]
+    fn missing_commit_or_reveal_times_out()
+    {
+        let mut game = game();
+        let [p1, p2] = game.players();
+        let [commit1, reveal1] = reveal(Rock, "a");
+        let [commit2, _] = reveal(Paper, "b");
+
+        game.set_move(&p1, commit1);
+        assert_eq!(game.time_out().unwrap().forfeited, Some(p2));
+
+        game.set_move(&p2, commit2);
+        game.set_move(&p1, reveal1);
+        let finished = game.time_out().unwrap();
+
+        // the revealed losing move doesn't matter once the opponent forfeits
+        assert_eq!(finished.forfeited, Some(p2));
+        assert_eq!(finished.resolve(), GameResult::Win);
+        assert_eq!(finished.info(Default::default()).verify(), [true, false]);
+    }
+}
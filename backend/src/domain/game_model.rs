@@ -1,40 +1,127 @@
 use async_trait::async_trait;
-use shared::game::{GameError, GameResult};
+use chrono::{DateTime, Duration, Utc};
+use shared::game::{GameError, GameKind, GameResult};
 use shared::ws_messages::ServerMsg;
+use std::hash::Hash;
 use uuid::Uuid;
 
+use crate::domain::rating::Rating;
+
 pub type GameId = usize;
 
+/// Serde helper storing a `chrono::Duration` as whole milliseconds.
+pub mod duration_ms
+{
+    use chrono::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error>
+    {
+        s.serialize_i64(d.num_milliseconds())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Duration, D::Error>
+    {
+        i64::deserialize(d).map(Duration::milliseconds)
+    }
+}
+
+/// How a player is presented to others in game messages.
+#[derive(Clone, Debug)]
+pub struct PlayerTag
+{
+    pub name: String,
+    pub rating: Option<Rating>,
+}
+
+impl PlayerTag
+{
+    pub fn rounded_rating(&self) -> Option<i32>
+    {
+        self.rating.map(|r| r.rating.round() as i32)
+    }
+}
+
 pub trait FinishedGame: Send + Sync + Clone
 {
-    fn into_msg(&self, player_id: Uuid, player_name: &str, opp_name: &str) -> ServerMsg;
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg;
+    /// Result from the point of view of the first player.
     fn resolve(&self) -> GameResult;
     fn reverse(&mut self);
+    fn players(&self) -> [Uuid; 2];
+}
+
+/// How a game ended, as handed to result listeners such as tournaments.
+#[derive(Clone)]
+pub enum GameOutcome<F>
+{
+    Finished(F),
+    /// Over without a result: abandoned, left or dropped.
+    Abandoned([Uuid; 2]),
 }
 
 pub trait ActiveGame: Send + Sync + Clone
 {
     type Move: Send + Sync + Clone;
     type FinishedGame: FinishedGame;
+    /// Options both players agree on before the game starts (e.g. series length).
+    type Settings: Send + Sync + Clone + Eq + Hash;
+    /// What clients know the game as: its id and the messages it is played with.
+    type Kind: GameKind<Settings = Self::Settings, Move = Self::Move>;
 
-    fn new(player: Uuid, opponent: Uuid) -> Self;
+    /// `move_time` is how long each player has to make every move.
+    fn new(player: Uuid, opponent: Uuid, settings: Self::Settings, move_time: Duration) -> Self;
+    fn settings(&self) -> Self::Settings;
+    fn players(&self) -> [Uuid; 2];
+    /// When the pending moves are due.
+    fn deadline(&self) -> DateTime<Utc>;
+    /// End the game after the deadline passed: players who did not move forfeit.
+    /// `None` when nobody moved and the game is simply abandoned.
+    fn time_out(&self) -> Option<Self::FinishedGame>;
+    /// End the game with `loser` forfeiting, e.g. after they failed to reconnect.
+    /// `None` for games that have no forfeit result and are just abandoned.
+    fn forfeit(&self, _loser: &Uuid) -> Option<Self::FinishedGame>
+    {
+        None
+    }
+    /// Reject a move before it is applied. Games with free-form moves check them here.
+    fn validate_move(&self, _player_id: &Uuid, _mv: &Self::Move) -> Result<(), GameError>
+    {
+        Ok(())
+    }
     fn set_move(&mut self, player_id: &Uuid, mv: Self::Move) -> Self;
     fn has_player(&self, player_id: &Uuid) -> bool;
     fn get_opp(&self, player_id: &Uuid) -> Option<Uuid>;
     fn is_ready(&self) -> bool;
+    /// Player who has to move next in turn-based games. `None` while both move at once
+    /// (as in RPS) or once the game is over.
+    fn turn(&self) -> Option<Uuid>
+    {
+        None
+    }
     fn try_resolve(&self) -> Option<Self::FinishedGame>;
-    fn into_msg(&self, player_id: Uuid, player_name: &str, opp_name: &str) -> ServerMsg;
-    fn is_spoiled(&self) -> bool;
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg;
+}
+
+/// What matchmaking needs to know about a player entering the queue.
+#[derive(Clone, Debug)]
+pub struct QueuedPlayer
+{
+    pub user_id: Uuid,
+    pub rating: f64,
+    /// Opponent of the previous game, so the same two players are not paired back to back.
+    pub last_opp: Option<Uuid>,
+    /// Coins staked on the game, only players with the same stake are paired.
+    pub stake: u32,
 }
 
 /// Abstract matchmaking queue that can be backed by any async runtime or actor system.
+/// The queue forms pairs on its own and reports them through a channel it was built with.
 #[async_trait]
 pub trait PlayerQueue: Send + Sync
 {
     async fn contains(&self, user_id: Uuid) -> bool;
-    /// Put a player into the queue. If another player was waiting return their id so a match can start.
-    async fn add(&self, user_id: Uuid);
-    async fn try_take(&self) -> Option<Uuid>;
+    async fn add(&self, player: QueuedPlayer);
     async fn remove(&self, user_id: Uuid);
 }
 
@@ -44,13 +131,19 @@ pub trait GameService<G>: Send + Sync
     where G: ActiveGame
 {
     async fn has_active_game(&self, user_id: Uuid) -> bool;
-    async fn start(&self, user_id: Uuid, opp_id: Uuid) -> G;
+    /// Fails with `AlreadyInGame` if either player is already in a game of this kind.
+    async fn start(&self, user_id: Uuid, opp_id: Uuid, settings: G::Settings)
+                   -> Result<G, GameError>;
     async fn submit_move(&self, user_id: Uuid, mv: G::Move) -> Result<G, GameError>;
     async fn opponent_for(&self, user_id: Uuid) -> Option<Uuid>;
     async fn drop_for(&self, user_id: Uuid) -> Result<(), GameError>;
     async fn try_resolve(&self, user_id: Uuid) -> Option<G::FinishedGame>;
     async fn get_game(&self, user_id: Uuid) -> Option<G>;
-    async fn clear_spoiled(&self);
+    async fn get_game_by_id(&self, game_id: GameId) -> Option<G>;
+    /// Every game in progress, for spectators to pick from.
+    async fn list_games(&self) -> Vec<(GameId, G)>;
+    /// Remove and return every game whose deadline has passed.
+    async fn take_timed_out(&self) -> Vec<G>;
 }
 
 /// Port for pushing game events to clients (e.g., via websockets).
@@ -60,11 +153,32 @@ pub trait GameNotifier: Send + Sync
     async fn notify(&self, user_id: Uuid, msg: ServerMsg);
     async fn get_name(&self, user_id: Uuid) -> Option<String>;
     async fn is_online(&self, user_id: Uuid) -> bool;
+    /// Id of a connected user by their username.
+    async fn find_online(&self, username: &str) -> Option<Uuid>;
+}
+
+/// Computer-controlled opponent that can stand in when nobody else is queued.
+pub trait Bot<G>: Send + Sync
+    where G: ActiveGame
+{
+    fn id(&self) -> Uuid;
+    fn name(&self) -> String;
+    /// Pick a move for the current round. Only finished rounds may be looked at.
+    fn pick_move(&self, game: &G) -> G::Move;
 }
 
 #[async_trait]
 pub trait GameRecorder<G>: Send + Sync
     where G: ActiveGame
 {
-    async fn record(&self, game: G::FinishedGame) -> Result<(), GameError>;
+    /// Store a finished game, filling in what the store assigns, such as a public id.
+    /// Unranked games (e.g. against bots) are kept out of stats.
+    async fn record(&self, game: &mut G::FinishedGame, ranked: bool) -> Result<(), GameError>;
+}
+
+/// Read side of player ratings, kept apart from recording so views can be enriched cheaply.
+#[async_trait]
+pub trait RatingProvider: Send + Sync
+{
+    async fn rating(&self, user_id: Uuid) -> Option<Rating>;
 }
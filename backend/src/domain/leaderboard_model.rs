@@ -0,0 +1,28 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::leaderboard::*;
+use uuid::Uuid;
+
+/// One page of the leaderboard plus where the asking player stands.
+pub struct Ranking
+{
+    pub entries: Vec<LeaderboardEntry>,
+    /// Players ranked in total, across all pages.
+    pub total: u32,
+    pub me: Option<LeaderboardEntry>,
+}
+
+#[async_trait]
+pub trait LeaderboardService: Send + Sync
+{
+    /// Ranked games since `since`, or all of them for `None`.
+    /// `min_games` only applies when sorting by win rate.
+    async fn ranking(&self,
+                     sort: LeaderboardSort,
+                     since: Option<DateTime<Utc>>,
+                     min_games: u32,
+                     offset: u32,
+                     limit: u32,
+                     user_id: Option<Uuid>)
+                     -> Result<Ranking, LeaderboardError>;
+}
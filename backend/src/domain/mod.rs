@@ -1,5 +1,20 @@
+pub mod achievement_model;
 pub mod auth_model;
+pub mod collusion_model;
+pub mod fair_rps_model;
 pub mod forum_model;
 pub mod game_model;
+pub mod leaderboard_model;
+pub mod profile_model;
+pub mod quest_model;
+pub mod rating;
+pub mod room_model;
+pub mod rooms_actor;
+pub mod royale_model;
+pub mod rps_bots;
 pub mod rps_model;
+pub mod season_model;
+pub mod tic_tac_toe_model;
+pub mod tournament_model;
 pub mod users_actor;
+pub mod wallet_model;
@@ -0,0 +1,135 @@
+use async_trait::async_trait;
+use shared::{
+    game::GameError,
+    profile::{MoveAnalytics, PlayerProfile},
+    rps_game::RpsMove,
+};
+use uuid::Uuid;
+
+use crate::domain::rps_bots::{MarkovPredictor, RpsStrategy};
+
+#[async_trait]
+pub trait ProfileService: Send + Sync
+{
+    /// Everything but the recent games, or `None` if there is no such user.
+    async fn profile(&self, username: &str) -> Result<Option<PlayerProfile>, GameError>;
+    /// The user's latest `limit` RPS rounds, oldest first, as `[own_move, opp_move]`.
+    async fn rounds(&self, user_id: Uuid, limit: u32) -> Result<Vec<[RpsMove; 2]>, GameError>;
+}
+
+/// Current and best run of wins in `scores`, oldest first, 1.0 being a win.
+pub fn win_streaks(scores: impl IntoIterator<Item = f64>) -> (u32, u32)
+{
+    scores.into_iter()
+          .fold((0, 0), |(current, best), score| {
+              if score == 1.0 {
+                  (current + 1, best.max(current + 1))
+              } else {
+                  (0, best)
+              }
+          })
+}
+
+/// Index of the result of `mv` against `other` in win, draw, loss order.
+fn outcome(mv: RpsMove, other: RpsMove) -> usize
+{
+    if mv.beats(&other) {
+        0
+    } else if mv == other {
+        1
+    } else {
+        2
+    }
+}
+
+/// Habits in `rounds`, oldest first, as `[own_move, opp_move]`. The predictor is the
+/// MarkovBot strategy, replayed so it only ever sees the rounds before the one it guesses.
+pub fn move_analytics(rounds: &[[RpsMove; 2]]) -> MoveAnalytics
+{
+    let index = |mv: RpsMove| RpsMove::ALL.iter().position(|m| *m == mv).unwrap_or(0);
+
+    let mut move_counts = [0; 3];
+    for [own, _] in rounds {
+        move_counts[index(*own)] += 1;
+    }
+
+    let mut after_result = [[0; 3]; 3];
+    for pair in rounds.windows(2) {
+        let [own, opp] = pair[0];
+        after_result[outcome(own, opp)][index(pair[1][0])] += 1;
+    }
+
+    let total = rounds.len() as f64;
+    let entropy = move_counts.iter()
+                             .filter(|count| **count > 0)
+                             .map(|count| {
+                                 let p = *count as f64 / total;
+                                 -p * p.log2()
+                             })
+                             .sum();
+
+    let mut predictor = [0; 3];
+    let mut seen = Vec::with_capacity(rounds.len());
+    for [own, _] in rounds {
+        let guess = MarkovPredictor.next_move(&seen);
+        predictor[outcome(guess, *own)] += 1;
+        seen.push([guess, *own]);
+    }
+
+    MoveAnalytics { rounds: rounds.len() as u32,
+                    move_counts,
+                    after_result,
+                    entropy,
+                    predictor }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use RpsMove::*;
+
+    #[test]
+    fn streaks_count_consecutive_wins()
+    {
+        assert_eq!(win_streaks([1.0, 1.0, 0.5, 1.0, 1.0, 1.0, 0.0, 1.0]), (1, 3));
+        assert_eq!(win_streaks([0.0, 1.0, 1.0]), (2, 2));
+        assert_eq!(win_streaks([]), (0, 0));
+    }
+
+    #[test]
+    fn counts_moves_and_what_follows_each_result()
+    {
+        let rounds = [[Rock, Scissors], [Rock, Rock], [Paper, Scissors], [Scissors, Paper]];
+
+        let analytics = move_analytics(&rounds);
+
+        assert_eq!(analytics.rounds, 4);
+        assert_eq!(analytics.move_counts, [2, 1, 1]);
+        // a win then Rock, a draw then Paper, a loss then Scissors
+        assert_eq!(analytics.after_result, [[1, 0, 0], [0, 1, 0], [0, 0, 1]]);
+    }
+
+    #[test]
+    fn entropy_spans_predictable_to_even()
+    {
+        let same = move_analytics(&[[Paper, Rock]; 6]);
+        let even = move_analytics(&[[Rock, Rock], [Paper, Rock], [Scissors, Rock]]);
+
+        assert_eq!(same.entropy, 0.0);
+        assert!((even.entropy - 3f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn predictor_learns_a_fixed_cycle()
+    {
+        let cycle = [Rock, Paper, Scissors];
+        let rounds: Vec<[RpsMove; 2]> = cycle.iter().cycle().take(30).map(|mv| [*mv, Rock]).collect();
+
+        let analytics = move_analytics(&rounds);
+
+        assert_eq!(analytics.predictor.iter().sum::<u32>(), 30);
+        // once every transition has been seen, every guess is right
+        assert!(analytics.predictor[0] >= 26, "{:?}", analytics.predictor);
+    }
+}
@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
+use rand::seq::IndexedRandom;
+use shared::{game::GameError, quest::*, rps_game::RpsMove};
+use uuid::Uuid;
+
+/// Something a player did that quests may count.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuestAction
+{
+    Played,
+    Won,
+    /// Won a round of RPS with the move.
+    WonWith(RpsMove),
+    Posted,
+}
+
+/// A quest about to be generated for a period.
+#[derive(Clone, Copy, Debug)]
+pub struct NewQuest
+{
+    pub goal: QuestGoal,
+    pub target: u32,
+}
+
+/// Goals quests are picked from, with their target for a day and for a week.
+const TEMPLATES: [(QuestGoal, u32, u32); 6] = [(QuestGoal::PlayGames, 5, 30),
+                                               (QuestGoal::WinGames, 3, 15),
+                                               (QuestGoal::WinWith(RpsMove::Rock), 3, 10),
+                                               (QuestGoal::WinWith(RpsMove::Paper), 3, 10),
+                                               (QuestGoal::WinWith(RpsMove::Scissors), 3, 10),
+                                               (QuestGoal::PostInForum, 1, 5)];
+
+pub fn counts(goal: QuestGoal, action: QuestAction) -> bool
+{
+    match (goal, action) {
+        (QuestGoal::PlayGames, QuestAction::Played) => true,
+        (QuestGoal::WinGames, QuestAction::Won) => true,
+        (QuestGoal::WinWith(mv), QuestAction::WonWith(won_with)) => mv == won_with,
+        (QuestGoal::PostInForum, QuestAction::Posted) => true,
+        _ => false,
+    }
+}
+
+/// Start and end of the period `now` falls in. Days start at midnight UTC, weeks on Monday.
+pub fn period_bounds(period: QuestPeriod, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>)
+{
+    let today = Utc.from_utc_datetime(&now.date_naive().and_hms_opt(0, 0, 0).unwrap());
+
+    match period {
+        QuestPeriod::Daily => (today, today + Duration::days(1)),
+        QuestPeriod::Weekly => {
+            let monday = today - Duration::days(now.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::weeks(1))
+        }
+    }
+}
+
+/// Random pick of distinct goals for a new period.
+pub fn generate(period: QuestPeriod) -> Vec<NewQuest>
+{
+    let count = match period {
+        QuestPeriod::Daily => 3,
+        QuestPeriod::Weekly => 2,
+    };
+
+    TEMPLATES.choose_multiple(&mut rand::rng(), count)
+             .map(|(goal, daily, weekly)| {
+                 let target = match period {
+                     QuestPeriod::Daily => *daily,
+                     QuestPeriod::Weekly => *weekly,
+                 };
+                 NewQuest { goal: *goal,
+                            target }
+             })
+             .collect()
+}
+
+#[async_trait]
+pub trait QuestService: Send + Sync
+{
+    /// Store the quests of a period unless it already has some.
+    async fn create(&self,
+                    period: QuestPeriod,
+                    bounds: (DateTime<Utc>, DateTime<Utc>),
+                    quests: &[NewQuest])
+                    -> Result<(), GameError>;
+    /// Quests running right now with the user's progress, daily ones first.
+    async fn current(&self, user_id: Uuid) -> Result<Vec<QuestInfo>, GameError>;
+    /// Add to the user's progress on a quest, answering with the new progress and
+    /// whether the quest is completed now.
+    async fn advance(&self,
+                     user_id: Uuid,
+                     quest: &QuestInfo,
+                     amount: u32)
+                     -> Result<(u32, bool), GameError>;
+}
@@ -0,0 +1,162 @@
+use std::f64::consts::PI;
+
+/// Conversion factor between the Glicko and Glicko-2 scales.
+const SCALE: f64 = 173.7178;
+/// Constrains how fast volatility can change between rating periods.
+const TAU: f64 = 0.5;
+const EPSILON: f64 = 1e-6;
+
+/// Glicko-2 rating of a single player, on the familiar Glicko scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rating
+{
+    pub rating: f64,
+    pub deviation: f64,
+    pub volatility: f64,
+}
+
+impl Default for Rating
+{
+    fn default() -> Self
+    {
+        Self { rating: 1500.0,
+               deviation: 350.0,
+               volatility: 0.06 }
+    }
+}
+
+impl Rating
+{
+    /// New rating after a single game against `opp`, `score` being 1.0 for a win,
+    /// 0.5 for a draw and 0.0 for a loss. Every game is treated as its own rating period.
+    pub fn update(&self, opp: &Rating, score: f64) -> Rating
+    {
+        self.rate_period(&[(*opp, score)])
+    }
+
+    /// New rating after a rating period made of `games`, each an opponent and a score.
+    fn rate_period(&self, games: &[(Rating, f64)]) -> Rating
+    {
+        let mu = (self.rating - 1500.0) / SCALE;
+        let phi = self.deviation / SCALE;
+        let sigma = self.volatility;
+
+        let mut v_inv = 0.0;
+        let mut improvement = 0.0;
+        for (opp, score) in games {
+            let opp_mu = (opp.rating - 1500.0) / SCALE;
+            let opp_phi = opp.deviation / SCALE;
+
+            let g = 1.0 / (1.0 + 3.0 * opp_phi.powi(2) / PI.powi(2)).sqrt();
+            let expected = 1.0 / (1.0 + (-g * (mu - opp_mu)).exp());
+            v_inv += g.powi(2) * expected * (1.0 - expected);
+            improvement += g * (score - expected);
+        }
+        let v = 1.0 / v_inv;
+        let delta = v * improvement;
+
+        let sigma = new_volatility(phi, sigma, v, delta);
+
+        let phi_star = (phi.powi(2) + sigma.powi(2)).sqrt();
+        let phi = 1.0 / (1.0 / phi_star.powi(2) + 1.0 / v).sqrt();
+        let mu = mu + phi.powi(2) * improvement;
+
+        Rating { rating: mu * SCALE + 1500.0,
+                 deviation: phi * SCALE,
+                 volatility: sigma }
+    }
+}
+
+/// Step 5 of the Glicko-2 paper: solve for the new volatility with the Illinois algorithm.
+fn new_volatility(phi: f64, sigma: f64, v: f64, delta: f64) -> f64
+{
+    let a = (sigma.powi(2)).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        let denom = phi.powi(2) + v + ex;
+        ex * (delta.powi(2) - denom + ex) / (2.0 * denom.powi(2)) - (x - a) / TAU.powi(2)
+    };
+
+    let mut x_a = a;
+    let mut x_b = if delta.powi(2) > phi.powi(2) + v {
+        (delta.powi(2) - phi.powi(2) - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * TAU) < 0.0 {
+            k += 1.0;
+        }
+        a - k * TAU
+    };
+
+    let mut f_a = f(x_a);
+    let mut f_b = f(x_b);
+
+    while (x_b - x_a).abs() > EPSILON {
+        let c = x_a + (x_a - x_b) * f_a / (f_b - f_a);
+        let f_c = f(c);
+
+        if f_c * f_b <= 0.0 {
+            x_a = x_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        x_b = c;
+        f_b = f_c;
+    }
+
+    (x_a / 2.0).exp()
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn rating(rating: f64, deviation: f64) -> Rating
+    {
+        Rating { rating,
+                 deviation,
+                 volatility: 0.06 }
+    }
+
+    /// The worked example from Glickman's "Example of the Glicko-2 system".
+    #[test]
+    fn matches_published_example()
+    {
+        let player = rating(1500.0, 200.0);
+        let games = [(rating(1400.0, 30.0), 1.0),
+                     (rating(1550.0, 100.0), 0.0),
+                     (rating(1700.0, 300.0), 0.0)];
+
+        let new = player.rate_period(&games);
+
+        assert!((new.rating - 1464.06).abs() < 0.01, "{}", new.rating);
+        assert!((new.deviation - 151.52).abs() < 0.01, "{}", new.deviation);
+        assert!((new.volatility - 0.05999).abs() < 1e-5, "{}", new.volatility);
+    }
+
+    #[test]
+    fn single_game_moves_ratings_apart()
+    {
+        let a = Rating::default();
+        let b = Rating::default();
+
+        let winner = a.update(&b, 1.0);
+        let loser = b.update(&a, 0.0);
+
+        assert!(winner.rating > 1500.0);
+        assert!((winner.rating - 1500.0 - (1500.0 - loser.rating)).abs() < 1e-9);
+        assert!(winner.deviation < a.deviation);
+    }
+
+    #[test]
+    fn draw_between_equals_keeps_rating()
+    {
+        let a = rating(1600.0, 80.0);
+
+        let new = a.update(&a, 0.5);
+
+        assert!((new.rating - 1600.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+use shared::{room::RoomError, rps_game::BestOf};
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct Room
+{
+    pub id: i64,
+    pub name: String,
+    pub owner: Option<String>,
+    pub best_of: BestOf,
+    /// Has a password; the hash itself stays in storage.
+    pub private: bool,
+}
+
+/// Storage of the rooms themselves. Who is in a room and the rotation live in `RoomsActor`.
+#[async_trait]
+pub trait RoomService: Send + Sync
+{
+    async fn rooms(&self) -> Result<Vec<Room>, RoomError>;
+    /// An empty password makes a public room.
+    async fn create(&self,
+                    owner: Uuid,
+                    name: &str,
+                    password: Option<&str>,
+                    best_of: BestOf)
+                    -> Result<Room, RoomError>;
+    /// Ok for public rooms, or when `password` matches.
+    async fn check_password(&self, room_id: i64, password: &str) -> Result<(), RoomError>;
+}
@@ -0,0 +1,350 @@
+use std::collections::{HashMap, VecDeque};
+
+use actix::prelude::*;
+use chrono::Utc;
+use shared::{room::*, rps_game::BestOf};
+use uuid::Uuid;
+
+use crate::domain::room_model::Room;
+
+/// Results kept per room for members to look back at.
+const RECENT_RESULTS: usize = 10;
+
+#[derive(Clone)]
+pub struct RoomTable
+{
+    pub room: Room,
+    /// Rotation order: the front member holds the table, the second one plays them next.
+    pub members: VecDeque<(Uuid, String)>,
+    /// Who stays on and how many games in a row they won.
+    pub champion: Option<(Uuid, u32)>,
+    pub playing: Option<[Uuid; 2]>,
+    pub recent: VecDeque<RoomResult>,
+}
+
+impl RoomTable
+{
+    fn new(room: Room) -> Self
+    {
+        Self { room,
+               members: VecDeque::new(),
+               champion: None,
+               playing: None,
+               recent: VecDeque::new() }
+    }
+
+    fn name_of(&self, user_id: Uuid) -> Option<String>
+    {
+        self.members
+            .iter()
+            .find_map(|(id, name)| (*id == user_id).then(|| name.clone()))
+    }
+
+    fn send_to_back(&mut self, user_id: Uuid)
+    {
+        if let Some(i) = self.members.iter().position(|(id, _)| *id == user_id) {
+            let member = self.members.remove(i).unwrap();
+            self.members.push_back(member);
+        }
+    }
+
+    fn summary(&self) -> RoomSummary<BestOf>
+    {
+        RoomSummary { id: self.room.id,
+                      name: self.room.name.clone(),
+                      owner: self.room.owner.clone(),
+                      settings: self.room.best_of,
+                      private: self.room.private,
+                      members: self.members.len() as u32 }
+    }
+
+    fn state(&self) -> RoomState<BestOf>
+    {
+        let champion = self.champion
+                           .and_then(|(id, streak)| self.name_of(id).map(|name| (name, streak)));
+        let playing = self.playing.and_then(|[a, b]| Some([self.name_of(a)?, self.name_of(b)?]));
+
+        RoomState { summary: self.summary(),
+                    members: self.members.iter().map(|(_, name)| name.clone()).collect(),
+                    champion,
+                    playing,
+                    recent: self.recent.iter().cloned().collect() }
+    }
+}
+
+/// Live state of every room: members, the winner-stays-on rotation and recent results.
+#[derive(Clone, Default)]
+pub struct RoomsActor
+{
+    pub rooms: HashMap<i64, RoomTable>,
+    /// Room each user is in; a user sits in one room at a time.
+    pub member_of: HashMap<Uuid, i64>,
+}
+
+impl RoomsActor
+{
+    pub fn new() -> Self
+    {
+        Self::default()
+    }
+
+    fn remove_member(&mut self, user_id: Uuid) -> Option<i64>
+    {
+        let room_id = self.member_of.remove(&user_id)?;
+        if let Some(table) = self.rooms.get_mut(&room_id) {
+            table.members.retain(|(id, _)| *id != user_id);
+            if table.champion.is_some_and(|(id, _)| id == user_id) {
+                table.champion = None;
+            }
+        }
+        Some(room_id)
+    }
+}
+
+impl Actor for RoomsActor
+{
+    type Context = Context<Self>;
+}
+
+// ---- Messages for RoomsActor
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct AddRooms
+{
+    pub rooms: Vec<Room>,
+}
+
+#[derive(Message)]
+#[rtype(result = "Vec<RoomSummary<BestOf>>")]
+pub struct ListRooms;
+
+/// Answers with the room the user left for this one, if any.
+#[derive(Message)]
+#[rtype(result = "Result<Option<i64>, RoomError>")]
+pub struct JoinRoom
+{
+    pub room_id: i64,
+    pub user_id: Uuid,
+    pub username: String,
+}
+
+#[derive(Message)]
+#[rtype(result = "Option<i64>")]
+pub struct LeaveRoom
+{
+    pub user_id: Uuid,
+}
+
+#[derive(Message)]
+#[rtype(result = "Option<i64>")]
+pub struct MemberOf
+{
+    pub user_id: Uuid,
+}
+
+/// Current state of a room along with its member ids to send it to.
+#[derive(Message)]
+#[rtype(result = "Option<(RoomState<BestOf>, Vec<Uuid>)>")]
+pub struct GetRoomState
+{
+    pub room_id: i64,
+}
+
+/// Next pair up in every room without a game going on.
+#[derive(Message)]
+#[rtype(result = "Vec<(i64, [Uuid; 2], BestOf)>")]
+pub struct NextPairings;
+
+/// Move a member who can't play right now to the end of the line.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct SkipMember
+{
+    pub room_id: i64,
+    pub user_id: Uuid,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct GameStarted
+{
+    pub room_id: i64,
+    pub players: [Uuid; 2],
+}
+
+/// Answers with the room the game was played in, if it was a room game.
+#[derive(Message)]
+#[rtype(result = "Option<i64>")]
+pub struct GameOver
+{
+    pub players: [Uuid; 2],
+    /// `None` for a draw or an abandoned game.
+    pub winner: Option<Uuid>,
+}
+
+// ---- Handlers for RoomsActor
+
+impl Handler<AddRooms> for RoomsActor
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: AddRooms, _ctx: &mut Self::Context) -> Self::Result
+    {
+        for room in msg.rooms {
+            self.rooms.entry(room.id).or_insert_with(|| RoomTable::new(room));
+        }
+    }
+}
+
+impl Handler<ListRooms> for RoomsActor
+{
+    type Result = Vec<RoomSummary<BestOf>>;
+
+    fn handle(&mut self, _msg: ListRooms, _ctx: &mut Self::Context) -> Self::Result
+    {
+        let mut rooms: Vec<_> = self.rooms.values().map(RoomTable::summary).collect();
+        rooms.sort_by(|a, b| b.members.cmp(&a.members).then(a.id.cmp(&b.id)));
+        rooms
+    }
+}
+
+impl Handler<JoinRoom> for RoomsActor
+{
+    type Result = Result<Option<i64>, RoomError>;
+
+    fn handle(&mut self, msg: JoinRoom, _ctx: &mut Self::Context) -> Self::Result
+    {
+        if !self.rooms.contains_key(&msg.room_id) {
+            return Err(RoomError::NotFound);
+        }
+        if self.member_of.get(&msg.user_id) == Some(&msg.room_id) {
+            return Ok(None);
+        }
+
+        let left = self.remove_member(msg.user_id);
+        let table = self.rooms.get_mut(&msg.room_id).unwrap();
+        table.members.push_back((msg.user_id, msg.username));
+        self.member_of.insert(msg.user_id, msg.room_id);
+
+        Ok(left)
+    }
+}
+
+impl Handler<LeaveRoom> for RoomsActor
+{
+    type Result = Option<i64>;
+
+    fn handle(&mut self, msg: LeaveRoom, _ctx: &mut Self::Context) -> Self::Result
+    {
+        self.remove_member(msg.user_id)
+    }
+}
+
+impl Handler<MemberOf> for RoomsActor
+{
+    type Result = Option<i64>;
+
+    fn handle(&mut self, msg: MemberOf, _ctx: &mut Self::Context) -> Self::Result
+    {
+        self.member_of.get(&msg.user_id).copied()
+    }
+}
+
+impl Handler<GetRoomState> for RoomsActor
+{
+    type Result = Option<(RoomState<BestOf>, Vec<Uuid>)>;
+
+    fn handle(&mut self, msg: GetRoomState, _ctx: &mut Self::Context) -> Self::Result
+    {
+        let table = self.rooms.get(&msg.room_id)?;
+        Some((table.state(), table.members.iter().map(|(id, _)| *id).collect()))
+    }
+}
+
+impl Handler<NextPairings> for RoomsActor
+{
+    type Result = Vec<(i64, [Uuid; 2], BestOf)>;
+
+    fn handle(&mut self, _msg: NextPairings, _ctx: &mut Self::Context) -> Self::Result
+    {
+        self.rooms
+            .values()
+            .filter(|t| t.playing.is_none() && t.members.len() >= 2)
+            .map(|t| (t.room.id, [t.members[0].0, t.members[1].0], t.room.best_of))
+            .collect()
+    }
+}
+
+impl Handler<SkipMember> for RoomsActor
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: SkipMember, _ctx: &mut Self::Context) -> Self::Result
+    {
+        if let Some(table) = self.rooms.get_mut(&msg.room_id) {
+            if table.champion.is_some_and(|(id, _)| id == msg.user_id) {
+                table.champion = None;
+            }
+            table.send_to_back(msg.user_id);
+        }
+    }
+}
+
+impl Handler<GameStarted> for RoomsActor
+{
+    type Result = ();
+
+    fn handle(&mut self, msg: GameStarted, _ctx: &mut Self::Context) -> Self::Result
+    {
+        if let Some(table) = self.rooms.get_mut(&msg.room_id) {
+            table.playing = Some(msg.players);
+        }
+    }
+}
+
+impl Handler<GameOver> for RoomsActor
+{
+    type Result = Option<i64>;
+
+    fn handle(&mut self, msg: GameOver, _ctx: &mut Self::Context) -> Self::Result
+    {
+        let [a, b] = msg.players;
+        let table = self.rooms.values_mut().find(|t| {
+                                                t.playing
+                                                 .is_some_and(|p| p == [a, b] || p == [b, a])
+                                            })?;
+        let [holder, challenger] = table.playing.take().unwrap();
+
+        let names = [table.name_of(holder), table.name_of(challenger)];
+        if let [Some(holder_name), Some(challenger_name)] = names {
+            let winner = msg.winner.map(|w| {
+                                       if w == holder {
+                                           holder_name.clone()
+                                       } else {
+                                           challenger_name.clone()
+                                       }
+                                   });
+            table.recent.push_front(RoomResult { players: [holder_name, challenger_name],
+                                                 winner,
+                                                 played_at: Utc::now() });
+            table.recent.truncate(RECENT_RESULTS);
+        }
+
+        // winner stays on, the loser goes to the end of the line; a draw keeps the holder on
+        match msg.winner {
+            Some(winner) => {
+                let loser = if winner == holder { challenger } else { holder };
+                let streak = match table.champion {
+                    Some((id, streak)) if id == winner => streak + 1,
+                    _ => 1,
+                };
+                table.champion = Some((winner, streak));
+                table.send_to_back(loser);
+            }
+            None => table.send_to_back(challenger),
+        }
+
+        Some(table.room.id)
+    }
+}
@@ -0,0 +1,273 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use shared::{game::GameError, royale::*, rps_game::RpsMove};
+use uuid::Uuid;
+
+/// Rounds after which whoever is still in shares first place, so three
+/// players who keep throwing everything at once can't stall a game forever.
+pub const MAX_ROUNDS: u32 = 30;
+
+#[derive(Clone)]
+pub struct RoyaleEntrant
+{
+    pub id: Uuid,
+    pub name: String,
+    pub throw: Option<RpsMove>,
+    pub last_throw: Option<RpsMove>,
+    pub eliminated_in: Option<u32>,
+    /// Left or disconnected; knocked out when the current round closes.
+    pub left: bool,
+}
+
+/// Free-for-all RPS: everybody still in throws at once each round. When exactly two
+/// different moves are thrown the players of the beaten one are out; a round with one
+/// or all three moves knocks out nobody. Players who don't throw in time are out too.
+#[derive(Clone)]
+pub struct RoyaleGame
+{
+    pub players: Vec<RoyaleEntrant>,
+    pub round: u32,
+    pub created_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    pub move_time: Duration,
+}
+
+#[async_trait]
+pub trait RoyaleRecorder: Send + Sync
+{
+    /// Store a finished game with every player's placement.
+    async fn record_royale(&self, game: &RoyaleGame) -> Result<(), GameError>;
+}
+
+impl RoyaleGame
+{
+    pub fn new(players: Vec<(Uuid, String)>, move_time: Duration) -> Self
+    {
+        let created_at = Utc::now();
+        let players = players.into_iter()
+                             .map(|(id, name)| RoyaleEntrant { id,
+                                                               name,
+                                                               throw: None,
+                                                               last_throw: None,
+                                                               eliminated_in: None,
+                                                               left: false })
+                             .collect();
+
+        Self { players,
+               round: 1,
+               created_at,
+               deadline: created_at + move_time,
+               move_time }
+    }
+
+    fn alive(&self) -> impl Iterator<Item = &RoyaleEntrant>
+    {
+        self.players.iter().filter(|p| p.eliminated_in.is_none())
+    }
+
+    pub fn has_player(&self, player_id: &Uuid) -> bool
+    {
+        self.players.iter().any(|p| p.id == *player_id)
+    }
+
+    pub fn throw(&mut self, player_id: &Uuid, mv: RpsMove) -> Result<(), GameError>
+    {
+        let player = self.players
+                         .iter_mut()
+                         .find(|p| p.id == *player_id)
+                         .ok_or(GameError::NotFound)?;
+
+        if player.eliminated_in.is_some() || player.left || player.throw.is_some() {
+            return Err(GameError::InvalidMove);
+        }
+
+        player.throw = Some(mv);
+        Ok(())
+    }
+
+    pub fn leave(&mut self, player_id: &Uuid)
+    {
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == *player_id) {
+            player.left = true;
+        }
+    }
+
+    /// Everyone still in and connected has thrown.
+    pub fn round_complete(&self) -> bool
+    {
+        self.alive().all(|p| p.left || p.throw.is_some())
+    }
+
+    pub fn resolve_round(&mut self)
+    {
+        let thrown: Vec<RpsMove> = self.alive().filter_map(|p| p.throw).collect();
+        let beaten = match RpsMove::ALL.map(|mv| thrown.contains(&mv)) {
+            [true, true, false] | [true, false, true] | [false, true, true] => {
+                thrown.iter()
+                      .copied()
+                      .find(|mv| thrown.iter().any(|other| other.beats(mv)))
+            }
+            _ => None,
+        };
+
+        let round = self.round;
+        for player in self.players.iter_mut().filter(|p| p.eliminated_in.is_none()) {
+            if player.throw.is_none() || player.throw == beaten {
+                player.eliminated_in = Some(round);
+            }
+            player.last_throw = player.throw.take();
+        }
+
+        self.round += 1;
+        self.deadline = Utc::now() + self.move_time;
+    }
+
+    pub fn is_over(&self) -> bool
+    {
+        self.alive().count() <= 1 || self.round > MAX_ROUNDS
+    }
+
+    /// 1 for the winner; players knocked out in the same round share a place.
+    pub fn place_of(&self, player: &RoyaleEntrant) -> u32
+    {
+        let outlasted_by = |p: &&RoyaleEntrant| match (p.eliminated_in, player.eliminated_in) {
+            (None, Some(_)) => true,
+            (Some(theirs), Some(mine)) => theirs > mine,
+            _ => false,
+        };
+
+        1 + self.players.iter().filter(outlasted_by).count() as u32
+    }
+
+    pub fn event(&self) -> RoyaleEvent
+    {
+        if self.is_over() {
+            let mut placements: Vec<RoyalePlacement> =
+                self.players
+                    .iter()
+                    .map(|p| RoyalePlacement { name: p.name.clone(),
+                                               place: self.place_of(p),
+                                               eliminated_in: p.eliminated_in })
+                    .collect();
+            placements.sort_by_key(|p| p.place);
+
+            return RoyaleEvent::Finished { rounds: self.round - 1,
+                                           placements };
+        }
+
+        let players = self.players
+                          .iter()
+                          .map(|p| RoyalePlayer { name: p.name.clone(),
+                                                  thrown: p.throw.is_some(),
+                                                  last_throw: p.last_throw,
+                                                  eliminated_in: p.eliminated_in })
+                          .collect();
+
+        RoyaleEvent::Round { round: self.round,
+                             players,
+                             deadline: self.deadline }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use RpsMove::*;
+
+    fn game(players: usize) -> RoyaleGame
+    {
+        let players = (0..players).map(|i| (Uuid::new_v4(), format!("player{i}")))
+                                  .collect();
+        RoyaleGame::new(players, Duration::seconds(30))
+    }
+
+    /// Throw `moves` in player order, `None` leaving a player idle, and close the round.
+    fn round(game: &mut RoyaleGame, moves: &[Option<RpsMove>])
+    {
+        let ids: Vec<Uuid> = game.players.iter().map(|p| p.id).collect();
+        for (id, mv) in ids.iter().zip(moves) {
+            if let Some(mv) = mv {
+                game.throw(id, *mv).unwrap();
+            }
+        }
+        game.resolve_round();
+    }
+
+    fn eliminated(game: &RoyaleGame) -> Vec<Option<u32>>
+    {
+        game.players.iter().map(|p| p.eliminated_in).collect()
+    }
+
+    #[test]
+    fn two_moves_knock_out_the_beaten_one()
+    {
+        let mut game = game(4);
+
+        round(&mut game, &[Some(Rock), Some(Scissors), Some(Rock), Some(Scissors)]);
+
+        assert_eq!(eliminated(&game), vec![None, Some(1), None, Some(1)]);
+        assert!(!game.is_over());
+    }
+
+    #[test]
+    fn one_or_three_moves_knock_out_nobody()
+    {
+        let mut game = game(3);
+
+        round(&mut game, &[Some(Paper), Some(Paper), Some(Paper)]);
+        round(&mut game, &[Some(Rock), Some(Paper), Some(Scissors)]);
+
+        assert_eq!(eliminated(&game), vec![None, None, None]);
+        assert_eq!(game.round, 3);
+    }
+
+    #[test]
+    fn idle_players_are_out()
+    {
+        let mut game = game(3);
+
+        round(&mut game, &[Some(Rock), None, Some(Rock)]);
+
+        assert_eq!(eliminated(&game), vec![None, Some(1), None]);
+    }
+
+    #[test]
+    fn eliminated_players_cannot_throw()
+    {
+        let mut game = game(3);
+        round(&mut game, &[Some(Rock), Some(Scissors), Some(Rock)]);
+
+        let out = game.players[1].id;
+
+        assert!(game.throw(&out, Paper).is_err());
+        assert!(!game.round_complete());
+    }
+
+    #[test]
+    fn placements_share_the_round_they_went_out_in()
+    {
+        let mut game = game(4);
+
+        round(&mut game, &[Some(Rock), Some(Scissors), Some(Rock), Some(Scissors)]);
+        round(&mut game, &[Some(Paper), None, Some(Rock), None]);
+
+        assert!(game.is_over());
+        let places: Vec<u32> = game.players.iter().map(|p| game.place_of(p)).collect();
+        assert_eq!(places, vec![1, 3, 2, 3]);
+    }
+
+    #[test]
+    fn stalled_game_ends_after_max_rounds()
+    {
+        let mut game = game(3);
+
+        for _ in 0..MAX_ROUNDS {
+            assert!(!game.is_over());
+            round(&mut game, &[Some(Rock), Some(Paper), Some(Scissors)]);
+        }
+
+        assert!(game.is_over());
+        assert!(game.players.iter().all(|p| game.place_of(p) == 1));
+    }
+}
@@ -0,0 +1,177 @@
+use rand::seq::IndexedRandom;
+use shared::rps_game::RpsMove;
+use uuid::Uuid;
+
+use crate::domain::game_model::Bot;
+use crate::domain::rps_model::RpsMatch;
+
+/// Move selection policy of an RPS bot.
+pub trait RpsStrategy: Send + Sync
+{
+    /// `history` holds the finished rounds of the match as `[bot_move, opp_move]`.
+    fn next_move(&self, history: &[[RpsMove; 2]]) -> RpsMove;
+}
+
+fn random_move() -> RpsMove
+{
+    *RpsMove::ALL.choose(&mut rand::rng()).unwrap()
+}
+
+/// Throws uniformly at random, which is unbeatable in the long run.
+pub struct UniformRandom;
+
+impl RpsStrategy for UniformRandom
+{
+    fn next_move(&self, _history: &[[RpsMove; 2]]) -> RpsMove
+    {
+        random_move()
+    }
+}
+
+/// Counters the opponent's most frequent move so far.
+pub struct FrequencyCounter;
+
+impl RpsStrategy for FrequencyCounter
+{
+    fn next_move(&self, history: &[[RpsMove; 2]]) -> RpsMove
+    {
+        let counts = RpsMove::ALL.map(|mv| history.iter().filter(|[_, opp]| *opp == mv).count());
+        let max = counts.iter().max().copied().unwrap_or(0);
+
+        if max == 0 {
+            return random_move();
+        }
+
+        let favourites: Vec<RpsMove> = RpsMove::ALL.into_iter()
+                                                   .zip(counts)
+                                                   .filter_map(|(mv, c)| (c == max).then_some(mv))
+                                                   .collect();
+
+        favourites.choose(&mut rand::rng()).unwrap().counter()
+    }
+}
+
+/// First-order Markov chain over the opponent's moves: predicts what usually follows
+/// their last throw and counters it.
+pub struct MarkovPredictor;
+
+impl RpsStrategy for MarkovPredictor
+{
+    fn next_move(&self, history: &[[RpsMove; 2]]) -> RpsMove
+    {
+        let Some([_, last]) = history.last() else {
+            return random_move();
+        };
+
+        let transitions: Vec<[RpsMove; 2]> = history.windows(2)
+                                                    .filter(|w| w[0][1] == *last)
+                                                    .map(|w| [w[0][1], w[1][1]])
+                                                    .collect();
+
+        FrequencyCounter.next_move(&transitions)
+    }
+}
+
+/// Plays whatever beats the opponent's previous throw.
+pub struct BeatLast;
+
+impl RpsStrategy for BeatLast
+{
+    fn next_move(&self, history: &[[RpsMove; 2]]) -> RpsMove
+    {
+        match history.last() {
+            Some([_, opp]) => opp.counter(),
+            None => random_move(),
+        }
+    }
+}
+
+pub struct RpsBot
+{
+    pub id: Uuid,
+    pub name: String,
+    pub strategy: Box<dyn RpsStrategy>,
+}
+
+impl RpsBot
+{
+    pub fn new(id: Uuid, name: &str, strategy: impl RpsStrategy + 'static) -> Self
+    {
+        Self { id,
+               name: name.to_string(),
+               strategy: Box::new(strategy) }
+    }
+}
+
+impl Bot<RpsMatch> for RpsBot
+{
+    fn id(&self) -> Uuid
+    {
+        self.id
+    }
+
+    fn name(&self) -> String
+    {
+        self.name.clone()
+    }
+
+    fn pick_move(&self, game: &RpsMatch) -> RpsMove
+    {
+        let history: Vec<[RpsMove; 2]> =
+            game.rounds
+                .iter()
+                .map(|r| {
+                    let [m1, m2] = r.moves;
+                    if r.players_id[0] == self.id { [m1, m2] } else { [m2, m1] }
+                })
+                .collect();
+
+        self.strategy.next_move(&history)
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use RpsMove::*;
+
+    /// History of a bot that always threw Rock against `opp_moves`.
+    fn against(opp_moves: &[RpsMove]) -> Vec<[RpsMove; 2]>
+    {
+        opp_moves.iter().map(|mv| [Rock, *mv]).collect()
+    }
+
+    #[test]
+    fn frequency_counter_beats_the_favourite()
+    {
+        let history = against(&[Scissors, Rock, Scissors, Paper]);
+
+        assert_eq!(FrequencyCounter.next_move(&history), Rock);
+    }
+
+    #[test]
+    fn markov_predictor_follows_transitions()
+    {
+        // Rock is always followed by Paper, which Scissors beats
+        let history = against(&[Rock, Paper, Scissors, Rock, Paper, Rock]);
+
+        assert_eq!(MarkovPredictor.next_move(&history), Scissors);
+    }
+
+    #[test]
+    fn markov_predictor_ignores_other_transitions()
+    {
+        let history = against(&[Paper, Paper, Paper, Scissors, Rock, Scissors, Rock]);
+
+        assert_eq!(MarkovPredictor.next_move(&history), Rock);
+    }
+
+    #[test]
+    fn beat_last_counters_the_previous_throw()
+    {
+        let history = against(&[Rock, Paper]);
+
+        assert_eq!(BeatLast.next_move(&history), Scissors);
+    }
+}
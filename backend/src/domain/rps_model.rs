@@ -1,24 +1,51 @@
+use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
-use shared::{game::GameResult, rps_game::*, ws_messages::ServerMsg};
+use serde::{Deserialize, Serialize};
+use shared::{
+    game::{GameEnvelope, GameError, GameResult},
+    history::{HistoryEntry, HistoryQuery},
+    rps_game::*,
+    ws_messages::ServerMsg,
+};
 use uuid::Uuid;
 
-use crate::domain::game_model::{ActiveGame, FinishedGame};
+use crate::domain::game_model::{duration_ms, ActiveGame, FinishedGame, PlayerTag};
 
-#[derive(Clone)]
+/// Read access to recorded RPS games, single ones and match rounds alike.
+#[async_trait]
+pub trait RpsArchive: Send + Sync
+{
+    /// Up to `limit` games of `username`, newest first, or `None` if there is no such user.
+    async fn history(&self,
+                     username: &str,
+                     filter: &HistoryQuery,
+                     limit: u32)
+                     -> Result<Option<Vec<HistoryEntry>>, GameError>;
+}
+
+/// Finished games played with RPS moves, for quests about winning with a given move.
+pub trait RpsWins
+{
+    /// Moves each player won a round with.
+    fn winning_moves(&self) -> [Vec<RpsMove>; 2];
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RpsPlayer
 {
     pub id: Uuid,
     pub current_move: Option<RpsMove>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct RpsGame
 {
     pub players: [RpsPlayer; 2],
     pub created_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct FinishedRpsGame
 {
     pub players_id: [Uuid; 2],
@@ -26,21 +53,72 @@ pub struct FinishedRpsGame
     pub created_at: DateTime<Utc>,
 }
 
+/// Drawn rounds a match replays before the score stands as it is.
+const MAX_DRAWS: usize = 10;
+
+/// Best-of-N series of `RpsGame` rounds between the same two players.
+///
+/// Drawn rounds are replayed, except in a best-of-one match where the single throw
+/// decides and a draw stands, just like a plain `RpsGame`. After `MAX_DRAWS` draws the
+/// match ends on the current score, which may itself be a draw.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RpsMatch
+{
+    pub best_of: BestOf,
+    pub round: RpsGame,
+    pub rounds: Vec<FinishedRpsGame>,
+    pub score: [u8; 2],
+    pub created_at: DateTime<Utc>,
+    /// Time each player has per round.
+    #[serde(with = "duration_ms")]
+    pub move_time: Duration,
+}
+
+#[derive(Clone)]
+pub struct FinishedRpsMatch
+{
+    pub players_id: [Uuid; 2],
+    pub best_of: BestOf,
+    pub score: [u8; 2],
+    pub rounds: Vec<FinishedRpsGame>,
+    pub created_at: DateTime<Utc>,
+    /// Player who let the move deadline pass and lost regardless of the score.
+    pub forfeited: Option<Uuid>,
+}
+
+impl FinishedRpsGame
+{
+    pub fn score(&self) -> [u8; 2]
+    {
+        match self.resolve() {
+            GameResult::Win => [1, 0],
+            GameResult::Defeat => [0, 1],
+            GameResult::Draw => [0, 0],
+        }
+    }
+}
+
 impl FinishedGame for FinishedRpsGame
 {
-    fn into_msg(&self, player_id: Uuid, player_name: &str, opp_name: &str) -> ServerMsg
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
     {
         let info = if player_id == self.players_id[0] {
-            RpsGameInfo { players: [player_name.to_string(),
-                                    opp_name.to_string()],
-                          moves: self.moves.clone() }
+            RpsGameInfo { players: [player.name.clone(), opp.name.clone()],
+                          moves: Some(self.moves),
+                          score: self.score(),
+                          best_of: BestOf::One,
+                          forfeited: [false, false] }
         } else {
-            RpsGameInfo { players: [opp_name.to_string(),
-                                    player_name.to_string()],
-                          moves: self.moves.clone() }
+            RpsGameInfo { players: [opp.name.clone(), player.name.clone()],
+                          moves: Some(self.moves),
+                          score: self.score(),
+                          best_of: BestOf::One,
+                          forfeited: [false, false] }
         };
 
-        ServerMsg::RpsGameMsg(RpsGameState::Finished(info))
+        let state = RpsGameState::Finished(info);
+
+        ServerMsg::GameMsg(GameEnvelope::state::<RpsKind>(&state))
     }
     fn resolve(&self) -> GameResult
     {
@@ -57,14 +135,25 @@ impl FinishedGame for FinishedRpsGame
         self.players_id.reverse();
         self.moves.reverse();
     }
+    fn players(&self) -> [Uuid; 2]
+    {
+        self.players_id
+    }
 }
 
-impl ActiveGame for RpsGame
+impl RpsWins for FinishedRpsGame
 {
-    type Move = RpsMove;
-    type FinishedGame = FinishedRpsGame;
+    fn winning_moves(&self) -> [Vec<RpsMove>; 2]
+    {
+        let [mv1, mv2] = self.moves;
+        [mv1.beats(&mv2).then_some(mv1).into_iter().collect(),
+         mv2.beats(&mv1).then_some(mv2).into_iter().collect()]
+    }
+}
 
-    fn new(player: Uuid, opponent: Uuid) -> Self
+impl RpsGame
+{
+    fn new(player: Uuid, opponent: Uuid, move_time: Duration) -> Self
     {
         let pl = RpsPlayer { id: player,
                              current_move: None };
@@ -73,15 +162,16 @@ impl ActiveGame for RpsGame
         let created_at = Utc::now();
 
         Self { players: [pl, op],
-               created_at }
+               created_at,
+               deadline: created_at + move_time }
     }
 
-    fn is_spoiled(&self) -> bool
+    fn players(&self) -> [Uuid; 2]
     {
-        (Utc::now() - self.created_at) >= Duration::minutes(2)
+        [self.players[0].id, self.players[1].id]
     }
 
-    fn set_move(&mut self, player_id: &Uuid, mv: RpsMove) -> Self
+    fn set_move(&mut self, player_id: &Uuid, mv: RpsMove)
     {
         let [p1, p2] = &mut self.players;
 
@@ -90,7 +180,6 @@ impl ActiveGame for RpsGame
         } else if p2.id == *player_id {
             p2.current_move.get_or_insert(mv);
         }
-        self.clone()
     }
 
     fn has_player(&self, player_id: &Uuid) -> bool
@@ -104,53 +193,228 @@ impl ActiveGame for RpsGame
         let [p1, p2] = &self.players;
 
         if p1.id == *player_id {
-            Some(p2.id.clone())
+            Some(p2.id)
         } else if p2.id == *player_id {
-            Some(p1.id.clone())
+            Some(p1.id)
         } else {
             None
         }
     }
 
+    fn try_resolve(&self) -> Option<FinishedRpsGame>
+    {
+        let [p1, p2] = &self.players;
+
+        Some(FinishedRpsGame { players_id: [p1.id, p2.id],
+                               moves: [p1.current_move?, p2.current_move?],
+                               created_at: self.created_at })
+    }
+}
+
+impl FinishedGame for FinishedRpsMatch
+{
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
+    {
+        let moves = self.rounds.last().map(|r| r.moves);
+
+        let players = if player_id == self.players_id[0] {
+            [player.name.clone(), opp.name.clone()]
+        } else {
+            [opp.name.clone(), player.name.clone()]
+        };
+
+        let info = RpsGameInfo { players,
+                                 moves,
+                                 score: self.score,
+                                 best_of: self.best_of,
+                                 forfeited: self.players_id.map(|id| self.forfeited == Some(id)) };
+
+        let state = RpsGameState::Finished(info);
+
+        ServerMsg::GameMsg(GameEnvelope::state::<RpsKind>(&state))
+    }
+    fn resolve(&self) -> GameResult
+    {
+        if let Some(forfeited) = self.forfeited {
+            return if forfeited == self.players_id[0] {
+                GameResult::Defeat
+            } else {
+                GameResult::Win
+            };
+        }
+
+        let [your_score, opp_score] = self.score;
+        if your_score > opp_score {
+            GameResult::Win
+        } else if your_score < opp_score {
+            GameResult::Defeat
+        } else {
+            GameResult::Draw
+        }
+    }
+    fn reverse(&mut self)
+    {
+        self.players_id.reverse();
+        self.score.reverse();
+        self.rounds.iter_mut().for_each(|r| r.reverse());
+    }
+    fn players(&self) -> [Uuid; 2]
+    {
+        self.players_id
+    }
+}
+
+impl RpsWins for FinishedRpsMatch
+{
+    fn winning_moves(&self) -> [Vec<RpsMove>; 2]
+    {
+        self.rounds
+            .iter()
+            .fold([Vec::new(), Vec::new()], |[mut won1, mut won2], round| {
+                let [round1, round2] = round.winning_moves();
+                won1.extend(round1);
+                won2.extend(round2);
+                [won1, won2]
+            })
+    }
+}
+
+impl ActiveGame for RpsMatch
+{
+    type Move = RpsMove;
+    type FinishedGame = FinishedRpsMatch;
+    type Settings = BestOf;
+    type Kind = RpsKind;
+
+    fn new(player: Uuid, opponent: Uuid, best_of: BestOf, move_time: Duration) -> Self
+    {
+        Self { best_of,
+               round: RpsGame::new(player, opponent, move_time),
+               rounds: Vec::new(),
+               score: [0, 0],
+               created_at: Utc::now(),
+               move_time }
+    }
+
+    fn settings(&self) -> BestOf
+    {
+        self.best_of
+    }
+
+    fn players(&self) -> [Uuid; 2]
+    {
+        self.round.players()
+    }
+
+    fn deadline(&self) -> DateTime<Utc>
+    {
+        self.round.deadline
+    }
+
+    fn time_out(&self) -> Option<FinishedRpsMatch>
+    {
+        let idle: Vec<Uuid> = self.round
+                                  .players
+                                  .iter()
+                                  .filter(|p| p.current_move.is_none())
+                                  .map(|p| p.id)
+                                  .collect();
+
+        let [forfeited] = idle[..] else {
+            return None;
+        };
+
+        self.forfeit(&forfeited)
+    }
+
+    fn forfeit(&self, loser: &Uuid) -> Option<FinishedRpsMatch>
+    {
+        self.has_player(loser).then(|| FinishedRpsMatch { players_id: self.players(),
+                                                          best_of: self.best_of,
+                                                          score: self.score,
+                                                          rounds: self.rounds.clone(),
+                                                          created_at: self.created_at,
+                                                          forfeited: Some(*loser) })
+    }
+
+    fn set_move(&mut self, player_id: &Uuid, mv: RpsMove) -> Self
+    {
+        self.round.set_move(player_id, mv);
+
+        if let Some(fin_round) = self.round.try_resolve() {
+            match fin_round.resolve() {
+                GameResult::Win => self.score[0] += 1,
+                GameResult::Defeat => self.score[1] += 1,
+                GameResult::Draw => {}
+            }
+            self.rounds.push(fin_round);
+
+            if !self.is_ready() {
+                let [p1, p2] = &self.round.players;
+                self.round = RpsGame::new(p1.id, p2.id, self.move_time);
+            }
+        }
+        self.clone()
+    }
+
+    fn has_player(&self, player_id: &Uuid) -> bool
+    {
+        self.round.has_player(player_id)
+    }
+
+    fn get_opp(&self, player_id: &Uuid) -> Option<Uuid>
+    {
+        self.round.get_opp(player_id)
+    }
+
     fn is_ready(&self) -> bool
     {
-        self.players.iter().all(|p| p.current_move.is_some())
+        let wins_needed = self.best_of.wins_needed();
+        let decided: usize = self.score.iter().map(|s| usize::from(*s)).sum();
+
+        self.score.iter().any(|s| *s >= wins_needed)
+        || (self.best_of == BestOf::One && !self.rounds.is_empty())
+        || self.rounds.len() - decided >= MAX_DRAWS
     }
 
-    fn try_resolve(&self) -> Option<FinishedRpsGame>
+    fn try_resolve(&self) -> Option<FinishedRpsMatch>
     {
         if !self.is_ready() {
             return None;
         }
-        let [p1, p2] = &self.players;
+        let [p1, p2] = &self.round.players;
 
-        Some(FinishedRpsGame { players_id: [p1.id, p2.id],
-                               moves: [p1.current_move.clone()?,
-                                       p2.current_move.clone()?],
-                               created_at: self.created_at })
+        Some(FinishedRpsMatch { players_id: [p1.id, p2.id],
+                                best_of: self.best_of,
+                                score: self.score,
+                                rounds: self.rounds.clone(),
+                                created_at: self.created_at,
+                                forfeited: None })
     }
 
-    fn into_msg(&self, player_id: Uuid, player_name: &str, opp_name: &str) -> ServerMsg
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
     {
         if !self.is_ready() {
-            let submitted = [self.players[0].current_move.is_some(),
-                             self.players[1].current_move.is_some()];
+            let [p1, p2] = &self.round.players;
+            let submitted = [p1.current_move.is_some(), p2.current_move.is_some()];
 
-            let state = if self.players[0].id == player_id {
-                RpsGameState::Game { players: [player_name.to_string(),
-                                               opp_name.to_string()],
-                                     submitted }
-            } else {
-                RpsGameState::Game { players: [opp_name.to_string(),
-                                               player_name.to_string()],
-                                     submitted }
-            };
+            let tags = if p1.id == player_id { [player, opp] } else { [opp, player] };
+            let round = u32::try_from(self.rounds.len() + 1).unwrap_or(u32::MAX);
+
+            let state = RpsGameState::Game { players: tags.map(|t| t.name.clone()),
+                                             ratings: tags.map(PlayerTag::rounded_rating),
+                                             submitted,
+                                             score: self.score,
+                                             best_of: self.best_of,
+                                             round,
+                                             last_round: self.rounds.last().map(|r| r.moves),
+                                             deadline: self.round.deadline };
 
-            ServerMsg::RpsGameMsg(state)
+            ServerMsg::GameMsg(GameEnvelope::state::<RpsKind>(&state))
         } else {
             self.try_resolve()
                 .unwrap()
-                .into_msg(player_id, player_name, opp_name)
+                .to_msg(player_id, player, opp)
         }
     }
 }
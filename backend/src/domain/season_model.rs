@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use shared::season::*;
+use uuid::Uuid;
+
+/// How far ratings are pulled back toward the mean when a season ends.
+#[derive(Clone, Copy, Debug)]
+pub struct SoftReset
+{
+    pub mean: f64,
+    /// Share of the distance to the mean a player keeps.
+    pub kept: f64,
+    /// Deviation every player starts the next season with at least, so their first games
+    /// move them quickly again.
+    pub min_deviation: f64,
+}
+
+pub const SOFT_RESET: SoftReset = SoftReset { mean: 1500.0,
+                                              kept: 0.5,
+                                              min_deviation: 150.0 };
+
+#[async_trait]
+pub trait SeasonService: Send + Sync
+{
+    /// Newest first.
+    async fn list(&self) -> Result<Vec<SeasonSummary>, SeasonError>;
+    /// Archived standings of a past season, live ones of any other, at most `limit` rows.
+    async fn details(&self, id: i64, limit: u32) -> Result<SeasonDetails, SeasonError>;
+    /// Fails with `Overlaps` if another season runs at any time in between.
+    async fn create(&self, user_id: Uuid, new: &NewSeason) -> Result<i64, SeasonError>;
+    /// Seasons past their end that still need archiving.
+    async fn ended(&self) -> Result<Vec<i64>, SeasonError>;
+    /// Store the final standings of a season, then soft-reset every rating. Does nothing
+    /// if the season was archived already.
+    async fn archive(&self, id: i64, reset: SoftReset) -> Result<(), SeasonError>;
+}
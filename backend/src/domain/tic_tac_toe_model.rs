@@ -0,0 +1,303 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{
+    game::{GameEnvelope, GameError, GameResult},
+    tic_tac_toe::*,
+    ws_messages::ServerMsg,
+};
+use uuid::Uuid;
+
+use crate::domain::game_model::{duration_ms, ActiveGame, FinishedGame, PlayerTag};
+
+/// Tic-tac-toe on a 3x3 board. The first player plays X and moves first, after that
+/// the players take turns, each with `move_time` to place their mark.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TicTacToe
+{
+    pub players: [Uuid; 2],
+    pub board: Board,
+    pub created_at: DateTime<Utc>,
+    pub deadline: DateTime<Utc>,
+    #[serde(with = "duration_ms")]
+    pub move_time: Duration,
+}
+
+#[derive(Clone)]
+pub struct FinishedTicTacToe
+{
+    pub players_id: [Uuid; 2],
+    pub board: Board,
+    pub forfeited: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TicTacToe
+{
+    fn finish(&self, forfeited: Option<Uuid>) -> FinishedTicTacToe
+    {
+        FinishedTicTacToe { players_id: self.players,
+                            board: self.board,
+                            forfeited,
+                            created_at: self.created_at }
+    }
+
+    /// Index of the player whose mark goes next.
+    fn turn_index(&self) -> usize
+    {
+        match next_mark(&self.board) {
+            Mark::X => 0,
+            Mark::O => 1,
+        }
+    }
+}
+
+impl FinishedTicTacToe
+{
+    pub fn info(&self, players: [String; 2]) -> TicTacToeInfo
+    {
+        TicTacToeInfo { players,
+                        board: self.board,
+                        forfeited: self.players_id.map(|id| self.forfeited == Some(id)) }
+    }
+}
+
+impl FinishedGame for FinishedTicTacToe
+{
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
+    {
+        let players = if player_id == self.players_id[0] {
+            [player.name.clone(), opp.name.clone()]
+        } else {
+            [opp.name.clone(), player.name.clone()]
+        };
+
+        let state = TicTacToeState::Finished(self.info(players));
+
+        ServerMsg::GameMsg(GameEnvelope::state::<TicTacToeKind>(&state))
+    }
+    fn resolve(&self) -> GameResult
+    {
+        self.info(Default::default()).resolve()
+    }
+    /// Swaps the players along with their marks, so the first player still plays X.
+    fn reverse(&mut self)
+    {
+        self.players_id.reverse();
+        self.board = self.board.map(|cell| cell.map(Mark::other));
+    }
+    fn players(&self) -> [Uuid; 2]
+    {
+        self.players_id
+    }
+}
+
+impl ActiveGame for TicTacToe
+{
+    type Move = u8;
+    type FinishedGame = FinishedTicTacToe;
+    type Settings = ();
+    type Kind = TicTacToeKind;
+
+    fn new(player: Uuid, opponent: Uuid, _settings: (), move_time: Duration) -> Self
+    {
+        let created_at = Utc::now();
+
+        Self { players: [player, opponent],
+               board: [None; 9],
+               created_at,
+               deadline: created_at + move_time,
+               move_time }
+    }
+
+    fn settings(&self) {}
+
+    fn players(&self) -> [Uuid; 2]
+    {
+        self.players
+    }
+
+    fn deadline(&self) -> DateTime<Utc>
+    {
+        self.deadline
+    }
+
+    /// The player on turn forfeits.
+    fn time_out(&self) -> Option<FinishedTicTacToe>
+    {
+        let idle = self.turn()?;
+        Some(self.finish(Some(idle)))
+    }
+
+    fn forfeit(&self, loser: &Uuid) -> Option<FinishedTicTacToe>
+    {
+        self.has_player(loser).then(|| self.finish(Some(*loser)))
+    }
+
+    fn validate_move(&self, player_id: &Uuid, mv: &u8) -> Result<(), GameError>
+    {
+        if !self.has_player(player_id) {
+            return Err(GameError::NotFound);
+        }
+
+        let cell = *mv as usize;
+        if self.turn() != Some(*player_id) || cell >= self.board.len() || self.board[cell].is_some()
+        {
+            return Err(GameError::InvalidMove);
+        }
+
+        Ok(())
+    }
+
+    fn set_move(&mut self, player_id: &Uuid, mv: u8) -> Self
+    {
+        if self.validate_move(player_id, &mv).is_err() {
+            return self.clone();
+        }
+
+        self.board[mv as usize] = Some(next_mark(&self.board));
+        self.deadline = Utc::now() + self.move_time;
+        self.clone()
+    }
+
+    fn has_player(&self, player_id: &Uuid) -> bool
+    {
+        self.players.contains(player_id)
+    }
+
+    fn get_opp(&self, player_id: &Uuid) -> Option<Uuid>
+    {
+        let [p1, p2] = self.players;
+
+        if p1 == *player_id {
+            Some(p2)
+        } else if p2 == *player_id {
+            Some(p1)
+        } else {
+            None
+        }
+    }
+
+    /// Over once a line is complete or the board is full.
+    fn is_ready(&self) -> bool
+    {
+        winning_line(&self.board).is_some() || self.board.iter().all(Option::is_some)
+    }
+
+    fn turn(&self) -> Option<Uuid>
+    {
+        (!self.is_ready()).then(|| self.players[self.turn_index()])
+    }
+
+    fn try_resolve(&self) -> Option<FinishedTicTacToe>
+    {
+        self.is_ready().then(|| self.finish(None))
+    }
+
+    fn to_msg(&self, player_id: Uuid, player: &PlayerTag, opp: &PlayerTag) -> ServerMsg
+    {
+        if self.is_ready() {
+            return self.try_resolve()
+                       .unwrap()
+                       .to_msg(player_id, player, opp);
+        }
+
+        let tags = if self.players[0] == player_id { [player, opp] } else { [opp, player] };
+
+        let state = TicTacToeState::Playing { players: tags.map(|t| t.name.clone()),
+                                              ratings: tags.map(PlayerTag::rounded_rating),
+                                              board: self.board,
+                                              turn: next_mark(&self.board),
+                                              deadline: self.deadline };
+
+        ServerMsg::GameMsg(GameEnvelope::state::<TicTacToeKind>(&state))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Play `cells` in turn, X first, and return the finished game if it ended.
+    fn play(cells: &[u8]) -> (TicTacToe, Option<FinishedTicTacToe>)
+    {
+        let mut game = TicTacToe::new(Uuid::new_v4(), Uuid::new_v4(), (), Duration::seconds(30));
+        for cell in cells {
+            let player = game.turn().expect("game over too early");
+            game.set_move(&player, *cell);
+        }
+        let finished = game.try_resolve();
+        (game, finished)
+    }
+
+    #[test]
+    fn row_wins()
+    {
+        let (_, finished) = play(&[0, 3, 1, 4, 2]);
+
+        assert_eq!(finished.unwrap().resolve(), GameResult::Win);
+    }
+
+    #[test]
+    fn column_wins()
+    {
+        let (_, finished) = play(&[0, 1, 3, 2, 6]);
+        let (_, o_wins) = play(&[0, 2, 1, 5, 6, 8]);
+
+        assert_eq!(finished.unwrap().resolve(), GameResult::Win);
+        assert_eq!(o_wins.unwrap().resolve(), GameResult::Defeat);
+    }
+
+    #[test]
+    fn diagonals_win()
+    {
+        let (_, main) = play(&[0, 1, 4, 2, 8]);
+        let (_, anti) = play(&[0, 2, 1, 4, 8, 6]);
+
+        assert_eq!(main.unwrap().resolve(), GameResult::Win);
+        assert_eq!(anti.unwrap().resolve(), GameResult::Defeat);
+    }
+
+    #[test]
+    fn full_board_without_line_is_a_draw()
+    {
+        let (game, finished) = play(&[0, 1, 2, 4, 3, 5, 7, 6, 8]);
+
+        assert_eq!(game.turn(), None);
+        assert_eq!(finished.unwrap().resolve(), GameResult::Draw);
+    }
+
+    #[test]
+    fn rejects_moves_out_of_turn_or_on_taken_cells()
+    {
+        let (game, finished) = play(&[4]);
+        let [x, o] = game.players;
+
+        assert!(finished.is_none());
+        assert!(game.validate_move(&x, &0).is_err());
+        assert!(game.validate_move(&o, &4).is_err());
+        assert!(game.validate_move(&o, &9).is_err());
+        assert!(game.validate_move(&o, &0).is_ok());
+    }
+
+    #[test]
+    fn player_on_turn_times_out()
+    {
+        let (game, _) = play(&[4]);
+
+        let finished = game.time_out().unwrap();
+
+        assert_eq!(finished.forfeited, Some(game.players[1]));
+        assert_eq!(finished.resolve(), GameResult::Win);
+    }
+
+    #[test]
+    fn reverse_keeps_the_result_for_each_player()
+    {
+        let (_, finished) = play(&[0, 3, 1, 4, 2]);
+        let mut reversed = finished.unwrap();
+        reversed.reverse();
+
+        assert_eq!(reversed.resolve(), GameResult::Defeat);
+    }
+}
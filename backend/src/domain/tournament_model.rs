@@ -0,0 +1,300 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::{rps_game::BestOf, tournament::*};
+use uuid::Uuid;
+
+/// Two players meeting in a round; no second player means a bye.
+pub type Pairing = (Uuid, Option<Uuid>);
+
+#[derive(Clone)]
+pub struct Tournament
+{
+    pub id: i64,
+    pub name: String,
+    pub format: TournamentFormat,
+    pub best_of: BestOf,
+    pub status: TournamentStatus,
+    pub created_by: Option<Uuid>,
+    pub rounds: u32,
+    pub current_round: u32,
+}
+
+#[derive(Clone)]
+pub struct Entrant
+{
+    pub user_id: Uuid,
+    /// 1 is the strongest player at the start.
+    pub seed: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub eliminated: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatchStatus
+{
+    /// Waiting for both players to be online and free.
+    Pending,
+    Playing,
+    Done,
+}
+
+#[derive(Clone)]
+pub struct ScheduledMatch
+{
+    pub id: i64,
+    pub tournament_id: i64,
+    pub round: u32,
+    pub slot: u32,
+    pub player1: Uuid,
+    pub player2: Option<Uuid>,
+    pub winner: Option<Uuid>,
+    pub status: MatchStatus,
+    /// How many times the game was abandoned and had to be replayed.
+    pub attempts: u32,
+    /// When the match became pending, or started playing.
+    pub since: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait TournamentService: Send + Sync
+{
+    async fn create(&self, user_id: Uuid, new: &NewTournament) -> Result<i64, TournamentError>;
+    async fn list(&self) -> Result<Vec<TournamentSummary>, TournamentError>;
+    async fn details(&self, id: i64, user_id: Uuid) -> Result<TournamentDetails, TournamentError>;
+    async fn tournament(&self, id: i64) -> Result<Tournament, TournamentError>;
+    async fn join(&self, id: i64, user_id: Uuid) -> Result<(), TournamentError>;
+    async fn leave(&self, id: i64, user_id: Uuid) -> Result<(), TournamentError>;
+    async fn set_status(&self, id: i64, status: TournamentStatus) -> Result<(), TournamentError>;
+
+    /// Open tournaments whose start time has come.
+    async fn due(&self) -> Result<Vec<Tournament>, TournamentError>;
+    async fn running(&self) -> Result<Vec<Tournament>, TournamentError>;
+    async fn entrants(&self, id: i64) -> Result<Vec<Entrant>, TournamentError>;
+    /// Store the seeds, the number of rounds and mark the tournament running.
+    async fn start(&self, id: i64, seeds: &[Uuid], rounds: u32) -> Result<(), TournamentError>;
+    /// Schedule the pairings as `round` and make it the current one.
+    async fn add_round(&self,
+                       id: i64,
+                       round: u32,
+                       pairings: &[Pairing])
+                       -> Result<(), TournamentError>;
+    /// Matches of every round so far, ordered by round and slot.
+    async fn matches(&self, id: i64) -> Result<Vec<ScheduledMatch>, TournamentError>;
+    async fn playing_match(&self,
+                           players: [Uuid; 2])
+                           -> Result<Option<ScheduledMatch>, TournamentError>;
+    async fn set_playing(&self, match_id: i64) -> Result<(), TournamentError>;
+    /// Put a match back to pending after its game was abandoned.
+    async fn reschedule(&self, match_id: i64) -> Result<(), TournamentError>;
+    /// Close a match and update the standings; `None` is a draw.
+    async fn record_result(&self,
+                           match_id: i64,
+                           winner: Option<Uuid>)
+                           -> Result<(), TournamentError>;
+}
+
+impl Entrant
+{
+    /// Swiss score doubled, so draws stay whole numbers.
+    pub fn half_points(&self) -> u32
+    {
+        2 * self.wins + self.draws
+    }
+}
+
+/// Rounds a single elimination bracket for `players` needs.
+pub fn elimination_rounds(players: usize) -> u32
+{
+    players.max(2).next_power_of_two().trailing_zeros()
+}
+
+/// Rounds of a Swiss tournament: enough for a single undefeated player to remain.
+pub fn swiss_rounds(players: usize) -> u32
+{
+    elimination_rounds(players)
+}
+
+/// Bracket positions of the seeds, so that the top seeds meet as late as possible:
+/// 1 v 8, 4 v 5, 2 v 7, 3 v 6 for eight players.
+fn bracket_order(size: usize) -> Vec<usize>
+{
+    let mut order = vec![1];
+    while order.len() < size {
+        let len = order.len() * 2;
+        order = order.into_iter().flat_map(|s| [s, len + 1 - s]).collect();
+    }
+    order
+}
+
+/// First round of a single elimination bracket for players sorted by seed.
+/// The bracket is padded to a power of two with byes, which go to the top seeds.
+pub fn elimination_first_round(seeded: &[Uuid]) -> Vec<Pairing>
+{
+    let size = seeded.len().max(2).next_power_of_two();
+    let seat = |seed: usize| seeded.get(seed - 1).copied();
+
+    bracket_order(size).chunks(2)
+                       .filter_map(|pair| match (seat(pair[0]), seat(pair[1])) {
+                           (Some(p1), p2) => Some((p1, p2)),
+                           (None, Some(p2)) => Some((p2, None)),
+                           (None, None) => None,
+                       })
+                       .collect()
+}
+
+/// Next elimination round: winners of neighbouring matches meet.
+/// `winners` follow the slot order of the previous round.
+pub fn elimination_next_round(winners: &[Uuid]) -> Vec<Pairing>
+{
+    winners.chunks(2)
+           .map(|pair| (pair[0], pair.get(1).copied()))
+           .collect()
+}
+
+/// Swiss pairing: players with equal scores meet, avoiding rematches where possible.
+/// With an odd count the lowest ranked player without a bye yet sits out.
+pub fn swiss_pairings(entrants: &[Entrant],
+                      played: &HashSet<(Uuid, Uuid)>,
+                      had_bye: &HashSet<Uuid>)
+                      -> Vec<Pairing>
+{
+    let mut ranked: Vec<&Entrant> = entrants.iter().collect();
+    ranked.sort_by(|a, b| {
+              b.half_points()
+               .cmp(&a.half_points())
+               .then(a.seed.cmp(&b.seed))
+          });
+
+    let mut pairings = Vec::new();
+
+    if ranked.len() % 2 == 1 {
+        let bye = ranked.iter()
+                        .rposition(|e| !had_bye.contains(&e.user_id))
+                        .unwrap_or(ranked.len() - 1);
+        pairings.push((ranked.remove(bye).user_id, None));
+    }
+
+    let met = |a: Uuid, b: Uuid| played.contains(&(a, b)) || played.contains(&(b, a));
+
+    while !ranked.is_empty() {
+        let player = ranked.remove(0);
+        let opp = ranked.iter()
+                        .position(|e| !met(player.user_id, e.user_id))
+                        .unwrap_or(0);
+        let opp = ranked.remove(opp);
+
+        pairings.push((player.user_id, Some(opp.user_id)));
+    }
+
+    pairings
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn players(count: usize) -> Vec<Uuid>
+    {
+        (0..count).map(|_| Uuid::new_v4()).collect()
+    }
+
+    fn entrant(user_id: Uuid, seed: u32, wins: u32) -> Entrant
+    {
+        Entrant { user_id,
+                  seed,
+                  wins,
+                  draws: 0,
+                  losses: 0,
+                  eliminated: false }
+    }
+
+    #[test]
+    fn rounds_cover_every_player()
+    {
+        assert_eq!(elimination_rounds(1), 1);
+        assert_eq!(elimination_rounds(2), 1);
+        assert_eq!(elimination_rounds(5), 3);
+        assert_eq!(elimination_rounds(8), 3);
+        assert_eq!(swiss_rounds(9), 4);
+    }
+
+    #[test]
+    fn top_seeds_meet_last()
+    {
+        assert_eq!(bracket_order(8), vec![1, 8, 4, 5, 2, 7, 3, 6]);
+    }
+
+    #[test]
+    fn byes_go_to_top_seeds()
+    {
+        let seeded = players(5);
+
+        let round = elimination_first_round(&seeded);
+
+        assert_eq!(round,
+                   vec![(seeded[0], None),
+                        (seeded[3], Some(seeded[4])),
+                        (seeded[1], None),
+                        (seeded[2], None)]);
+    }
+
+    #[test]
+    fn lone_player_gets_a_bye()
+    {
+        let seeded = players(1);
+
+        assert_eq!(elimination_first_round(&seeded), vec![(seeded[0], None)]);
+    }
+
+    #[test]
+    fn odd_winners_leave_a_bye()
+    {
+        let winners = players(3);
+
+        assert_eq!(elimination_next_round(&winners),
+                   vec![(winners[0], Some(winners[1])), (winners[2], None)]);
+    }
+
+    #[test]
+    fn swiss_pairs_equal_scores()
+    {
+        let ids = players(4);
+        let entrants = [entrant(ids[0], 1, 0),
+                        entrant(ids[1], 2, 1),
+                        entrant(ids[2], 3, 0),
+                        entrant(ids[3], 4, 1)];
+
+        let round = swiss_pairings(&entrants, &HashSet::new(), &HashSet::new());
+
+        assert_eq!(round, vec![(ids[1], Some(ids[3])), (ids[0], Some(ids[2]))]);
+    }
+
+    #[test]
+    fn swiss_avoids_rematches()
+    {
+        let ids = players(4);
+        let entrants: Vec<_> = ids.iter().zip(1..).map(|(id, seed)| entrant(*id, seed, 0)).collect();
+        let played = HashSet::from([(ids[1], ids[0])]);
+
+        let round = swiss_pairings(&entrants, &played, &HashSet::new());
+
+        assert_eq!(round, vec![(ids[0], Some(ids[2])), (ids[1], Some(ids[3]))]);
+    }
+
+    #[test]
+    fn swiss_bye_skips_players_who_had_one()
+    {
+        let ids = players(3);
+        let entrants: Vec<_> = ids.iter().zip(1..).map(|(id, seed)| entrant(*id, seed, 0)).collect();
+        let had_bye = HashSet::from([ids[2]]);
+
+        let round = swiss_pairings(&entrants, &HashSet::new(), &had_bye);
+
+        assert_eq!(round, vec![(ids[1], None), (ids[0], Some(ids[2]))]);
+    }
+}
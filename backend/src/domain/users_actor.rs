@@ -11,6 +11,8 @@ pub struct UsersActor
 {
     pub users_online: HashMap<Uuid, Slab<UnboundedSender<ServerMsg>>>,
     pub user_names: HashMap<Uuid, String>,
+    /// Reverse of `user_names`, usernames being unique.
+    pub user_ids: HashMap<String, Uuid>,
 }
 
 impl UsersActor
@@ -78,6 +80,13 @@ pub struct GetName
     pub user_id: Uuid,
 }
 
+#[derive(Message)]
+#[rtype(result = "Option<Uuid>")]
+pub struct GetId
+{
+    pub username: String,
+}
+
 // ---- Handlers for UsersActor
 
 impl Handler<Joined> for UsersActor
@@ -85,19 +94,13 @@ impl Handler<Joined> for UsersActor
     type Result = usize;
     fn handle(&mut self, msg: Joined, _ctx: &mut Self::Context) -> Self::Result
     {
-        if self.users_online.contains_key(&msg.user_id) {
-            let conns = self.users_online.get_mut(&msg.user_id).unwrap();
-            let conn_id = conns.insert(msg.tx);
-            return conn_id;
-        } else {
-            let mut conns = Slab::new();
-            let conn_id = conns.insert(msg.tx);
-
-            self.users_online.insert(msg.user_id, conns);
+        let conns = self.users_online.entry(msg.user_id).or_default();
+        if conns.is_empty() {
+            self.user_ids.insert(msg.username.clone(), msg.user_id);
             self.user_names.insert(msg.user_id, msg.username);
-
-            return conn_id;
         }
+
+        conns.insert(msg.tx)
     }
 }
 
@@ -111,7 +114,9 @@ impl Handler<Disconnected> for UsersActor
 
         if conns.is_empty() {
             self.users_online.remove(&msg.user_id);
-            self.user_names.remove(&msg.user_id);
+            if let Some(name) = self.user_names.remove(&msg.user_id) {
+                self.user_ids.remove(&name);
+            }
         };
     }
 }
@@ -171,3 +176,13 @@ impl Handler<IsOnline> for UsersActor
         self.users_online.contains_key(&msg.user_id)
     }
 }
+
+impl Handler<GetId> for UsersActor
+{
+    type Result = Option<Uuid>;
+
+    fn handle(&mut self, msg: GetId, _ctx: &mut Self::Context) -> Self::Result
+    {
+        self.user_ids.get(&msg.username).copied()
+    }
+}
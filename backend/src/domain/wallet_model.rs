@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use chrono::Duration;
+use shared::wallet::WalletError;
+use uuid::Uuid;
+
+/// Coins every user is given once a day, counted from midnight UTC.
+pub const DAILY_ALLOWANCE: i64 = 100;
+
+/// Why coins moved, one per ledger row.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LedgerReason
+{
+    Allowance,
+    /// Taken into escrow when a wagered game starts.
+    Stake,
+    /// Both stakes, to the winner.
+    Payout,
+    /// A stake given back after a draw or an abandoned game.
+    Refund,
+}
+
+#[async_trait]
+pub trait WalletService: Send + Sync
+{
+    async fn balance(&self, user_id: Uuid) -> Result<i64, WalletError>;
+    /// Credit today's allowance unless the user already got it.
+    async fn grant_allowance(&self, user_id: Uuid, amount: i64) -> Result<(), WalletError>;
+    /// Take the stake from both players into escrow for the game between them. Fails with
+    /// `InsufficientFunds` if either can't cover it.
+    async fn open_wager(&self, players: [Uuid; 2], stake: u32) -> Result<(), WalletError>;
+    /// Give both players their stake back from the open wager between them, if any.
+    async fn refund_wager(&self, players: [Uuid; 2]) -> Result<(), WalletError>;
+    /// Refund wagers open longer than `age`, whose game can't still be running.
+    async fn refund_stale(&self, age: Duration) -> Result<(), WalletError>;
+}
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use shared::{achievement::*, game::GameError, rps_game::RpsMove};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{achievement_model::*, profile_model::win_streaks};
+
+pub struct PsqlAchievementService
+{
+    pub db: PgPool,
+}
+
+#[async_trait]
+impl AchievementService for PsqlAchievementService
+{
+    async fn stats(&self, user_id: Uuid) -> Result<PlayerStats, GameError>
+    {
+        let results = sqlx::query!(
+                                   r#"
+            SELECT win_counter, lose_counter, draw_counter
+            FROM rps_results
+            WHERE user_id = $1
+            "#,
+                                   user_id
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let scores = sqlx::query_scalar!(
+                                         r#"
+            SELECT score AS "score!"
+            FROM rps_rating_history
+            WHERE user_id = $1 AND score IS NOT NULL
+            ORDER BY created_at, id
+            "#,
+                                         user_id
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let won_rounds = sqlx::query!(
+                                      r#"
+            SELECT mv AS "mv!", COUNT(*) AS "count!"
+            FROM (
+                SELECT CASE WHEN player1 = $1 THEN move1 ELSE move2 END AS mv,
+                       CASE WHEN player1 = $1 THEN move2 ELSE move1 END AS opp
+                FROM rps_games
+                WHERE $1 IN (player1, player2)
+                UNION ALL
+                SELECT CASE WHEN player1 = $1 THEN move1 ELSE move2 END,
+                       CASE WHEN player1 = $1 THEN move2 ELSE move1 END
+                FROM rps_fair_games
+                WHERE $1 IN (player1, player2)
+            ) played
+            WHERE (mv, opp) IN (('Rock', 'Scissors'), ('Paper', 'Rock'), ('Scissors', 'Paper'))
+            GROUP BY mv
+            "#,
+                                      user_id
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let top_post_likes = sqlx::query_scalar!(
+                                                 r#"
+            SELECT COALESCE(MAX(like_count), 0) AS "likes!"
+            FROM posts
+            WHERE author_id = $1
+            "#,
+                                                 user_id
+        ).fetch_one(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let mut wins_by_move = [0; 3];
+        for row in won_rounds {
+            if let Ok(mv) = row.mv.parse::<RpsMove>()
+               && let Some(i) = RpsMove::ALL.iter().position(|m| *m == mv)
+            {
+                wins_by_move[i] = row.count as u32;
+            }
+        }
+
+        let (wins, games) = results.map_or((0, 0), |r| {
+                                       (r.win_counter as u32,
+                                        (r.win_counter + r.lose_counter + r.draw_counter) as u32)
+                                   });
+        let (_, best_streak) = win_streaks(scores);
+
+        Ok(PlayerStats { wins,
+                         games,
+                         best_streak,
+                         wins_by_move,
+                         top_post_likes: top_post_likes.max(0) as u32 })
+    }
+
+    async fn unlock(&self,
+                    user_id: Uuid,
+                    achievements: &[Achievement])
+                    -> Result<Vec<UnlockedAchievement>, GameError>
+    {
+        let keys: Vec<String> = achievements.iter()
+                                            .map(|a| key_of(*a).to_string())
+                                            .collect();
+
+        let rows = sqlx::query!(
+                                r#"
+            INSERT INTO user_achievements (user_id, achievement)
+            SELECT $1, UNNEST($2::text[])
+            ON CONFLICT (user_id, achievement) DO NOTHING
+            RETURNING achievement, unlocked_at
+            "#,
+                                user_id,
+                                &keys
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        Ok(rows.into_iter()
+               .filter_map(|r| {
+                   Some(UnlockedAchievement { achievement: from_key(&r.achievement)?,
+                                              unlocked_at: r.unlocked_at })
+               })
+               .collect())
+    }
+
+    async fn post_author(&self, post_id: i64) -> Result<Option<Uuid>, GameError>
+    {
+        sqlx::query_scalar!("SELECT author_id FROM posts WHERE id = $1", post_id)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|_e| GameError::DbError)
+    }
+}
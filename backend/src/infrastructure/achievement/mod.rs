@@ -0,0 +1,3 @@
+mod achievement_service;
+
+pub use achievement_service::*;
@@ -2,6 +2,7 @@ use actix_web::cookie::{Cookie, SameSite, time::Duration};
 use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
 
 use crate::application::auth_handler::*;
+use crate::application::wallet_handler::WalletHandler;
 use crate::domain::auth_model::AuthError;
 use crate::infrastructure::auth::extract_id;
 use shared::auth::*;
@@ -69,8 +70,16 @@ async fn logout() -> impl Responder {
 }
 
 #[get("/me")]
-async fn whoami(handler: web::Data<AuthHandler>, req: HttpRequest) -> impl Responder {
+async fn whoami(
+    handler: web::Data<AuthHandler>,
+    wallet: web::Data<WalletHandler>,
+    req: HttpRequest,
+) -> impl Responder {
     if let Some(id) = extract_id(&req) {
+        // Every visit starts here, so this is where the daily allowance is earned.
+        if let Err(err) = wallet.check_in(id).await {
+            eprintln!("Daily check-in failed for user {id}: {err:?}");
+        }
         match handler.get_userinfo(id).await {
             Ok(info) => HttpResponse::Ok().json(info),
 
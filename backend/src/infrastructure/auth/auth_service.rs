@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use shared::auth::Credentials;
 use shared::auth::UserInfo;
+use shared::game::RatingInfo;
 use sqlx::postgres::PgPool;
 use sqlx::query_as;
 use uuid::Uuid;
@@ -47,7 +48,29 @@ impl AuthService for PsqlAuthService {
             .map_err(|_| AuthError::DatabaseError)?;
 
         let user = user.ok_or(AuthError::InvalidCredentials)?;
-        Ok(user.into())
+
+        let rating = query_as::<_, (f64, f64)>(
+            "SELECT rating, deviation FROM rps_ratings WHERE user_id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|_| AuthError::DatabaseError)?;
+
+        let coins = query_as::<_, (i64,)>("SELECT balance FROM coin_accounts WHERE user_id = $1")
+            .bind(id)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|_| AuthError::DatabaseError)?;
+
+        let mut info: UserInfo = user.into();
+        if let Some((rating, deviation)) = rating {
+            info.rating = RatingInfo { rating, deviation };
+        }
+        if let Some((coins,)) = coins {
+            info.coins = coins;
+        }
+        Ok(info)
     }
 
     async fn get_user(&self, id: Uuid) -> Result<User, AuthError> {
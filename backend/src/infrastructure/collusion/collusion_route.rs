@@ -0,0 +1,34 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, post, web};
+use shared::collusion::CollusionCmd;
+
+use crate::application::{auth_handler::AuthHandler, collusion_handler::CollusionHandler};
+use crate::infrastructure::auth::extract_id;
+
+/// Moderator review of the pairs flagged by the collusion analysis.
+#[post("/collusion")]
+pub async fn collusion_control(
+    collusion_handler: web::Data<CollusionHandler>,
+    auth_handler: web::Data<AuthHandler>,
+    req: HttpRequest,
+    collusion_cmd: web::Json<CollusionCmd>,
+) -> impl Responder {
+    let Some(user_id) = extract_id(&req) else {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    };
+
+    let Ok(user) = auth_handler.get_user(user_id).await else {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    };
+
+    match collusion_cmd.into_inner() {
+        CollusionCmd::List => HttpResponse::Ok().json(collusion_handler.list(&user).await),
+
+        CollusionCmd::Dismiss { id } => {
+            HttpResponse::Ok().json(collusion_handler.dismiss(&user, id).await)
+        }
+
+        CollusionCmd::Exclude { id } => {
+            HttpResponse::Ok().json(collusion_handler.exclude(&user, id).await)
+        }
+    }
+}
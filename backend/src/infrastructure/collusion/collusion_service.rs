@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::{collusion::*, rps_game::RpsMove};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::collusion_model::*;
+
+pub struct PsqlCollusionService
+{
+    pub db: PgPool,
+}
+
+fn signal_to_str(signal: CollusionSignal) -> &'static str
+{
+    match signal {
+        CollusionSignal::HeadToHead => "head_to_head",
+        CollusionSignal::OneSided => "one_sided",
+        CollusionSignal::MovePattern => "move_pattern",
+    }
+}
+
+fn signal_from_str(s: &str) -> Option<CollusionSignal>
+{
+    match s {
+        "head_to_head" => Some(CollusionSignal::HeadToHead),
+        "one_sided" => Some(CollusionSignal::OneSided),
+        "move_pattern" => Some(CollusionSignal::MovePattern),
+        _ => None,
+    }
+}
+
+fn status_from_str(s: &str) -> FlagStatus
+{
+    match s {
+        "dismissed" => FlagStatus::Dismissed,
+        "excluded" => FlagStatus::Excluded,
+        _ => FlagStatus::Open,
+    }
+}
+
+fn move_index(mv: &str) -> Option<usize>
+{
+    let mv = mv.parse::<RpsMove>().ok()?;
+    RpsMove::ALL.iter().position(|m| *m == mv)
+}
+
+#[async_trait]
+impl CollusionService for PsqlCollusionService
+{
+    async fn pair_games(&self, since: DateTime<Utc>) -> Result<Vec<PairGames>, CollusionError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT LEAST(g.player1, g.player2) AS "first!",
+                   GREATEST(g.player1, g.player2) AS "second!",
+                   CASE WHEN g.player1 < g.player2 THEN g.move1 ELSE g.move2 END AS "move1!",
+                   CASE WHEN g.player1 < g.player2 THEN g.move2 ELSE g.move1 END AS "move2!",
+                   COUNT(*) AS "count!"
+            FROM rps_games g
+            JOIN users u1 ON u1.id = g.player1
+            JOIN users u2 ON u2.id = g.player2
+            WHERE g.ranked AND g.created_at >= $1
+              AND u1.role <> 'bot' AND u2.role <> 'bot'
+            GROUP BY 1, 2, 3, 4
+            "#,
+                                since
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| CollusionError::DbError)?;
+
+        let mut pairs: HashMap<[Uuid; 2], [[u32; 3]; 3]> = HashMap::new();
+        for row in rows {
+            if let (Some(i), Some(j)) = (move_index(&row.move1), move_index(&row.move2)) {
+                pairs.entry([row.first, row.second]).or_default()[i][j] += row.count as u32;
+            }
+        }
+
+        Ok(pairs.into_iter()
+                .map(|(players, moves)| PairGames { players, moves })
+                .collect())
+    }
+
+    async fn game_counts(&self,
+                         since: DateTime<Utc>)
+                         -> Result<HashMap<Uuid, u32>, CollusionError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT user_id AS "user_id!", COUNT(*) AS "count!"
+            FROM (
+                SELECT player1 AS user_id FROM rps_games WHERE ranked AND created_at >= $1
+                UNION ALL
+                SELECT player2 FROM rps_games WHERE ranked AND created_at >= $1
+            ) games
+            GROUP BY user_id
+            "#,
+                                since
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| CollusionError::DbError)?;
+
+        Ok(rows.into_iter()
+               .map(|r| (r.user_id, r.count as u32))
+               .collect())
+    }
+
+    async fn flag(&self, suspicion: &Suspicion) -> Result<(), CollusionError>
+    {
+        let signals: Vec<&str> = suspicion.signals
+                                          .iter()
+                                          .map(|s| signal_to_str(*s))
+                                          .collect();
+
+        sqlx::query!(
+                     r#"
+            INSERT INTO collusion_flags
+                (player1, player2, games, wins1, wins2, head_to_head, move_chi2, signals)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (player1, player2) DO UPDATE
+            SET games = $3, wins1 = $4, wins2 = $5, head_to_head = $6, move_chi2 = $7,
+                signals = $8
+            WHERE collusion_flags.status = 'open'
+            "#,
+                     suspicion.players[0],
+                     suspicion.players[1],
+                     suspicion.games as i32,
+                     suspicion.wins[0] as i32,
+                     suspicion.wins[1] as i32,
+                     suspicion.head_to_head,
+                     suspicion.move_chi2,
+                     &signals as &[&str],
+        ).execute(&self.db)
+         .await
+         .map_err(|_e| CollusionError::DbError)?;
+
+        Ok(())
+    }
+
+    async fn list(&self) -> Result<Vec<CollusionFlag>, CollusionError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT f.id, u1.name AS name1, u2.name AS name2, f.games, f.wins1, f.wins2,
+                   f.head_to_head, f.move_chi2, f.signals, f.status, f.flagged_at,
+                   r.name AS "reviewed_by?"
+            FROM collusion_flags f
+            JOIN users u1 ON u1.id = f.player1
+            JOIN users u2 ON u2.id = f.player2
+            LEFT JOIN users r ON r.id = f.reviewed_by
+            ORDER BY f.status <> 'open', f.flagged_at DESC
+            "#
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| CollusionError::DbError)?;
+
+        Ok(rows.into_iter()
+               .map(|r| CollusionFlag { id: r.id,
+                                        players: [r.name1, r.name2],
+                                        games: r.games as u32,
+                                        wins: [r.wins1 as u32, r.wins2 as u32],
+                                        head_to_head: r.head_to_head,
+                                        move_chi2: r.move_chi2,
+                                        signals: r.signals
+                                                  .iter()
+                                                  .filter_map(|s| signal_from_str(s))
+                                                  .collect(),
+                                        status: status_from_str(&r.status),
+                                        flagged_at: r.flagged_at,
+                                        reviewed_by: r.reviewed_by })
+               .collect())
+    }
+
+    async fn dismiss(&self, id: i64, by: Uuid) -> Result<(), CollusionError>
+    {
+        let updated = sqlx::query!(
+                                   r#"
+            UPDATE collusion_flags
+            SET status = 'dismissed', reviewed_by = $2, reviewed_at = now()
+            WHERE id = $1 AND status = 'open'
+            "#,
+                                   id,
+                                   by
+        ).execute(&self.db)
+         .await
+         .map_err(|_e| CollusionError::DbError)?;
+
+        match updated.rows_affected() {
+            0 => Err(CollusionError::NotFound),
+            _ => Ok(()),
+        }
+    }
+
+    async fn exclude(&self, id: i64, by: Uuid) -> Result<(), CollusionError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| CollusionError::DbError)?;
+
+        let pair = sqlx::query!(
+                                r#"
+            UPDATE collusion_flags
+            SET status = 'excluded', reviewed_by = $2, reviewed_at = now()
+            WHERE id = $1 AND status <> 'excluded'
+            RETURNING player1, player2
+            "#,
+                                id,
+                                by
+        ).fetch_optional(&mut *tx)
+         .await
+         .map_err(|_e| CollusionError::DbError)?
+         .ok_or(CollusionError::NotFound)?;
+
+        // Every rating change from a game, match or fair game between the two is
+        // subtracted from where their ratings are now.
+        sqlx::query!(
+                     r#"
+            WITH pair_changes AS (
+                UPDATE rps_rating_history h
+                SET excluded = TRUE
+                FROM rps_rating_history src
+                LEFT JOIN rps_games g ON g.id = src.game_id
+                LEFT JOIN rps_matches m ON m.id = src.match_id
+                LEFT JOIN rps_fair_games f ON f.id = src.fair_game_id
+                WHERE h.id = src.id
+                  AND NOT src.excluded
+                  AND LEAST(COALESCE(g.player1, m.player1, f.player1),
+                            COALESCE(g.player2, m.player2, f.player2)) = $1
+                  AND GREATEST(COALESCE(g.player1, m.player1, f.player1),
+                               COALESCE(g.player2, m.player2, f.player2)) = $2
+                RETURNING h.user_id, h.rating_after - h.rating_before AS delta
+            )
+            UPDATE rps_ratings r
+            SET rating = r.rating - c.delta, updated_at = now()
+            FROM (
+                SELECT user_id, SUM(delta) AS delta FROM pair_changes GROUP BY user_id
+            ) c
+            WHERE r.user_id = c.user_id
+            "#,
+                     pair.player1,
+                     pair.player2
+        ).execute(&mut *tx)
+         .await
+         .map_err(|_e| CollusionError::DbError)?;
+
+        tx.commit().await.map_err(|_e| CollusionError::DbError)
+    }
+}
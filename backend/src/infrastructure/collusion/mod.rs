@@ -0,0 +1,5 @@
+mod collusion_route;
+mod collusion_service;
+
+pub use collusion_route::*;
+pub use collusion_service::*;
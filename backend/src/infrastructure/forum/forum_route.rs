@@ -3,7 +3,10 @@ use actix_web::{HttpRequest, HttpResponse, Responder, post, web};
 use shared::forum::*;
 use shared::ws_messages::ServerMsg;
 
-use crate::application::{auth_handler::AuthHandler, forum_handler::ForumHandler};
+use crate::application::{
+    achievement_handler::AchievementHandler, auth_handler::AuthHandler,
+    forum_handler::ForumHandler, quest_handler::QuestHandler,
+};
 use crate::domain::auth_model::AuthError;
 use crate::domain::users_actor::{Broadcast, UsersActor};
 use crate::infrastructure::auth::extract_id;
@@ -11,6 +14,8 @@ use crate::infrastructure::auth::extract_id;
 #[post("/forum")]
 pub async fn forum_control(
     forum_handler: web::Data<ForumHandler>,
+    achievement_handler: web::Data<AchievementHandler>,
+    quest_handler: web::Data<QuestHandler>,
     auth_handler: web::Data<AuthHandler>,
     users_actor: web::Data<Addr<UsersActor>>,
     req: HttpRequest,
@@ -26,6 +31,7 @@ pub async fn forum_control(
                         users_actor.do_send(Broadcast {
                             msg: ServerMsg::NewPostMsg(post.clone()),
                         });
+                        quest_handler.posted(user_id).await;
                     };
                     HttpResponse::Ok().json(result)
                 }
@@ -44,6 +50,10 @@ pub async fn forum_control(
 
                 ForumCmd::LikePost { post_id } => {
                     let result = forum_handler.like_post(user_id, post_id).await;
+
+                    if result.is_ok() {
+                        let _ = achievement_handler.post_liked(post_id).await;
+                    }
                     HttpResponse::Ok().json(result)
                 }
 
@@ -0,0 +1,40 @@
+use sqlx::PgPool;
+use std::sync::Arc;
+
+use crate::domain::game_model::{ActiveGame, Bot};
+
+/// Make sure every bot owns a `users` row so its games can be recorded.
+/// The password hash is not a valid PHC string, so bot accounts can never log in.
+///
+/// Returns the bots that got their row. A bot whose name already belongs to a player
+/// is left out rather than renaming the player.
+pub async fn register_bot_accounts<G>(db: &PgPool,
+                                      bots: Vec<Arc<dyn Bot<G>>>)
+                                      -> Result<Vec<Arc<dyn Bot<G>>>, sqlx::Error>
+    where G: ActiveGame
+{
+    let mut registered = Vec::with_capacity(bots.len());
+
+    for bot in bots {
+        let res = sqlx::query(
+                              r#"
+            INSERT INTO users (id, name, password_hash, role)
+            VALUES ($1, $2, '!', 'bot')
+            ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name
+            "#,
+        ).bind(bot.id())
+         .bind(bot.name())
+         .execute(db)
+         .await;
+
+        match res {
+            Ok(_) => registered.push(bot),
+            Err(e) if e.as_database_error().is_some_and(|e| e.is_unique_violation()) => {
+                eprintln!("Bot {} is disabled, a player already owns its name", bot.name());
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(registered)
+}
@@ -1,11 +1,23 @@
 use async_trait::async_trait;
-use shared::game::{GameError, GameResult};
-use sqlx::PgPool;
+use shared::{
+    fair_rps::{FairRpsInfo, FairRpsRecord},
+    game::{GameError, GameResult},
+    history::{HistoryEntry, HistoryQuery},
+    rps_game::RpsMove,
+    tic_tac_toe::Mark,
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
 
 use crate::domain::{
-    game_model::{FinishedGame, GameRecorder},
-    rps_model::{FinishedRpsGame, RpsGame},
+    fair_rps_model::{FairGameArchive, FairRpsGame, FinishedFairRpsGame},
+    game_model::{FinishedGame, GameRecorder, RatingProvider},
+    rating::Rating,
+    royale_model::{RoyaleGame, RoyaleRecorder},
+    rps_model::{FinishedRpsMatch, RpsArchive, RpsMatch},
+    tic_tac_toe_model::{FinishedTicTacToe, TicTacToe},
 };
+use crate::infrastructure::wallet::settle_wager;
 
 pub struct PsqlGameRecorder
 {
@@ -13,104 +25,559 @@ pub struct PsqlGameRecorder
 }
 
 #[async_trait]
-impl GameRecorder<RpsGame> for PsqlGameRecorder
+impl GameRecorder<RpsMatch> for PsqlGameRecorder
 {
-    async fn record(&self, game: FinishedRpsGame) -> Result<(), GameError>
+    async fn record(&self, game: &mut FinishedRpsMatch, ranked: bool) -> Result<(), GameError>
     {
+        let mut tx = self.db.begin().await.map_err(|_e| GameError::DbError)?;
+        let ranked = ranked && !excluded_pair(&mut tx, game.players_id).await?;
+
+        let match_id = sqlx::query_scalar!(
+                                           r#"
+            INSERT INTO rps_matches
+                (player1, player2, best_of, score1, score2, created_at, ranked, forfeited_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id
+            "#,
+                                           game.players_id[0],
+                                           game.players_id[1],
+                                           game.best_of.rounds() as i16,
+                                           game.score[0] as i16,
+                                           game.score[1] as i16,
+                                           game.created_at,
+                                           ranked,
+                                           game.forfeited,
+        ).fetch_one(&mut *tx)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        for round in &game.rounds {
+            sqlx::query!(
+                         r#"
+            INSERT INTO rps_games
+                (player1, player2, move1, move2, created_at, match_id, ranked)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+                         round.players_id[0],
+                         round.players_id[1],
+                         round.moves[0].to_string(),
+                         round.moves[1].to_string(),
+                         round.created_at,
+                         match_id,
+                         ranked,
+            ).execute(&mut *tx)
+             .await
+             .map_err(|_e| GameError::DbError)?;
+        }
+
+        if ranked {
+            record_result(&mut tx, game.players_id, game.resolve()).await?;
+            update_ratings(&mut tx, game.players_id, game.resolve(), RatedGame::Match(match_id)).await?;
+        }
+        // Paid out whether or not the game counted for ratings.
+        settle_wager(&mut tx, game.players_id, game.resolve()).await
+                                                              .map_err(|_e| GameError::DbError)?;
+
+        tx.commit().await.map_err(|_e| GameError::DbError)
+    }
+}
+
+#[async_trait]
+impl GameRecorder<FairRpsGame> for PsqlGameRecorder
+{
+    async fn record(&self, game: &mut FinishedFairRpsGame, ranked: bool) -> Result<(), GameError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| GameError::DbError)?;
+        let ranked = ranked && !excluded_pair(&mut tx, game.players_id).await?;
+
+        let [reveal1, reveal2] = game.reveals.clone();
+        let [(move1, nonce1), (move2, nonce2)] =
+            [reveal1, reveal2].map(|r| r.map(|(mv, nonce)| (mv.to_string(), nonce)).unzip());
+
+        let fair_game_id = sqlx::query_scalar!(
+                                               r#"
+            INSERT INTO rps_fair_games
+                (player1, player2, commitment1, commitment2, move1, move2,
+                 nonce1, nonce2, forfeited_by, ranked, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING id
+            "#,
+                                               game.players_id[0],
+                                               game.players_id[1],
+                                               game.commitments[0],
+                                               game.commitments[1],
+                                               move1,
+                                               move2,
+                                               nonce1,
+                                               nonce2,
+                                               game.forfeited,
+                                               ranked,
+                                               game.created_at,
+        ).fetch_one(&mut *tx)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        if ranked {
+            let result = game.resolve();
+            record_result(&mut tx, game.players_id, result).await?;
+            update_ratings(&mut tx, game.players_id, result, RatedGame::FairGame(fair_game_id))
+                .await?;
+        }
+
+        tx.commit().await.map_err(|_e| GameError::DbError)?;
+        game.id = Some(fair_game_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl GameRecorder<TicTacToe> for PsqlGameRecorder
+{
+    async fn record(&self, game: &mut FinishedTicTacToe, ranked: bool) -> Result<(), GameError>
+    {
+        let board: String = game.board
+                                .iter()
+                                .map(|cell| match cell {
+                                    Some(Mark::X) => 'X',
+                                    Some(Mark::O) => 'O',
+                                    None => '.',
+                                })
+                                .collect();
+        let winner_id = match game.resolve() {
+            GameResult::Win => Some(game.players_id[0]),
+            GameResult::Defeat => Some(game.players_id[1]),
+            GameResult::Draw => None,
+        };
+
         sqlx::query!(
                      r#"
-            INSERT INTO rps_games
-                (player1, player2, move1, move2, created_at)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO ttt_games
+                (player1, player2, board, winner, forfeited_by, ranked, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
             "#,
                      game.players_id[0],
                      game.players_id[1],
-                     game.moves[0].to_string(),
-                     game.moves[1].to_string(),
+                     board,
+                     winner_id,
+                     game.forfeited,
+                     ranked,
                      game.created_at,
         ).execute(&self.db)
          .await
          .map_err(|_e| GameError::DbError)?;
 
-        match game.resolve() {
-            GameResult::Win => {
-                sqlx::query!(
-                             r#"
-            INSERT INTO rps_results (user_id, win_counter)
-            VALUES ($1, 1)
-            ON CONFLICT (user_id)
-            DO UPDATE SET win_counter = rps_results.win_counter + 1
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RoyaleRecorder for PsqlGameRecorder
+{
+    async fn record_royale(&self, game: &RoyaleGame) -> Result<(), GameError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| GameError::DbError)?;
+
+        let royale_id = sqlx::query_scalar!(
+                                            r#"
+            INSERT INTO royale_games (rounds, created_at)
+            VALUES ($1, $2)
+            RETURNING id
             "#,
-                             game.players_id[0]
-                ).execute(&self.db)
-                 .await
-                 .map_err(|_e| GameError::DbError)?;
+                                            (game.round - 1) as i32,
+                                            game.created_at,
+        ).fetch_one(&mut *tx)
+         .await
+         .map_err(|_e| GameError::DbError)?;
 
-                sqlx::query!(
-                             r#"
-            INSERT INTO rps_results (user_id, lose_counter)
-            VALUES ($1, 1)
-            ON CONFLICT (user_id)
-            DO UPDATE SET lose_counter = rps_results.lose_counter + 1
+        let users: Vec<Uuid> = game.players.iter().map(|p| p.id).collect();
+        let places: Vec<i32> = game.players.iter().map(|p| game.place_of(p) as i32).collect();
+        let rounds: Vec<Option<i32>> =
+            game.players.iter().map(|p| p.eliminated_in.map(|r| r as i32)).collect();
+
+        sqlx::query!(
+                     r#"
+            INSERT INTO royale_placements (game_id, user_id, place, eliminated_in)
+            SELECT $1, * FROM UNNEST($2::uuid[], $3::int[], $4::int[])
             "#,
-                             game.players_id[1]
-                ).execute(&self.db)
-                 .await
-                 .map_err(|_e| GameError::DbError)?;
-            }
+                     royale_id,
+                     &users[..],
+                     &places[..],
+                     &rounds[..] as &[Option<i32>],
+        ).execute(&mut *tx)
+         .await
+         .map_err(|_e| GameError::DbError)?;
 
-            GameResult::Draw => {
-                sqlx::query!(
-                             r#"
-            INSERT INTO rps_results (user_id, draw_counter)
-            VALUES ($1, 1)
-            ON CONFLICT (user_id)
-            DO UPDATE SET draw_counter = rps_results.draw_counter + 1
+        tx.commit().await.map_err(|_e| GameError::DbError)
+    }
+}
+
+#[async_trait]
+impl FairGameArchive for PsqlGameRecorder
+{
+    async fn fair_game(&self, id: i64) -> Result<Option<FairRpsRecord>, GameError>
+    {
+        let row = sqlx::query!(
+                               r#"
+            SELECT g.id, g.created_at, g.forfeited_by,
+                   u1.name AS name1, u2.name AS name2,
+                   g.commitment1, g.commitment2, g.move1, g.move2, g.nonce1, g.nonce2,
+                   g.player1, g.player2
+            FROM rps_fair_games g
+            JOIN users u1 ON u1.id = g.player1
+            JOIN users u2 ON u2.id = g.player2
+            WHERE g.id = $1
             "#,
-                             game.players_id[0]
-                ).execute(&self.db)
-                 .await
-                 .map_err(|_e| GameError::DbError)?;
+                               id
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
 
-                sqlx::query!(
-                             r#"
-            INSERT INTO rps_results (user_id, draw_counter)
-            VALUES ($1, 1)
-            ON CONFLICT (user_id)
-            DO UPDATE SET draw_counter = rps_results.draw_counter + 1
+        Ok(row.map(|r| {
+                  let parse = |mv: Option<String>| mv.and_then(|mv| mv.parse().ok());
+                  let info = FairRpsInfo { id: Some(r.id),
+                                           players: [r.name1, r.name2],
+                                           commitments: [r.commitment1, r.commitment2],
+                                           moves: [parse(r.move1), parse(r.move2)],
+                                           nonces: [r.nonce1, r.nonce2],
+                                           forfeited: [r.forfeited_by == Some(r.player1),
+                                                       r.forfeited_by == Some(r.player2)] };
+
+                  FairRpsRecord { id: r.id,
+                                  created_at: r.created_at,
+                                  info }
+              }))
+    }
+}
+
+#[async_trait]
+impl RpsArchive for PsqlGameRecorder
+{
+    async fn history(&self,
+                     username: &str,
+                     filter: &HistoryQuery,
+                     limit: u32)
+                     -> Result<Option<Vec<HistoryEntry>>, GameError>
+    {
+        let Some(user_id) = sqlx::query_scalar!("SELECT id FROM users WHERE name = $1", username)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|_e| GameError::DbError)?
+        else {
+            return Ok(None);
+        };
+
+        let result = filter.result.map(|r| match r {
+                                      GameResult::Win => "win",
+                                      GameResult::Defeat => "defeat",
+                                      GameResult::Draw => "draw",
+                                  });
+
+        let rows = sqlx::query!(
+                                r#"
+            SELECT g.id, g.match_id, g.ranked, g.created_at,
+                   s.mine AS "mine!", s.theirs AS "theirs!", o.name AS opponent
+            FROM rps_games g
+            CROSS JOIN LATERAL (
+                SELECT CASE WHEN g.player1 = $1 THEN g.move1 ELSE g.move2 END AS mine,
+                       CASE WHEN g.player1 = $1 THEN g.move2 ELSE g.move1 END AS theirs,
+                       CASE WHEN g.player1 = $1 THEN g.player2 ELSE g.player1 END AS opponent_id
+            ) s
+            JOIN users o ON o.id = s.opponent_id
+            WHERE $1 IN (g.player1, g.player2)
+              AND ($2::text IS NULL OR o.name = $2)
+              AND ($3::bigint IS NULL OR g.id < $3)
+              AND ($4::text IS NULL OR $4 = CASE
+                      WHEN s.mine = s.theirs THEN 'draw'
+                      WHEN (s.mine, s.theirs) IN (('Rock', 'Scissors'), ('Paper', 'Rock'),
+                                                  ('Scissors', 'Paper')) THEN 'win'
+                      ELSE 'defeat'
+                  END)
+            ORDER BY g.id DESC
+            LIMIT $5
+            "#,
+                                user_id,
+                                filter.opponent,
+                                filter.before,
+                                result,
+                                limit as i64,
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let entries = rows.into_iter()
+                          .filter_map(|r| {
+                              let mine: RpsMove = r.mine.parse().ok()?;
+                              let theirs: RpsMove = r.theirs.parse().ok()?;
+                              let result = if mine == theirs {
+                                  GameResult::Draw
+                              } else if mine.beats(&theirs) {
+                                  GameResult::Win
+                              } else {
+                                  GameResult::Defeat
+                              };
+
+                              Some(HistoryEntry { id: r.id,
+                                                  opponent: r.opponent,
+                                                  moves: [mine, theirs],
+                                                  result,
+                                                  match_id: r.match_id,
+                                                  ranked: r.ranked,
+                                                  played_at: r.created_at })
+                          })
+                          .collect();
+
+        Ok(Some(entries))
+    }
+}
+
+#[async_trait]
+impl RatingProvider for PsqlGameRecorder
+{
+    /// Current rating, or the starting one for players who have not played a ranked game yet.
+    async fn rating(&self, user_id: Uuid) -> Option<Rating>
+    {
+        let row = sqlx::query!(
+                               r#"
+            SELECT rating, deviation, volatility
+            FROM rps_ratings
+            WHERE user_id = $1
+            "#,
+                               user_id
+        ).fetch_optional(&self.db)
+         .await
+         .ok()?;
+
+        Some(row.map(|r| Rating { rating: r.rating,
+                                  deviation: r.deviation,
+                                  volatility: r.volatility })
+                .unwrap_or_default())
+    }
+}
+
+/// Stored game a rating change came from.
+enum RatedGame
+{
+    Match(i64),
+    FairGame(i64),
+}
+
+/// Glicko-2 update for both players plus a history row each, `result` being from the first
+/// player's side. Rows are locked in a fixed order so concurrent games cannot deadlock.
+async fn update_ratings(conn: &mut PgConnection,
+                        players_id: [Uuid; 2],
+                        result: GameResult,
+                        source: RatedGame)
+                        -> Result<(), GameError>
+{
+    let (match_id, fair_game_id) = match source {
+        RatedGame::Match(id) => (Some(id), None),
+        RatedGame::FairGame(id) => (None, Some(id)),
+    };
+
+    sqlx::query!(
+                 r#"
+            INSERT INTO rps_ratings (user_id)
+            SELECT * FROM UNNEST($1::uuid[])
+            ON CONFLICT (user_id) DO NOTHING
+            "#,
+                 &players_id[..]
+    ).execute(&mut *conn)
+     .await
+     .map_err(|_e| GameError::DbError)?;
+
+    let rows = sqlx::query!(
+                            r#"
+            SELECT user_id, rating, deviation, volatility
+            FROM rps_ratings
+            WHERE user_id = ANY($1)
+            ORDER BY user_id
+            FOR UPDATE
+            "#,
+                            &players_id[..]
+    ).fetch_all(&mut *conn)
+     .await
+     .map_err(|_e| GameError::DbError)?;
+
+    let before = players_id.map(|id| {
+                               rows.iter()
+                                   .find(|r| r.user_id == id)
+                                   .map(|r| Rating { rating: r.rating,
+                                                     deviation: r.deviation,
+                                                     volatility: r.volatility })
+                                   .unwrap_or_default()
+                           });
+
+    let score = match result {
+        GameResult::Win => [1.0, 0.0],
+        GameResult::Defeat => [0.0, 1.0],
+        GameResult::Draw => [0.5, 0.5],
+    };
+    let after = [before[0].update(&before[1], score[0]),
+                 before[1].update(&before[0], score[1])];
+
+    for i in 0..2 {
+        sqlx::query!(
+                     r#"
+            UPDATE rps_ratings
+            SET rating = $2, deviation = $3, volatility = $4, updated_at = now()
+            WHERE user_id = $1
+            "#,
+                     players_id[i],
+                     after[i].rating,
+                     after[i].deviation,
+                     after[i].volatility,
+        ).execute(&mut *conn)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        sqlx::query!(
+                     r#"
+            INSERT INTO rps_rating_history
+                (user_id, match_id, fair_game_id, rating_before, deviation_before,
+                 rating_after, deviation_after, volatility_after, score)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+                     players_id[i],
+                     match_id,
+                     fair_game_id,
+                     before[i].rating,
+                     before[i].deviation,
+                     after[i].rating,
+                     after[i].deviation,
+                     after[i].volatility,
+                     score[i],
+        ).execute(&mut *conn)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+    }
+
+    Ok(())
+}
+
+/// Whether moderators excluded the pair's games from ratings for collusion.
+async fn excluded_pair(conn: &mut PgConnection, players_id: [Uuid; 2]) -> Result<bool, GameError>
+{
+    sqlx::query_scalar!(
+                        r#"
+            SELECT EXISTS (
+                SELECT 1 FROM collusion_flags
+                WHERE player1 = LEAST($1::uuid, $2::uuid)
+                  AND player2 = GREATEST($1::uuid, $2::uuid)
+                  AND status = 'excluded'
+            ) AS "excluded!"
+            "#,
+                        players_id[0],
+                        players_id[1]
+    ).fetch_one(conn)
+     .await
+     .map_err(|_e| GameError::DbError)
+}
+
+/// Bump win/lose/draw counters in `rps_results` and of the current season, `result` being from
+/// the first player's side.
+async fn record_result(conn: &mut PgConnection,
+                       players_id: [Uuid; 2],
+                       result: GameResult)
+                       -> Result<(), GameError>
+{
+    let [p1, p2] = players_id;
+
+    match result {
+        GameResult::Win => {
+            add_win(conn, p1).await?;
+            add_lose(conn, p2).await?;
+        }
+        GameResult::Defeat => {
+            add_win(conn, p2).await?;
+            add_lose(conn, p1).await?;
+        }
+        GameResult::Draw => {
+            add_draw(conn, p1).await?;
+            add_draw(conn, p2).await?;
+        }
+    }
+
+    add_season_result(conn, p1, result).await?;
+    add_season_result(conn, p2, result.reverse()).await?;
+
+    Ok(())
+}
+
+/// Count a result towards the season running right now, if any.
+async fn add_season_result(conn: &mut PgConnection,
+                           user_id: Uuid,
+                           result: GameResult)
+                           -> Result<(), GameError>
+{
+    sqlx::query!(
+                 r#"
+            INSERT INTO season_results (season_id, user_id, win_counter, lose_counter, draw_counter)
+            SELECT id, $1, $2, $3, $4
+            FROM seasons
+            WHERE starts_at <= now() AND ends_at > now() AND archived_at IS NULL
+            ON CONFLICT (season_id, user_id)
+            DO UPDATE SET win_counter = season_results.win_counter + EXCLUDED.win_counter,
+                          lose_counter = season_results.lose_counter + EXCLUDED.lose_counter,
+                          draw_counter = season_results.draw_counter + EXCLUDED.draw_counter
             "#,
-                             game.players_id[1]
-                ).execute(&self.db)
-                 .await
-                 .map_err(|_e| GameError::DbError)?;
-            }
-            GameResult::Defeat => {
-                sqlx::query!(
-                             r#"
+                 user_id,
+                 (result == GameResult::Win) as i32,
+                 (result == GameResult::Defeat) as i32,
+                 (result == GameResult::Draw) as i32,
+    ).execute(conn)
+     .await
+     .map_err(|_e| GameError::DbError)?;
+
+    Ok(())
+}
+
+async fn add_win(conn: &mut PgConnection, user_id: Uuid) -> Result<(), GameError>
+{
+    sqlx::query!(
+                 r#"
             INSERT INTO rps_results (user_id, win_counter)
             VALUES ($1, 1)
             ON CONFLICT (user_id)
             DO UPDATE SET win_counter = rps_results.win_counter + 1
             "#,
-                             game.players_id[1]
-                ).execute(&self.db)
-                 .await
-                 .map_err(|_e| GameError::DbError)?;
+                 user_id
+    ).execute(conn)
+     .await
+     .map_err(|_e| GameError::DbError)?;
 
-                sqlx::query!(
-                             r#"
+    Ok(())
+}
+
+async fn add_lose(conn: &mut PgConnection, user_id: Uuid) -> Result<(), GameError>
+{
+    sqlx::query!(
+                 r#"
             INSERT INTO rps_results (user_id, lose_counter)
             VALUES ($1, 1)
             ON CONFLICT (user_id)
             DO UPDATE SET lose_counter = rps_results.lose_counter + 1
             "#,
-                             game.players_id[0]
-                ).execute(&self.db)
-                 .await
-                 .map_err(|_e| GameError::DbError)?;
-            }
-        }
+                 user_id
+    ).execute(conn)
+     .await
+     .map_err(|_e| GameError::DbError)?;
 
-        Ok(())
-    }
+    Ok(())
+}
+
+async fn add_draw(conn: &mut PgConnection, user_id: Uuid) -> Result<(), GameError>
+{
+    sqlx::query!(
+                 r#"
+            INSERT INTO rps_results (user_id, draw_counter)
+            VALUES ($1, 1)
+            ON CONFLICT (user_id)
+            DO UPDATE SET draw_counter = rps_results.draw_counter + 1
+            "#,
+                 user_id
+    ).execute(conn)
+     .await
+     .map_err(|_e| GameError::DbError)?;
+
+    Ok(())
 }
@@ -0,0 +1,52 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, get, web};
+use shared::history::HistoryQuery;
+
+use crate::application::{
+    auth_handler::AuthHandler, fair_play_handler::FairPlayHandler, history_handler::HistoryHandler,
+};
+use crate::infrastructure::auth::extract_id;
+
+pub fn configure_games(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/games")
+            .service(fair_rps_game)
+            .service(rps_history),
+    );
+}
+
+/// Public record of a commit-reveal game, including nonces, for independent verification.
+#[get("/rps/fair/{id}")]
+async fn fair_rps_game(handler: web::Data<FairPlayHandler>, id: web::Path<i64>) -> impl Responder {
+    match handler.get_game(id.into_inner()).await {
+        Ok(Some(record)) => HttpResponse::Ok().json(record),
+        Ok(None) => HttpResponse::NotFound().body("Game not found."),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to load game."),
+    }
+}
+
+/// Past RPS games of a user, yours unless `username` is given. Paged by the `before` cursor.
+#[get("/rps/history")]
+async fn rps_history(
+    handler: web::Data<HistoryHandler>,
+    auth_handler: web::Data<AuthHandler>,
+    req: HttpRequest,
+    query: web::Query<HistoryQuery>,
+) -> impl Responder {
+    let Some(user_id) = extract_id(&req) else {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    };
+
+    let username = match &query.username {
+        Some(username) => username.clone(),
+        None => match auth_handler.get_user(user_id).await {
+            Ok(user) => user.name,
+            Err(_) => return HttpResponse::Unauthorized().body("Not logged in"),
+        },
+    };
+
+    match handler.history(&username, &query).await {
+        Ok(Some(page)) => HttpResponse::Ok().json(page),
+        Ok(None) => HttpResponse::NotFound().body("User not found."),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to load history."),
+    }
+}
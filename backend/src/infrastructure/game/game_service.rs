@@ -2,8 +2,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::Utc;
 use slab::Slab;
-use tokio::{sync::Mutex, time::Duration};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 use shared::game::GameError;
@@ -17,27 +18,17 @@ pub struct InMemoryGameService<G>
 {
     active_games: Arc<Mutex<Slab<G>>>,
     player_to_game: Arc<Mutex<HashMap<Uuid, GameId>>>,
+    move_time: chrono::Duration,
 }
 
 impl<G> InMemoryGameService<G> where G: ActiveGame + 'static
 {
-    pub fn new() -> Arc<Self>
+    pub fn new(move_time: chrono::Duration) -> Arc<Self>
     {
         let gs = Self { active_games: Arc::new(Mutex::new(Slab::new())),
-                        player_to_game: Arc::new(Mutex::new(HashMap::new())) };
-        let arc_gs = Arc::new(gs);
-
-        {
-            let arc_gs = arc_gs.clone();
-            tokio::spawn(async move {
-                loop {
-                    tokio::time::sleep(Duration::from_secs(30)).await;
-                    arc_gs.clear_spoiled().await;
-                }
-            });
-        }
-
-        arc_gs
+                        player_to_game: Arc::new(Mutex::new(HashMap::new())),
+                        move_time };
+        Arc::new(gs)
     }
 }
 
@@ -63,21 +54,35 @@ impl<G> GameService<G> for InMemoryGameService<G> where G: ActiveGame
         Some(game.clone())
     }
 
-    async fn start(&self, user_id: Uuid, opp_id: Uuid) -> G
+    async fn get_game_by_id(&self, game_id: GameId) -> Option<G>
     {
-        let game = G::new(user_id, opp_id);
+        let games = self.active_games.lock().await;
+        games.get(game_id).cloned()
+    }
 
-        let game_id = {
-            let mut games = self.active_games.lock().await;
-            let key = games.insert(game.clone());
-            key
-        };
+    async fn list_games(&self) -> Vec<(GameId, G)>
+    {
+        let games = self.active_games.lock().await;
+        games.iter().map(|(id, g)| (id, g.clone())).collect()
+    }
+
+    async fn start(&self, user_id: Uuid, opp_id: Uuid, settings: G::Settings)
+                   -> Result<G, GameError>
+    {
+        let game = G::new(user_id, opp_id, settings, self.move_time);
 
+        // same lock order as drop_for and take_timed_out: games first, then the index
+        let mut games = self.active_games.lock().await;
         let mut map = self.player_to_game.lock().await;
+        if map.contains_key(&user_id) || map.contains_key(&opp_id) {
+            return Err(GameError::AlreadyInGame);
+        }
+
+        let game_id = games.insert(game.clone());
         map.insert(user_id, game_id);
         map.insert(opp_id, game_id);
 
-        game
+        Ok(game)
     }
 
     async fn submit_move(&self, user_id: Uuid, mv: G::Move) -> Result<G, GameError>
@@ -91,6 +96,12 @@ impl<G> GameService<G> for InMemoryGameService<G> where G: ActiveGame
             let mut games = self.active_games.lock().await;
             let game = games.get_mut(game_id).ok_or(GameError::NotFound)?;
 
+            // late moves are refused, the game is about to be timed out
+            if game.deadline() < Utc::now() {
+                return Err(GameError::InvalidMove);
+            }
+            game.validate_move(&user_id, &mv)?;
+
             game.set_move(&user_id, mv);
             game.clone()
         };
@@ -166,16 +177,19 @@ impl<G> GameService<G> for InMemoryGameService<G> where G: ActiveGame
         finished
     }
 
-    async fn clear_spoiled(&self)
+    async fn take_timed_out(&self) -> Vec<G>
     {
         let mut games = self.active_games.lock().await;
         let mut map = self.player_to_game.lock().await;
 
-        let spoiled_k: Vec<usize> = games.iter()
-                                         .filter_map(|(id, g)| g.is_spoiled().then_some(id))
-                                         .collect();
+        let now = Utc::now();
+        let timed_out_k: Vec<usize> = games.iter()
+                                           .filter_map(|(id, g)| {
+                                               (!g.is_ready() && g.deadline() < now).then_some(id)
+                                           })
+                                           .collect();
 
-        games.retain(|id, _| !spoiled_k.contains(&id));
-        map.retain(|_, game_id| !spoiled_k.contains(game_id));
+        map.retain(|_, game_id| !timed_out_k.contains(game_id));
+        timed_out_k.into_iter().map(|id| games.remove(id)).collect()
     }
 }
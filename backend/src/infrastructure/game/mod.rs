@@ -1,11 +1,17 @@
+mod bot_accounts;
 mod game_recorder;
+mod game_route;
 mod game_service;
 mod notifier;
 mod player_queue;
 mod players_actor;
+mod psql_game_service;
 
+pub use bot_accounts::register_bot_accounts;
 pub use game_recorder::PsqlGameRecorder;
+pub use game_route::configure_games;
 pub use game_service::InMemoryGameService;
 pub use notifier::WsGameNotifier;
 pub use player_queue::ActorPlayerQueue;
 pub use players_actor::PlayersQueueActor;
+pub use psql_game_service::PsqlGameService;
@@ -45,4 +45,12 @@ impl GameNotifier for WsGameNotifier
             .await
             .unwrap()
     }
+
+    async fn find_online(&self, username: &str) -> Option<Uuid>
+    {
+        self.users_actor
+            .send(users_actor::GetId { username: username.to_string() })
+            .await
+            .unwrap()
+    }
 }
@@ -1,7 +1,7 @@
 use actix::Addr;
 use uuid::Uuid;
 
-use crate::domain::game_model::PlayerQueue;
+use crate::domain::game_model::{PlayerQueue, QueuedPlayer};
 use crate::infrastructure::game::players_actor::{self, PlayersQueueActor};
 
 #[derive(Clone)]
@@ -29,14 +29,9 @@ impl PlayerQueue for ActorPlayerQueue
             .unwrap_or(false)
     }
 
-    async fn add(&self, user_id: Uuid)
+    async fn add(&self, player: QueuedPlayer)
     {
-        self.addr.do_send(players_actor::Join { user_id });
-    }
-
-    async fn try_take(&self) -> Option<Uuid>
-    {
-        self.addr.send(players_actor::TryTake).await.unwrap_or(None)
+        self.addr.do_send(players_actor::Join { player });
     }
 
     async fn remove(&self, user_id: Uuid)
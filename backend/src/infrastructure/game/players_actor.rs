@@ -1,25 +1,121 @@
-use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 use actix::prelude::*;
+use tokio::sync::mpsc::UnboundedSender;
 use uuid::Uuid;
 
-#[derive(Clone, Default)]
+use crate::domain::game_model::QueuedPlayer;
+
+/// How often waiting players are paired.
+const TICK: Duration = Duration::from_secs(1);
+/// Rating gap accepted right after joining.
+const BASE_GAP: f64 = 100.0;
+/// How much the accepted gap grows per second of waiting.
+const GAP_PER_SEC: f64 = 25.0;
+const MAX_GAP: f64 = 1000.0;
+/// After this long two players who just met may be paired again.
+const REPEAT_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Clone)]
+struct Waiting
+{
+    player: QueuedPlayer,
+    since: Instant,
+}
+
+impl Waiting
+{
+    fn max_gap(&self, now: Instant) -> f64
+    {
+        let waited = now.duration_since(self.since).as_secs_f64();
+        (BASE_GAP + GAP_PER_SEC * waited).min(MAX_GAP)
+    }
+
+    fn fits(&self, other: &Waiting, now: Instant) -> bool
+    {
+        if self.player.stake != other.player.stake {
+            return false;
+        }
+
+        let gap = (self.player.rating - other.player.rating).abs();
+        if gap > self.max_gap(now).min(other.max_gap(now)) {
+            return false;
+        }
+
+        let met_last = self.player.last_opp == Some(other.player.user_id)
+                       || other.player.last_opp == Some(self.player.user_id);
+        let waited_long = now.duration_since(self.since) >= REPEAT_AFTER
+                          && now.duration_since(other.since) >= REPEAT_AFTER;
+
+        !met_last || waited_long
+    }
+}
+
+/// Matchmaking queue that pairs players by rating on a periodic tick and
+/// hands the pairs over through `matches`.
+#[derive(Clone)]
 pub struct PlayersQueueActor
 {
-    pub players: VecDeque<Uuid>,
+    players: Vec<Waiting>,
+    matches: UnboundedSender<[Uuid; 2]>,
 }
 
 impl PlayersQueueActor
 {
-    pub fn new() -> Self
+    pub fn new(matches: UnboundedSender<[Uuid; 2]>) -> Self
     {
-        Self::default()
+        Self { players: Vec::new(),
+               matches }
+    }
+
+    /// Greedily pair players, longest waiting first, each with the closest rated fitting opponent.
+    fn pair_players(&mut self)
+    {
+        let now = Instant::now();
+        let mut waiting = std::mem::take(&mut self.players);
+
+        let mut i = 0;
+        while i < waiting.len() {
+            let best = waiting.iter()
+                              .enumerate()
+                              .skip(i + 1)
+                              .filter(|(_, other)| waiting[i].fits(other, now))
+                              .min_by(|(_, a), (_, b)| {
+                                  let gap_a = (a.player.rating - waiting[i].player.rating).abs();
+                                  let gap_b = (b.player.rating - waiting[i].player.rating).abs();
+                                  gap_a.total_cmp(&gap_b)
+                              })
+                              .map(|(j, _)| j);
+
+            match best {
+                Some(j) => {
+                    let opp = waiting.remove(j);
+                    let player = waiting.remove(i);
+                    let pair = [player.player.user_id, opp.player.user_id];
+
+                    if self.matches.send(pair).is_err() {
+                        // nobody listens for matches, keep everyone waiting
+                        waiting.insert(i, player);
+                        waiting.insert(j, opp);
+                        break;
+                    }
+                }
+                None => i += 1,
+            }
+        }
+
+        self.players = waiting;
     }
 }
 
 impl Actor for PlayersQueueActor
 {
     type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context)
+    {
+        ctx.run_interval(TICK, |act, _ctx| act.pair_players());
+    }
 }
 
 // ---- Mesages for PlayersQueueActor
@@ -28,13 +124,9 @@ impl Actor for PlayersQueueActor
 #[rtype(result = "()")]
 pub struct Join
 {
-    pub user_id: Uuid,
+    pub player: QueuedPlayer,
 }
 
-#[derive(Message)]
-#[rtype(result = "Option<Uuid>")]
-pub struct TryTake;
-
 #[derive(Message)]
 #[rtype(result = "()")]
 pub struct Disconnected
@@ -54,8 +146,12 @@ impl Handler<Join> for PlayersQueueActor
     type Result = ();
     fn handle(&mut self, msg: Join, _ctx: &mut Self::Context) -> Self::Result
     {
-        if !self.players.contains(&msg.user_id) {
-            self.players.push_back(msg.user_id);
+        if !self.players
+                .iter()
+                .any(|w| w.player.user_id == msg.player.user_id)
+        {
+            self.players.push(Waiting { player: msg.player,
+                                        since: Instant::now() });
         }
     }
 }
@@ -65,28 +161,93 @@ impl Handler<Disconnected> for PlayersQueueActor
     type Result = ();
     fn handle(&mut self, msg: Disconnected, _ctx: &mut Self::Context) -> Self::Result
     {
-        if let Some(pos) = self.players.iter().position(|x| *x == msg.user_id) {
-            self.players.remove(pos);
-        }
+        self.players.retain(|w| w.player.user_id != msg.user_id);
     }
 }
 
-impl Handler<TryTake> for PlayersQueueActor
+impl Handler<Contains> for PlayersQueueActor
 {
-    type Result = Option<Uuid>;
+    type Result = bool;
 
-    fn handle(&mut self, _msg: TryTake, _ctx: &mut Self::Context) -> Self::Result
+    fn handle(&mut self, msg: Contains, _ctx: &mut Self::Context) -> Self::Result
     {
-        self.players.pop_front()
+        self.players.iter().any(|w| w.player.user_id == msg.user_id)
     }
 }
 
-impl Handler<Contains> for PlayersQueueActor
+#[cfg(test)]
+mod tests
 {
-    type Result = bool;
+    use tokio::sync::mpsc;
 
-    fn handle(&mut self, msg: Contains, _ctx: &mut Self::Context) -> Self::Result
+    use super::*;
+
+    fn waiting(rating: f64, stake: u32, waited: Duration) -> Waiting
+    {
+        Waiting { player: QueuedPlayer { user_id: Uuid::new_v4(),
+                                         rating,
+                                         last_opp: None,
+                                         stake },
+                  since: Instant::now() - waited }
+    }
+
+    fn pairs(players: Vec<Waiting>) -> Vec<[Uuid; 2]>
+    {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut queue = PlayersQueueActor::new(tx);
+        queue.players = players;
+        queue.pair_players();
+
+        let mut pairs = Vec::new();
+        while let Ok(pair) = rx.try_recv() {
+            pairs.push(pair);
+        }
+        pairs
+    }
+
+    #[test]
+    fn pairs_only_equal_stakes()
+    {
+        let long = Duration::from_secs(120);
+        let players = vec![waiting(1500.0, 0, long),
+                           waiting(1500.0, 10, long),
+                           waiting(1500.0, 50, long)];
+
+        assert!(pairs(players).is_empty());
+    }
+
+    #[test]
+    fn pairs_equal_stakes_over_closer_ratings()
     {
-        self.players.contains(&msg.user_id)
+        let now = Duration::ZERO;
+        let players = vec![waiting(1500.0, 10, now),
+                           waiting(1500.0, 0, now),
+                           waiting(1550.0, 10, now)];
+        let expected = [players[0].player.user_id, players[2].player.user_id];
+
+        assert_eq!(pairs(players), vec![expected]);
+    }
+
+    #[test]
+    fn accepted_gap_grows_with_waiting()
+    {
+        let fresh = vec![waiting(1500.0, 0, Duration::ZERO), waiting(1800.0, 0, Duration::ZERO)];
+        assert!(pairs(fresh).is_empty());
+
+        let long = Duration::from_secs(20);
+        let waited = vec![waiting(1500.0, 0, long), waiting(1800.0, 0, long)];
+        assert_eq!(pairs(waited).len(), 1);
+    }
+
+    #[test]
+    fn picks_the_closest_rating()
+    {
+        let now = Duration::ZERO;
+        let players = vec![waiting(1500.0, 5, now),
+                           waiting(1580.0, 5, now),
+                           waiting(1520.0, 5, now)];
+        let expected = [players[0].player.user_id, players[2].player.user_id];
+
+        assert_eq!(pairs(players), vec![expected]);
     }
 }
@@ -0,0 +1,265 @@
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use shared::game::GameError;
+
+use crate::domain::game_model::{ActiveGame, GameId, GameService};
+
+/// GameService that keeps every active game as a JSON row in `active_games`,
+/// so games in progress survive a backend restart.
+///
+/// Several game types share the table, `kind` tells them apart.
+pub struct PsqlGameService<G>
+{
+    db: PgPool,
+    kind: &'static str,
+    move_time: chrono::Duration,
+    _game: PhantomData<fn() -> G>,
+}
+
+impl<G> PsqlGameService<G> where G: ActiveGame + Serialize + DeserializeOwned + 'static
+{
+    pub fn new(db: PgPool, kind: &'static str, move_time: chrono::Duration) -> Arc<Self>
+    {
+        let gs = Self { db,
+                        kind,
+                        move_time,
+                        _game: PhantomData };
+        Arc::new(gs)
+    }
+
+    fn decode(state: serde_json::Value) -> Option<G>
+    {
+        serde_json::from_value(state).ok()
+    }
+
+    fn encode(game: &G) -> Result<serde_json::Value, GameError>
+    {
+        serde_json::to_value(game).map_err(|_e| GameError::DbError)
+    }
+}
+
+#[async_trait]
+impl<G> GameService<G> for PsqlGameService<G>
+    where G: ActiveGame + Serialize + DeserializeOwned + 'static
+{
+    async fn has_active_game(&self, user_id: Uuid) -> bool
+    {
+        sqlx::query_scalar!(
+                            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM active_games
+                WHERE kind = $1 AND (player1 = $2 OR player2 = $2)
+            ) AS "exists!"
+            "#,
+                            self.kind,
+                            user_id
+        ).fetch_one(&self.db)
+         .await
+         .unwrap_or(false)
+    }
+
+    async fn get_game(&self, user_id: Uuid) -> Option<G>
+    {
+        let state = sqlx::query_scalar!(
+                                        r#"
+            SELECT state FROM active_games
+            WHERE kind = $1 AND (player1 = $2 OR player2 = $2)
+            "#,
+                                        self.kind,
+                                        user_id
+        ).fetch_optional(&self.db)
+         .await
+         .ok()??;
+
+        Self::decode(state)
+    }
+
+    async fn get_game_by_id(&self, game_id: GameId) -> Option<G>
+    {
+        let state = sqlx::query_scalar!(
+                                        r#"
+            SELECT state FROM active_games
+            WHERE kind = $1 AND id = $2
+            "#,
+                                        self.kind,
+                                        game_id as i64
+        ).fetch_optional(&self.db)
+         .await
+         .ok()??;
+
+        Self::decode(state)
+    }
+
+    async fn list_games(&self) -> Vec<(GameId, G)>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT id, state FROM active_games
+            WHERE kind = $1
+            ORDER BY id
+            "#,
+                                self.kind
+        ).fetch_all(&self.db)
+         .await
+         .unwrap_or_default();
+
+        rows.into_iter()
+            .filter_map(|row| Some((row.id as GameId, Self::decode(row.state)?)))
+            .collect()
+    }
+
+    async fn start(&self, user_id: Uuid, opp_id: Uuid, settings: G::Settings)
+                   -> Result<G, GameError>
+    {
+        let game = G::new(user_id, opp_id, settings, self.move_time);
+
+        // a player already in a game of this kind breaks the key of active_game_players
+        sqlx::query!(
+                     r#"
+            WITH game AS (
+                INSERT INTO active_games (kind, player1, player2, state, deadline, created_at)
+                VALUES ($1, $2, $3, $4, $5, now())
+                RETURNING id
+            )
+            INSERT INTO active_game_players (kind, player_id, game_id)
+            SELECT $1, player_id, game.id
+            FROM game, unnest(ARRAY[$2::uuid, $3::uuid]) AS player_id
+            "#,
+                     self.kind,
+                     user_id,
+                     opp_id,
+                     Self::encode(&game)?,
+                     game.deadline()
+        ).execute(&self.db)
+         .await
+         .map_err(|e| match e.as_database_error() {
+             Some(db_err) if db_err.is_unique_violation() => GameError::AlreadyInGame,
+             _ => GameError::DbError,
+         })?;
+
+        Ok(game)
+    }
+
+    async fn submit_move(&self, user_id: Uuid, mv: G::Move) -> Result<G, GameError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| GameError::DbError)?;
+
+        let row = sqlx::query!(
+                               r#"
+            SELECT id, state FROM active_games
+            WHERE kind = $1 AND (player1 = $2 OR player2 = $2)
+            FOR UPDATE
+            "#,
+                               self.kind,
+                               user_id
+        ).fetch_optional(&mut *tx)
+         .await
+         .map_err(|_e| GameError::DbError)?
+         .ok_or(GameError::NotFound)?;
+
+        let mut game = Self::decode(row.state).ok_or(GameError::DbError)?;
+
+        // late moves are refused, the game is about to be timed out
+        if game.deadline() < Utc::now() {
+            return Err(GameError::InvalidMove);
+        }
+        game.validate_move(&user_id, &mv)?;
+        game.set_move(&user_id, mv);
+
+        sqlx::query!(
+                     r#"
+            UPDATE active_games SET state = $2, deadline = $3, ready = $4
+            WHERE id = $1
+            "#,
+                     row.id,
+                     Self::encode(&game)?,
+                     game.deadline(),
+                     game.is_ready()
+        ).execute(&mut *tx)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        tx.commit().await.map_err(|_e| GameError::DbError)?;
+
+        Ok(game)
+    }
+
+    async fn opponent_for(&self, user_id: Uuid) -> Option<Uuid>
+    {
+        self.get_game(user_id).await?.get_opp(&user_id)
+    }
+
+    async fn drop_for(&self, user_id: Uuid) -> Result<(), GameError>
+    {
+        let res = sqlx::query!(
+                               r#"
+            DELETE FROM active_games
+            WHERE kind = $1 AND (player1 = $2 OR player2 = $2)
+            "#,
+                               self.kind,
+                               user_id
+        ).execute(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(GameError::NotFound);
+        }
+
+        Ok(())
+    }
+
+    async fn try_resolve(&self, user_id: Uuid) -> Option<G::FinishedGame>
+    {
+        let mut tx = self.db.begin().await.ok()?;
+
+        let row = sqlx::query!(
+                               r#"
+            SELECT id, state FROM active_games
+            WHERE kind = $1 AND (player1 = $2 OR player2 = $2)
+            FOR UPDATE
+            "#,
+                               self.kind,
+                               user_id
+        ).fetch_optional(&mut *tx)
+         .await
+         .ok()??;
+
+        let finished = Self::decode(row.state)?.try_resolve()?;
+
+        sqlx::query!(
+                     r#"
+            DELETE FROM active_games WHERE id = $1
+            "#,
+                     row.id
+        ).execute(&mut *tx)
+         .await
+         .ok()?;
+        tx.commit().await.ok()?;
+
+        Some(finished)
+    }
+
+    async fn take_timed_out(&self) -> Vec<G>
+    {
+        let states = sqlx::query_scalar!(
+                                         r#"
+            DELETE FROM active_games
+            WHERE kind = $1 AND NOT ready AND deadline < now()
+            RETURNING state
+            "#,
+                                         self.kind
+        ).fetch_all(&self.db)
+         .await
+         .unwrap_or_default();
+
+        states.into_iter().filter_map(Self::decode).collect()
+    }
+}
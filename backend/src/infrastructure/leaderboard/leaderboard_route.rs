@@ -0,0 +1,17 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, get, web};
+use shared::leaderboard::LeaderboardQuery;
+
+use crate::application::leaderboard_handler::LeaderboardHandler;
+use crate::infrastructure::auth::extract_id;
+
+/// Public, but logged in players also get their own position.
+#[get("/leaderboard")]
+pub async fn leaderboard(
+    leaderboard_handler: web::Data<LeaderboardHandler>,
+    req: HttpRequest,
+    query: web::Query<LeaderboardQuery>,
+) -> impl Responder {
+    let user_id = extract_id(&req);
+
+    HttpResponse::Ok().json(leaderboard_handler.page(query.into_inner(), user_id).await)
+}
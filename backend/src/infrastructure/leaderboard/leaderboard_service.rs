@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::leaderboard::*;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::leaderboard_model::*;
+
+pub struct PsqlLeaderboardService
+{
+    pub db: PgPool,
+}
+
+fn sort_to_str(sort: LeaderboardSort) -> &'static str
+{
+    match sort {
+        LeaderboardSort::Wins => "wins",
+        LeaderboardSort::WinRate => "win_rate",
+        LeaderboardSort::Rating => "rating",
+    }
+}
+
+#[async_trait]
+impl LeaderboardService for PsqlLeaderboardService
+{
+    /// All-time totals come from the `rps_results` counters, windows are counted from the
+    /// rating history. Ties are broken by wins, then by name.
+    async fn ranking(&self,
+                     sort: LeaderboardSort,
+                     since: Option<DateTime<Utc>>,
+                     min_games: u32,
+                     offset: u32,
+                     limit: u32,
+                     user_id: Option<Uuid>)
+                     -> Result<Ranking, LeaderboardError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            WITH stats AS (
+                SELECT user_id,
+                       win_counter::bigint AS wins,
+                       lose_counter::bigint AS losses,
+                       draw_counter::bigint AS draws
+                FROM rps_results
+                WHERE $1::timestamptz IS NULL
+                UNION ALL
+                SELECT user_id,
+                       COUNT(*) FILTER (WHERE score = 1),
+                       COUNT(*) FILTER (WHERE score = 0),
+                       COUNT(*) FILTER (WHERE score = 0.5)
+                FROM rps_rating_history
+                WHERE created_at >= $1
+                GROUP BY user_id
+            ),
+            ranked AS (
+                SELECT u.id, u.name, s.wins, s.losses, s.draws,
+                       COALESCE(r.rating, 1500) AS rating,
+                       ROW_NUMBER() OVER (
+                           ORDER BY CASE $2::text
+                                        WHEN 'win_rate'
+                                            THEN s.wins::float8 / (s.wins + s.losses + s.draws)
+                                        WHEN 'rating' THEN COALESCE(r.rating, 1500)
+                                        ELSE s.wins::float8
+                                    END DESC,
+                                    s.wins DESC,
+                                    u.name
+                       ) AS rank,
+                       COUNT(*) OVER () AS total
+                FROM stats s
+                JOIN users u ON u.id = s.user_id
+                LEFT JOIN rps_ratings r ON r.user_id = s.user_id
+                WHERE u.role <> 'bot'
+                  AND s.wins + s.losses + s.draws > 0
+                  AND ($2 <> 'win_rate' OR s.wins + s.losses + s.draws >= $3)
+            )
+            SELECT id AS "id!", name AS "name!", wins AS "wins!", losses AS "losses!",
+                   draws AS "draws!", rating AS "rating!", rank AS "rank!", total AS "total!"
+            FROM ranked
+            WHERE (rank > $4 AND rank <= $4 + $5) OR id = $6
+            ORDER BY rank
+            "#,
+                                since,
+                                sort_to_str(sort),
+                                min_games as i64,
+                                offset as i64,
+                                limit as i64,
+                                user_id,
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| LeaderboardError::DbError)?;
+
+        let total = rows.first().map_or(0, |r| r.total as u32);
+        let mut entries = Vec::new();
+        let mut me = None;
+
+        for row in rows {
+            let entry = LeaderboardEntry { rank: row.rank as u32,
+                                           username: row.name,
+                                           wins: row.wins as u32,
+                                           losses: row.losses as u32,
+                                           draws: row.draws as u32,
+                                           rating: row.rating };
+
+            if Some(row.id) == user_id {
+                me = Some(entry.clone());
+            }
+            if row.rank > offset as i64 && row.rank <= (offset + limit) as i64 {
+                entries.push(entry);
+            }
+        }
+
+        Ok(Ranking { entries, total, me })
+    }
+}
@@ -0,0 +1,5 @@
+mod leaderboard_route;
+mod leaderboard_service;
+
+pub use leaderboard_route::*;
+pub use leaderboard_service::*;
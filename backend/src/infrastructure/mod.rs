@@ -1,3 +1,12 @@
+pub mod achievement;
 pub mod auth;
+pub mod collusion;
 pub mod forum;
 pub mod game;
+pub mod leaderboard;
+pub mod profile;
+pub mod quest;
+pub mod room;
+pub mod season;
+pub mod tournament;
+pub mod wallet;
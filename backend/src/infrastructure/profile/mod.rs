@@ -0,0 +1,5 @@
+mod profile_route;
+mod profile_service;
+
+pub use profile_route::*;
+pub use profile_service::*;
@@ -0,0 +1,37 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, get, web};
+
+use crate::application::profile_handler::ProfileHandler;
+use crate::infrastructure::auth::extract_id;
+
+#[get("/users/{username}")]
+pub async fn user_profile(
+    handler: web::Data<ProfileHandler>,
+    req: HttpRequest,
+    username: web::Path<String>,
+) -> impl Responder {
+    if extract_id(&req).is_none() {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    }
+
+    match handler.profile(&username).await {
+        Ok(Some(profile)) => HttpResponse::Ok().json(profile),
+        Ok(None) => HttpResponse::NotFound().body("User not found."),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to load profile."),
+    }
+}
+
+/// Move habits of the logged-in player; only your own are available.
+#[get("/moves")]
+pub async fn move_analytics(
+    handler: web::Data<ProfileHandler>,
+    req: HttpRequest,
+) -> impl Responder {
+    let Some(user_id) = extract_id(&req) else {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    };
+
+    match handler.move_analytics(user_id).await {
+        Ok(analytics) => HttpResponse::Ok().json(analytics),
+        Err(_) => HttpResponse::InternalServerError().body("Failed to analyse moves."),
+    }
+}
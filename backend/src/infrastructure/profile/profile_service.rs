@@ -0,0 +1,179 @@
+use async_trait::async_trait;
+use shared::{
+    achievement::UnlockedAchievement,
+    auth::UserInfo,
+    game::{GameError, RatingInfo},
+    profile::PlayerProfile,
+    rps_game::RpsMove,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::{achievement_model::from_key, auth_model::User, profile_model::*};
+
+pub struct PsqlProfileService
+{
+    pub db: PgPool,
+}
+
+#[async_trait]
+impl ProfileService for PsqlProfileService
+{
+    async fn profile(&self, username: &str) -> Result<Option<PlayerProfile>, GameError>
+    {
+        let Some(user) = sqlx::query_as::<_, User>("SELECT * FROM users WHERE name = $1")
+            .bind(username)
+            .fetch_optional(&self.db)
+            .await
+            .map_err(|_e| GameError::DbError)?
+        else {
+            return Ok(None);
+        };
+        let user_id = user.id;
+
+        let rating = sqlx::query!(
+                                  r#"
+            SELECT rating, deviation
+            FROM rps_ratings
+            WHERE user_id = $1
+            "#,
+                                  user_id
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let results = sqlx::query!(
+                                   r#"
+            SELECT win_counter, lose_counter, draw_counter
+            FROM rps_results
+            WHERE user_id = $1
+            "#,
+                                   user_id
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let scores = sqlx::query_scalar!(
+                                         r#"
+            SELECT score AS "score!"
+            FROM rps_rating_history
+            WHERE user_id = $1 AND score IS NOT NULL
+            ORDER BY created_at, id
+            "#,
+                                         user_id
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let moves = sqlx::query!(
+                                 r#"
+            SELECT mv AS "mv!", COUNT(*) AS "count!"
+            FROM (
+                SELECT CASE WHEN player1 = $1 THEN move1 ELSE move2 END AS mv
+                FROM rps_games
+                WHERE $1 IN (player1, player2)
+                UNION ALL
+                SELECT CASE WHEN player1 = $1 THEN move1 ELSE move2 END
+                FROM rps_fair_games
+                WHERE $1 IN (player1, player2)
+            ) played
+            WHERE mv IS NOT NULL
+            GROUP BY mv
+            "#,
+                                 user_id
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let forum_posts = sqlx::query_scalar!(
+                                              r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts
+            WHERE author_id = $1
+            "#,
+                                              user_id
+        ).fetch_one(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let achievements = sqlx::query!(
+                                        r#"
+            SELECT achievement, unlocked_at
+            FROM user_achievements
+            WHERE user_id = $1
+            ORDER BY unlocked_at
+            "#,
+                                        user_id
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        let mut info: UserInfo = user.into();
+        if let Some(r) = rating {
+            info.rating = RatingInfo { rating: r.rating,
+                                       deviation: r.deviation };
+        }
+
+        let mut move_counts = [0; 3];
+        for row in moves {
+            if let Ok(mv) = row.mv.parse::<RpsMove>()
+               && let Some(i) = RpsMove::ALL.iter().position(|m| *m == mv)
+            {
+                move_counts[i] = row.count as u32;
+            }
+        }
+
+        let (wins, losses, draws) = results.map_or((0, 0, 0), |r| {
+                                               (r.win_counter as u32,
+                                                r.lose_counter as u32,
+                                                r.draw_counter as u32)
+                                           });
+        let (current_streak, best_streak) = win_streaks(scores);
+        // keys of achievements since removed are skipped
+        let achievements =
+            achievements.into_iter()
+                        .filter_map(|r| {
+                            Some(UnlockedAchievement { achievement: from_key(&r.achievement)?,
+                                                      unlocked_at: r.unlocked_at })
+                        })
+                        .collect();
+
+        Ok(Some(PlayerProfile { user: info,
+                                wins,
+                                losses,
+                                draws,
+                                current_streak,
+                                best_streak,
+                                move_counts,
+                                recent: Vec::new(),
+                                forum_posts: forum_posts as u32,
+                                achievements }))
+    }
+    async fn rounds(&self, user_id: Uuid, limit: u32) -> Result<Vec<[RpsMove; 2]>, GameError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT own AS "own!", opp AS "opp!"
+            FROM (
+                SELECT id,
+                       created_at,
+                       CASE WHEN player1 = $1 THEN move1 ELSE move2 END AS own,
+                       CASE WHEN player1 = $1 THEN move2 ELSE move1 END AS opp
+                FROM rps_games
+                WHERE $1 IN (player1, player2)
+                ORDER BY created_at DESC, id DESC
+                LIMIT $2
+            ) latest
+            ORDER BY created_at, id
+            "#,
+                                user_id,
+                                limit as i64
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        Ok(rows.into_iter()
+               .filter_map(|r| Some([r.own.parse().ok()?, r.opp.parse().ok()?]))
+               .collect())
+    }
+}
@@ -0,0 +1,3 @@
+mod quest_service;
+
+pub use quest_service::*;
@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::{game::GameError, quest::*, rps_game::RpsMove};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::quest_model::*;
+
+pub struct PsqlQuestService
+{
+    pub db: PgPool,
+}
+
+fn period_to_str(period: QuestPeriod) -> &'static str
+{
+    match period {
+        QuestPeriod::Daily => "daily",
+        QuestPeriod::Weekly => "weekly",
+    }
+}
+
+fn period_from_str(s: &str) -> QuestPeriod
+{
+    match s {
+        "weekly" => QuestPeriod::Weekly,
+        _ => QuestPeriod::Daily,
+    }
+}
+
+/// Goal column along with the move column.
+fn goal_to_str(goal: QuestGoal) -> (&'static str, Option<String>)
+{
+    match goal {
+        QuestGoal::PlayGames => ("play", None),
+        QuestGoal::WinGames => ("win", None),
+        QuestGoal::WinWith(mv) => ("win_with", Some(mv.to_string())),
+        QuestGoal::PostInForum => ("post", None),
+    }
+}
+
+fn goal_from_str(s: &str, mv: Option<&str>) -> Option<QuestGoal>
+{
+    match s {
+        "play" => Some(QuestGoal::PlayGames),
+        "win" => Some(QuestGoal::WinGames),
+        "win_with" => mv?.parse::<RpsMove>().ok().map(QuestGoal::WinWith),
+        "post" => Some(QuestGoal::PostInForum),
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl QuestService for PsqlQuestService
+{
+    async fn create(&self,
+                    period: QuestPeriod,
+                    bounds: (DateTime<Utc>, DateTime<Utc>),
+                    quests: &[NewQuest])
+                    -> Result<(), GameError>
+    {
+        let (starts_at, ends_at) = bounds;
+        let slots: Vec<i16> = (0..quests.len() as i16).collect();
+        let (goals, moves): (Vec<&str>, Vec<Option<String>>) =
+            quests.iter().map(|q| goal_to_str(q.goal)).unzip();
+        let targets: Vec<i32> = quests.iter().map(|q| q.target as i32).collect();
+
+        sqlx::query!(
+                     r#"
+            INSERT INTO quests (period, slot, goal, mv, target, starts_at, ends_at)
+            SELECT $1, *, $6, $7 FROM UNNEST($2::smallint[], $3::text[], $4::text[], $5::int[])
+            ON CONFLICT (period, starts_at, slot) DO NOTHING
+            "#,
+                     period_to_str(period),
+                     &slots,
+                     &goals as &[&str],
+                     &moves as &[Option<String>],
+                     &targets,
+                     starts_at,
+                     ends_at,
+        ).execute(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        Ok(())
+    }
+
+    async fn current(&self, user_id: Uuid) -> Result<Vec<QuestInfo>, GameError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT q.id, q.period, q.goal, q.mv, q.target, q.ends_at,
+                   COALESCE(uq.progress, 0) AS "progress!",
+                   uq.completed_at IS NOT NULL AS "completed!"
+            FROM quests q
+            LEFT JOIN user_quests uq ON uq.quest_id = q.id AND uq.user_id = $1
+            WHERE q.starts_at <= now() AND q.ends_at > now()
+            ORDER BY q.ends_at, q.slot
+            "#,
+                                user_id
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        Ok(rows.into_iter()
+               .filter_map(|r| {
+                   Some(QuestInfo { id: r.id,
+                                    period: period_from_str(&r.period),
+                                    goal: goal_from_str(&r.goal, r.mv.as_deref())?,
+                                    target: r.target as u32,
+                                    progress: r.progress as u32,
+                                    completed: r.completed,
+                                    ends_at: r.ends_at })
+               })
+               .collect())
+    }
+
+    async fn advance(&self,
+                     user_id: Uuid,
+                     quest: &QuestInfo,
+                     amount: u32)
+                     -> Result<(u32, bool), GameError>
+    {
+        let row = sqlx::query!(
+                               r#"
+            INSERT INTO user_quests (user_id, quest_id, progress, completed_at)
+            VALUES ($1, $2, LEAST($3::int, $4::int), CASE WHEN $3 >= $4 THEN now() END)
+            ON CONFLICT (user_id, quest_id) DO UPDATE
+            SET progress = LEAST(user_quests.progress + $3, $4),
+                completed_at = COALESCE(user_quests.completed_at,
+                                        CASE WHEN user_quests.progress + $3 >= $4 THEN now() END)
+            RETURNING progress, completed_at IS NOT NULL AS "completed!"
+            "#,
+                               user_id,
+                               quest.id,
+                               amount as i32,
+                               quest.target as i32
+        ).fetch_one(&self.db)
+         .await
+         .map_err(|_e| GameError::DbError)?;
+
+        Ok((row.progress as u32, row.completed))
+    }
+}
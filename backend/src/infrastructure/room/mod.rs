@@ -0,0 +1,3 @@
+mod room_service;
+
+pub use room_service::*;
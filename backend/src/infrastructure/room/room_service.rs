@@ -0,0 +1,94 @@
+use async_trait::async_trait;
+use shared::{room::RoomError, rps_game::BestOf};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::room_model::*;
+use crate::infrastructure::auth::{hash_password, verify_password};
+
+pub struct PsqlRoomService
+{
+    pub db: PgPool,
+}
+
+fn best_of_from_i16(rounds: i16) -> BestOf
+{
+    BestOf::from_rounds(rounds as u8).unwrap_or_default()
+}
+
+#[async_trait]
+impl RoomService for PsqlRoomService
+{
+    async fn rooms(&self) -> Result<Vec<Room>, RoomError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT r.id, r.name, u.name AS "owner?", r.best_of,
+                   r.password_hash IS NOT NULL AS "private!"
+            FROM rooms r
+            LEFT JOIN users u ON u.id = r.owner
+            ORDER BY r.created_at
+            "#
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| RoomError::DbError)?;
+
+        Ok(rows.into_iter()
+               .map(|r| Room { id: r.id,
+                               name: r.name,
+                               owner: r.owner,
+                               best_of: best_of_from_i16(r.best_of),
+                               private: r.private })
+               .collect())
+    }
+
+    async fn create(&self,
+                    owner: Uuid,
+                    name: &str,
+                    password: Option<&str>,
+                    best_of: BestOf)
+                    -> Result<Room, RoomError>
+    {
+        let password_hash = match password.filter(|p| !p.is_empty()) {
+            Some(password) => Some(hash_password(password).map_err(|_e| RoomError::DbError)?),
+            None => None,
+        };
+
+        let row = sqlx::query!(
+                               r#"
+            INSERT INTO rooms (name, owner, password_hash, best_of)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (name) DO NOTHING
+            RETURNING id, (SELECT name FROM users WHERE id = $2) AS "owner?"
+            "#,
+                               name,
+                               owner,
+                               password_hash,
+                               best_of.rounds() as i16,
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| RoomError::DbError)?
+         .ok_or(RoomError::NameTaken)?;
+
+        Ok(Room { id: row.id,
+                  name: name.to_string(),
+                  owner: row.owner,
+                  best_of,
+                  private: password_hash.is_some() })
+    }
+
+    async fn check_password(&self, room_id: i64, password: &str) -> Result<(), RoomError>
+    {
+        let password_hash = sqlx::query_scalar!("SELECT password_hash FROM rooms WHERE id = $1",
+                                                room_id)
+                            .fetch_optional(&self.db)
+                            .await
+                            .map_err(|_e| RoomError::DbError)?
+                            .ok_or(RoomError::NotFound)?;
+
+        match password_hash {
+            None => Ok(()),
+            Some(hash) => verify_password(password, &hash).map_err(|_e| RoomError::WrongPassword),
+        }
+    }
+}
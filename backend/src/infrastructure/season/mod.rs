@@ -0,0 +1,5 @@
+mod season_route;
+mod season_service;
+
+pub use season_route::*;
+pub use season_service::*;
@@ -0,0 +1,44 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
+use shared::season::NewSeason;
+
+use crate::application::{auth_handler::AuthHandler, season_handler::SeasonHandler};
+use crate::infrastructure::auth::extract_id;
+
+pub fn configure_seasons(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/seasons")
+            .service(list_seasons)
+            .service(season_details)
+            .service(create_season),
+    );
+}
+
+/// Every season, newest first. Public like the leaderboard.
+#[get("")]
+async fn list_seasons(handler: web::Data<SeasonHandler>) -> impl Responder {
+    HttpResponse::Ok().json(handler.list().await)
+}
+
+/// Standings of a season, final once it is archived.
+#[get("/{id}")]
+async fn season_details(handler: web::Data<SeasonHandler>, id: web::Path<i64>) -> impl Responder {
+    HttpResponse::Ok().json(handler.details(id.into_inner()).await)
+}
+
+/// Admins only.
+#[post("")]
+async fn create_season(
+    handler: web::Data<SeasonHandler>,
+    auth_handler: web::Data<AuthHandler>,
+    req: HttpRequest,
+    new: web::Json<NewSeason>,
+) -> impl Responder {
+    let Some(user_id) = extract_id(&req) else {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    };
+
+    match auth_handler.get_user(user_id).await {
+        Ok(user) => HttpResponse::Ok().json(handler.create(&user, new.into_inner()).await),
+        Err(_) => HttpResponse::Unauthorized().body("Not logged in"),
+    }
+}
@@ -0,0 +1,186 @@
+use async_trait::async_trait;
+use shared::season::*;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::season_model::*;
+
+pub struct PsqlSeasonService
+{
+    pub db: PgPool,
+}
+
+#[async_trait]
+impl SeasonService for PsqlSeasonService
+{
+    async fn list(&self) -> Result<Vec<SeasonSummary>, SeasonError>
+    {
+        let rows = sqlx::query!(
+                                r#"
+            SELECT id, name, starts_at, ends_at, archived_at IS NOT NULL AS "archived!"
+            FROM seasons
+            ORDER BY starts_at DESC
+            "#
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| SeasonError::DbError)?;
+
+        Ok(rows.into_iter()
+               .map(|r| SeasonSummary { id: r.id,
+                                        name: r.name,
+                                        starts_at: r.starts_at,
+                                        ends_at: r.ends_at,
+                                        archived: r.archived })
+               .collect())
+    }
+
+    async fn details(&self, id: i64, limit: u32) -> Result<SeasonDetails, SeasonError>
+    {
+        let summary = sqlx::query!(
+                                   r#"
+            SELECT id, name, starts_at, ends_at, archived_at IS NOT NULL AS "archived!"
+            FROM seasons
+            WHERE id = $1
+            "#,
+                                   id
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| SeasonError::DbError)?
+         .map(|r| SeasonSummary { id: r.id,
+                                  name: r.name,
+                                  starts_at: r.starts_at,
+                                  ends_at: r.ends_at,
+                                  archived: r.archived })
+         .ok_or(SeasonError::NotFound)?;
+
+        // Archived seasons read their final standings, others rank the season results
+        // by the ratings as they are now.
+        let rows = sqlx::query!(
+                                r#"
+            SELECT u.name AS "name!", st.rank::bigint AS "rank!", st.rating AS "rating!",
+                   st.wins AS "wins!", st.losses AS "losses!", st.draws AS "draws!"
+            FROM season_standings st
+            JOIN users u ON u.id = st.user_id
+            WHERE st.season_id = $1 AND $2::bool
+            UNION ALL
+            SELECT u.name,
+                   ROW_NUMBER() OVER (
+                       ORDER BY COALESCE(r.rating, 1500) DESC, sr.win_counter DESC, u.name
+                   ),
+                   COALESCE(r.rating, 1500),
+                   sr.win_counter, sr.lose_counter, sr.draw_counter
+            FROM season_results sr
+            JOIN users u ON u.id = sr.user_id
+            LEFT JOIN rps_ratings r ON r.user_id = sr.user_id
+            WHERE sr.season_id = $1 AND NOT $2 AND u.role <> 'bot'
+            ORDER BY 2
+            LIMIT $3
+            "#,
+                                id,
+                                summary.archived,
+                                limit as i64,
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| SeasonError::DbError)?;
+
+        let standings = rows.into_iter()
+                            .map(|r| SeasonStanding { rank: r.rank as u32,
+                                                      username: r.name,
+                                                      rating: r.rating,
+                                                      wins: r.wins as u32,
+                                                      losses: r.losses as u32,
+                                                      draws: r.draws as u32 })
+                            .collect();
+
+        Ok(SeasonDetails { summary, standings })
+    }
+
+    async fn create(&self, user_id: Uuid, new: &NewSeason) -> Result<i64, SeasonError>
+    {
+        let id = sqlx::query_scalar!(
+                                     r#"
+            INSERT INTO seasons (name, starts_at, ends_at, created_by)
+            SELECT $1, $2, $3, $4
+            WHERE NOT EXISTS (
+                SELECT 1 FROM seasons WHERE starts_at < $3 AND ends_at > $2
+            )
+            RETURNING id
+            "#,
+                                     new.name,
+                                     new.starts_at,
+                                     new.ends_at,
+                                     user_id,
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| SeasonError::DbError)?;
+
+        id.ok_or(SeasonError::Overlaps)
+    }
+
+    async fn ended(&self) -> Result<Vec<i64>, SeasonError>
+    {
+        sqlx::query_scalar!(
+                            r#"
+            SELECT id FROM seasons
+            WHERE ends_at <= now() AND archived_at IS NULL
+            ORDER BY ends_at
+            "#
+        ).fetch_all(&self.db)
+         .await
+         .map_err(|_e| SeasonError::DbError)
+    }
+
+    async fn archive(&self, id: i64, reset: SoftReset) -> Result<(), SeasonError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| SeasonError::DbError)?;
+
+        let archived = sqlx::query_scalar!(
+                                           r#"
+            UPDATE seasons SET archived_at = now()
+            WHERE id = $1 AND archived_at IS NULL
+            RETURNING id
+            "#,
+                                           id
+        ).fetch_optional(&mut *tx)
+         .await
+         .map_err(|_e| SeasonError::DbError)?;
+        if archived.is_none() {
+            return Ok(());
+        }
+
+        sqlx::query!(
+                     r#"
+            INSERT INTO season_standings (season_id, user_id, rank, rating, wins, losses, draws)
+            SELECT sr.season_id, sr.user_id,
+                   ROW_NUMBER() OVER (
+                       ORDER BY COALESCE(r.rating, 1500) DESC, sr.win_counter DESC, u.name
+                   ),
+                   COALESCE(r.rating, 1500),
+                   sr.win_counter, sr.lose_counter, sr.draw_counter
+            FROM season_results sr
+            JOIN users u ON u.id = sr.user_id
+            LEFT JOIN rps_ratings r ON r.user_id = sr.user_id
+            WHERE sr.season_id = $1 AND u.role <> 'bot'
+            "#,
+                     id
+        ).execute(&mut *tx)
+         .await
+         .map_err(|_e| SeasonError::DbError)?;
+
+        sqlx::query!(
+                     r#"
+            UPDATE rps_ratings
+            SET rating = $1 + (rating - $1) * $2,
+                deviation = GREATEST(deviation, $3),
+                updated_at = now()
+            "#,
+                     reset.mean,
+                     reset.kept,
+                     reset.min_deviation,
+        ).execute(&mut *tx)
+         .await
+         .map_err(|_e| SeasonError::DbError)?;
+
+        tx.commit().await.map_err(|_e| SeasonError::DbError)
+    }
+}
@@ -0,0 +1,5 @@
+mod tournament_route;
+mod tournament_service;
+
+pub use tournament_route::*;
+pub use tournament_service::*;
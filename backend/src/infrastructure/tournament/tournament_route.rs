@@ -0,0 +1,51 @@
+use actix_web::{HttpRequest, HttpResponse, Responder, post, web};
+use shared::tournament::*;
+
+use crate::application::{auth_handler::AuthHandler, tournament_handler::TournamentHandler};
+use crate::domain::auth_model::AuthError;
+use crate::domain::rps_model::RpsMatch;
+use crate::infrastructure::auth::extract_id;
+
+#[post("/tournaments")]
+pub async fn tournament_control(
+    tournament_handler: web::Data<TournamentHandler<RpsMatch>>,
+    auth_handler: web::Data<AuthHandler>,
+    req: HttpRequest,
+    tournament_cmd: web::Json<TournamentCmd>,
+) -> impl Responder {
+    let Some(user_id) = extract_id(&req) else {
+        return HttpResponse::Unauthorized().body("Not logged in");
+    };
+
+    match auth_handler.get_user(user_id).await {
+        Ok(user) => match tournament_cmd.into_inner() {
+            TournamentCmd::List => HttpResponse::Ok().json(tournament_handler.list().await),
+
+            TournamentCmd::Fetch { id } => {
+                HttpResponse::Ok().json(tournament_handler.details(&user, id).await)
+            }
+
+            TournamentCmd::Create(new) => {
+                HttpResponse::Ok().json(tournament_handler.create(user_id, new).await)
+            }
+
+            TournamentCmd::Join { id } => {
+                HttpResponse::Ok().json(tournament_handler.join(id, user_id).await)
+            }
+
+            TournamentCmd::Leave { id } => {
+                HttpResponse::Ok().json(tournament_handler.leave(id, user_id).await)
+            }
+
+            TournamentCmd::Cancel { id } => {
+                HttpResponse::Ok().json(tournament_handler.cancel(&user, id).await)
+            }
+        },
+
+        Err(AuthError::InvalidCredentials) => {
+            HttpResponse::Unauthorized().body("Wrong username or password!")
+        }
+
+        Err(_) => HttpResponse::InternalServerError().body("Login failed."),
+    }
+}
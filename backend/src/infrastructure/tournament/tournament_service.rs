@@ -0,0 +1,632 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::{rps_game::BestOf, tournament::*};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::tournament_model::*;
+
+/// Most players a single tournament takes.
+const MAX_PLAYERS: i64 = 64;
+
+pub struct PsqlTournamentService
+{
+    pub db: PgPool,
+}
+
+fn format_to_str(format: TournamentFormat) -> &'static str
+{
+    match format {
+        TournamentFormat::SingleElimination => "single_elimination",
+        TournamentFormat::Swiss => "swiss",
+    }
+}
+
+fn format_from_str(s: &str) -> TournamentFormat
+{
+    match s {
+        "swiss" => TournamentFormat::Swiss,
+        _ => TournamentFormat::SingleElimination,
+    }
+}
+
+fn status_to_str(status: TournamentStatus) -> &'static str
+{
+    match status {
+        TournamentStatus::Open => "open",
+        TournamentStatus::Running => "running",
+        TournamentStatus::Finished => "finished",
+        TournamentStatus::Cancelled => "cancelled",
+    }
+}
+
+fn status_from_str(s: &str) -> TournamentStatus
+{
+    match s {
+        "running" => TournamentStatus::Running,
+        "finished" => TournamentStatus::Finished,
+        "cancelled" => TournamentStatus::Cancelled,
+        _ => TournamentStatus::Open,
+    }
+}
+
+fn match_status_from_str(s: &str) -> MatchStatus
+{
+    match s {
+        "playing" => MatchStatus::Playing,
+        "done" => MatchStatus::Done,
+        _ => MatchStatus::Pending,
+    }
+}
+
+fn best_of_from_i16(rounds: i16) -> BestOf
+{
+    BestOf::from_rounds(rounds as u8).unwrap_or_default()
+}
+
+struct TournamentRow
+{
+    id: i64,
+    name: String,
+    format: String,
+    best_of: i16,
+    status: String,
+    created_by: Option<Uuid>,
+    rounds: i32,
+    current_round: i32,
+}
+
+impl From<TournamentRow> for Tournament
+{
+    fn from(row: TournamentRow) -> Self
+    {
+        Tournament { id: row.id,
+                     name: row.name,
+                     format: format_from_str(&row.format),
+                     best_of: best_of_from_i16(row.best_of),
+                     status: status_from_str(&row.status),
+                     created_by: row.created_by,
+                     rounds: row.rounds as u32,
+                     current_round: row.current_round as u32 }
+    }
+}
+
+struct MatchRow
+{
+    id: i64,
+    tournament_id: i64,
+    round: i32,
+    slot: i32,
+    player1: Uuid,
+    player2: Option<Uuid>,
+    winner: Option<Uuid>,
+    status: String,
+    attempts: i32,
+    since: DateTime<Utc>,
+}
+
+impl From<MatchRow> for ScheduledMatch
+{
+    fn from(row: MatchRow) -> Self
+    {
+        ScheduledMatch { id: row.id,
+                         tournament_id: row.tournament_id,
+                         round: row.round as u32,
+                         slot: row.slot as u32,
+                         player1: row.player1,
+                         player2: row.player2,
+                         winner: row.winner,
+                         status: match_status_from_str(&row.status),
+                         attempts: row.attempts as u32,
+                         since: row.since }
+    }
+}
+
+struct SummaryRow
+{
+    id: i64,
+    name: String,
+    format: String,
+    best_of: i16,
+    status: String,
+    starts_at: DateTime<Utc>,
+    created_by: Option<String>,
+    players: i64,
+}
+
+impl From<SummaryRow> for TournamentSummary
+{
+    fn from(row: SummaryRow) -> Self
+    {
+        TournamentSummary { id: row.id,
+                            name: row.name,
+                            format: format_from_str(&row.format),
+                            best_of: best_of_from_i16(row.best_of),
+                            status: status_from_str(&row.status),
+                            starts_at: row.starts_at,
+                            created_by: row.created_by,
+                            players: row.players as u32 }
+    }
+}
+
+#[async_trait]
+impl TournamentService for PsqlTournamentService
+{
+    async fn create(&self, user_id: Uuid, new: &NewTournament) -> Result<i64, TournamentError>
+    {
+        sqlx::query_scalar!(
+            r#"
+            INSERT INTO tournaments (name, format, best_of, created_by, starts_at)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            new.name,
+            format_to_str(new.format),
+            new.best_of.rounds() as i16,
+            user_id,
+            new.starts_at
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)
+    }
+
+    async fn list(&self) -> Result<Vec<TournamentSummary>, TournamentError>
+    {
+        let rows = sqlx::query_as!(
+            SummaryRow,
+            r#"
+            SELECT t.id, t.name, t.format, t.best_of, t.status, t.starts_at,
+                   u.name AS "created_by?",
+                   (SELECT COUNT(*) FROM tournament_players p
+                    WHERE p.tournament_id = t.id) AS "players!"
+            FROM tournaments t
+            LEFT JOIN users u ON u.id = t.created_by
+            WHERE t.status <> 'cancelled'
+            ORDER BY t.status = 'finished', t.starts_at DESC
+            LIMIT 50
+            "#
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn details(&self, id: i64, user_id: Uuid) -> Result<TournamentDetails, TournamentError>
+    {
+        let summary = sqlx::query_as!(
+            SummaryRow,
+            r#"
+            SELECT t.id, t.name, t.format, t.best_of, t.status, t.starts_at,
+                   u.name AS "created_by?",
+                   (SELECT COUNT(*) FROM tournament_players p
+                    WHERE p.tournament_id = t.id) AS "players!"
+            FROM tournaments t
+            LEFT JOIN users u ON u.id = t.created_by
+            WHERE t.id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?
+        .ok_or(TournamentError::NotFound)?;
+
+        let tournament = self.tournament(id).await?;
+
+        let matches = sqlx::query!(
+            r#"
+            SELECT m.round, m.status,
+                   u1.name AS "player1!", u2.name AS "player2?", uw.name AS "winner?"
+            FROM tournament_matches m
+            JOIN users u1 ON u1.id = m.player1
+            LEFT JOIN users u2 ON u2.id = m.player2
+            LEFT JOIN users uw ON uw.id = m.winner
+            WHERE m.tournament_id = $1
+            ORDER BY m.round, m.slot
+            "#,
+            id
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?
+        .into_iter()
+        .map(|row| TournamentMatch { round: row.round as u32,
+                                     players: [Some(row.player1), row.player2],
+                                     winner: row.winner,
+                                     finished: row.status == "done" })
+        .collect();
+
+        let standings: Vec<Standing> = sqlx::query!(
+            r#"
+            SELECT u.name, p.wins, p.draws, p.losses, p.eliminated, p.user_id
+            FROM tournament_players p
+            JOIN users u ON u.id = p.user_id
+            WHERE p.tournament_id = $1
+            ORDER BY p.eliminated, 2 * p.wins + p.draws DESC, p.seed, p.joined_at
+            "#,
+            id
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?
+        .into_iter()
+        .map(|row| Standing { username: row.name,
+                              wins: row.wins as u32,
+                              draws: row.draws as u32,
+                              losses: row.losses as u32,
+                              eliminated: row.eliminated })
+        .collect();
+
+        let joined = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM tournament_players WHERE tournament_id = $1 AND user_id = $2
+            ) AS "exists!"
+            "#,
+            id,
+            user_id
+        )
+        .fetch_one(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(TournamentDetails { summary: summary.into(),
+                               rounds: tournament.rounds,
+                               current_round: tournament.current_round,
+                               matches,
+                               standings,
+                               joined,
+                               can_cancel: tournament.created_by == Some(user_id) })
+    }
+
+    async fn tournament(&self, id: i64) -> Result<Tournament, TournamentError>
+    {
+        let row = sqlx::query_as!(
+            TournamentRow,
+            r#"
+            SELECT id, name, format, best_of, status, created_by, rounds, current_round
+            FROM tournaments WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?
+        .ok_or(TournamentError::NotFound)?;
+
+        Ok(row.into())
+    }
+
+    async fn join(&self, id: i64, user_id: Uuid) -> Result<(), TournamentError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| TournamentError::DbError)?;
+
+        // lock the tournament so the player cap and the open status hold
+        let status = sqlx::query_scalar!("SELECT status FROM tournaments WHERE id = $1 FOR UPDATE",
+                                         id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|_e| TournamentError::DbError)?
+            .ok_or(TournamentError::NotFound)?;
+        if status_from_str(&status) != TournamentStatus::Open {
+            return Err(TournamentError::NotOpen);
+        }
+
+        let players = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM tournament_players WHERE tournament_id = $1"#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+        if players >= MAX_PLAYERS {
+            return Err(TournamentError::Full);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO tournament_players (tournament_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+            id,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        tx.commit().await.map_err(|_e| TournamentError::DbError)
+    }
+
+    async fn leave(&self, id: i64, user_id: Uuid) -> Result<(), TournamentError>
+    {
+        let res = sqlx::query!(
+            r#"
+            DELETE FROM tournament_players p
+            USING tournaments t
+            WHERE t.id = p.tournament_id AND t.status = 'open'
+              AND p.tournament_id = $1 AND p.user_id = $2
+            "#,
+            id,
+            user_id
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        if res.rows_affected() == 0 {
+            return Err(TournamentError::NotOpen);
+        }
+
+        Ok(())
+    }
+
+    async fn set_status(&self, id: i64, status: TournamentStatus) -> Result<(), TournamentError>
+    {
+        sqlx::query!("UPDATE tournaments SET status = $2 WHERE id = $1",
+                     id,
+                     status_to_str(status))
+            .execute(&self.db)
+            .await
+            .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(())
+    }
+
+    async fn due(&self) -> Result<Vec<Tournament>, TournamentError>
+    {
+        let rows = sqlx::query_as!(
+            TournamentRow,
+            r#"
+            SELECT id, name, format, best_of, status, created_by, rounds, current_round
+            FROM tournaments
+            WHERE status = 'open' AND starts_at <= now()
+            "#
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn running(&self) -> Result<Vec<Tournament>, TournamentError>
+    {
+        let rows = sqlx::query_as!(
+            TournamentRow,
+            r#"
+            SELECT id, name, format, best_of, status, created_by, rounds, current_round
+            FROM tournaments
+            WHERE status = 'running'
+            "#
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn entrants(&self, id: i64) -> Result<Vec<Entrant>, TournamentError>
+    {
+        let rows = sqlx::query!(
+            r#"
+            SELECT user_id, seed, wins, draws, losses, eliminated
+            FROM tournament_players
+            WHERE tournament_id = $1
+            ORDER BY seed, joined_at
+            "#,
+            id
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(rows.into_iter()
+               .map(|row| Entrant { user_id: row.user_id,
+                                    seed: row.seed as u32,
+                                    wins: row.wins as u32,
+                                    draws: row.draws as u32,
+                                    losses: row.losses as u32,
+                                    eliminated: row.eliminated })
+               .collect())
+    }
+
+    async fn start(&self, id: i64, seeds: &[Uuid], rounds: u32) -> Result<(), TournamentError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| TournamentError::DbError)?;
+
+        for (i, user_id) in seeds.iter().enumerate() {
+            sqlx::query!(
+                "UPDATE tournament_players SET seed = $3 WHERE tournament_id = $1 AND user_id = $2",
+                id,
+                user_id,
+                i as i32 + 1
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|_e| TournamentError::DbError)?;
+        }
+
+        sqlx::query!(
+            "UPDATE tournaments SET status = 'running', rounds = $2 WHERE id = $1",
+            id,
+            rounds as i32
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        tx.commit().await.map_err(|_e| TournamentError::DbError)
+    }
+
+    async fn add_round(&self,
+                       id: i64,
+                       round: u32,
+                       pairings: &[Pairing])
+                       -> Result<(), TournamentError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| TournamentError::DbError)?;
+
+        for (slot, (player1, player2)) in pairings.iter().enumerate() {
+            sqlx::query!(
+                r#"
+                INSERT INTO tournament_matches (tournament_id, round, slot, player1, player2)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+                id,
+                round as i32,
+                slot as i32,
+                player1,
+                *player2
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|_e| TournamentError::DbError)?;
+        }
+
+        sqlx::query!("UPDATE tournaments SET current_round = $2 WHERE id = $1",
+                     id,
+                     round as i32)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_e| TournamentError::DbError)?;
+
+        tx.commit().await.map_err(|_e| TournamentError::DbError)
+    }
+
+    async fn matches(&self, id: i64) -> Result<Vec<ScheduledMatch>, TournamentError>
+    {
+        let rows = sqlx::query_as!(
+            MatchRow,
+            r#"
+            SELECT id, tournament_id, round, slot, player1, player2, winner, status, attempts, since
+            FROM tournament_matches
+            WHERE tournament_id = $1
+            ORDER BY round, slot
+            "#,
+            id
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn playing_match(&self,
+                           players: [Uuid; 2])
+                           -> Result<Option<ScheduledMatch>, TournamentError>
+    {
+        let row = sqlx::query_as!(
+            MatchRow,
+            r#"
+            SELECT id, tournament_id, round, slot, player1, player2, winner, status, attempts, since
+            FROM tournament_matches
+            WHERE status = 'playing'
+              AND ((player1 = $1 AND player2 = $2) OR (player1 = $2 AND player2 = $1))
+            "#,
+            players[0],
+            players[1]
+        )
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn set_playing(&self, match_id: i64) -> Result<(), TournamentError>
+    {
+        sqlx::query!(
+            "UPDATE tournament_matches SET status = 'playing', since = now() WHERE id = $1",
+            match_id
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(())
+    }
+
+    async fn reschedule(&self, match_id: i64) -> Result<(), TournamentError>
+    {
+        sqlx::query!(
+            r#"
+            UPDATE tournament_matches
+            SET status = 'pending', attempts = attempts + 1, since = now()
+            WHERE id = $1
+            "#,
+            match_id
+        )
+        .execute(&self.db)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        Ok(())
+    }
+
+    async fn record_result(&self,
+                           match_id: i64,
+                           winner: Option<Uuid>)
+                           -> Result<(), TournamentError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| TournamentError::DbError)?;
+
+        let row = sqlx::query!(
+            r#"
+            UPDATE tournament_matches SET status = 'done', winner = $2
+            WHERE id = $1 AND status <> 'done'
+            RETURNING tournament_id, player1, player2
+            "#,
+            match_id,
+            winner
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|_e| TournamentError::DbError)?;
+
+        // already recorded
+        let Some(row) = row else {
+            return Ok(());
+        };
+
+        let players: HashSet<Uuid> = [Some(row.player1), row.player2].into_iter()
+                                                                      .flatten()
+                                                                      .collect();
+        for user_id in players {
+            let (win, draw, loss) = match winner {
+                None => (0, 1, 0),
+                Some(w) if w == user_id => (1, 0, 0),
+                Some(_) => (0, 0, 1),
+            };
+
+            // losing knocks you out of an elimination bracket
+            sqlx::query!(
+                r#"
+                UPDATE tournament_players p
+                SET wins = wins + $3, draws = draws + $4, losses = losses + $5,
+                    eliminated = eliminated OR ($5 > 0 AND t.format = 'single_elimination')
+                FROM tournaments t
+                WHERE t.id = p.tournament_id AND p.tournament_id = $1 AND p.user_id = $2
+                "#,
+                row.tournament_id,
+                user_id,
+                win,
+                draw,
+                loss
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|_e| TournamentError::DbError)?;
+        }
+
+        tx.commit().await.map_err(|_e| TournamentError::DbError)
+    }
+}
@@ -0,0 +1,114 @@
+use shared::{game::GameResult, wallet::WalletError};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+use crate::domain::wallet_model::LedgerReason;
+
+fn reason_to_str(reason: LedgerReason) -> &'static str
+{
+    match reason {
+        LedgerReason::Allowance => "allowance",
+        LedgerReason::Stake => "stake",
+        LedgerReason::Payout => "payout",
+        LedgerReason::Refund => "refund",
+    }
+}
+
+/// Add `amount` to a balance, negative to take coins away, and write it to the ledger.
+/// Answers with the new balance.
+pub(crate) async fn post_entry(conn: &mut PgConnection,
+                               user_id: Uuid,
+                               amount: i64,
+                               reason: LedgerReason,
+                               wager_id: Option<i64>)
+                               -> Result<i64, WalletError>
+{
+    sqlx::query_scalar!(
+                        r#"
+            WITH account AS (
+                INSERT INTO coin_accounts (user_id, balance)
+                VALUES ($1, $2)
+                ON CONFLICT (user_id)
+                DO UPDATE SET balance = coin_accounts.balance + EXCLUDED.balance
+                RETURNING balance
+            )
+            INSERT INTO coin_ledger (user_id, amount, balance, reason, wager_id)
+            SELECT $1, $2, balance, $3, $4 FROM account
+            RETURNING balance
+            "#,
+                        user_id,
+                        amount,
+                        reason_to_str(reason),
+                        wager_id,
+    ).fetch_one(conn)
+     .await
+     .map_err(|_e| WalletError::DbError)
+}
+
+/// Give both players their stake back and close the wager.
+pub(crate) async fn refund(conn: &mut PgConnection,
+                           wager_id: i64,
+                           players: [Uuid; 2],
+                           stake: i32)
+                           -> Result<(), WalletError>
+{
+    sqlx::query!(
+                 r#"
+            UPDATE coin_wagers SET status = 'refunded', settled_at = now()
+            WHERE id = $1
+            "#,
+                 wager_id
+    ).execute(&mut *conn)
+     .await
+     .map_err(|_e| WalletError::DbError)?;
+
+    for user_id in players {
+        post_entry(conn, user_id, stake as i64, LedgerReason::Refund, Some(wager_id)).await?;
+    }
+    Ok(())
+}
+
+/// Pay out the open wager between the players of a recorded game, if there is one.
+/// `result` is from the first player's side; a draw gives both their stake back.
+pub(crate) async fn settle_wager(conn: &mut PgConnection,
+                                 players_id: [Uuid; 2],
+                                 result: GameResult)
+                                 -> Result<(), WalletError>
+{
+    let winner = match result {
+        GameResult::Win => Some(players_id[0]),
+        GameResult::Defeat => Some(players_id[1]),
+        GameResult::Draw => None,
+    };
+
+    let Some(wager) = sqlx::query!(
+                                   r#"
+            UPDATE coin_wagers SET status = 'settled', winner = $3, settled_at = now()
+            WHERE status = 'open'
+              AND LEAST(player1, player2) = LEAST($1::uuid, $2::uuid)
+              AND GREATEST(player1, player2) = GREATEST($1::uuid, $2::uuid)
+            RETURNING id, stake
+            "#,
+                                   players_id[0],
+                                   players_id[1],
+                                   winner,
+    ).fetch_optional(&mut *conn)
+     .await
+     .map_err(|_e| WalletError::DbError)?
+    else {
+        return Ok(());
+    };
+
+    let stake = wager.stake as i64;
+    match winner {
+        Some(winner) => {
+            post_entry(conn, winner, 2 * stake, LedgerReason::Payout, Some(wager.id)).await?;
+        }
+        None => {
+            for user_id in players_id {
+                post_entry(conn, user_id, stake, LedgerReason::Refund, Some(wager.id)).await?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,5 @@
+mod ledger;
+mod wallet_service;
+
+pub(crate) use ledger::*;
+pub use wallet_service::*;
@@ -0,0 +1,146 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use shared::wallet::WalletError;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::wallet_model::*;
+use crate::infrastructure::wallet::{post_entry, refund};
+
+pub struct PsqlWalletService
+{
+    pub db: PgPool,
+}
+
+#[async_trait]
+impl WalletService for PsqlWalletService
+{
+    async fn balance(&self, user_id: Uuid) -> Result<i64, WalletError>
+    {
+        let balance = sqlx::query_scalar!(
+                                          r#"
+            SELECT balance FROM coin_accounts WHERE user_id = $1
+            "#,
+                                          user_id
+        ).fetch_optional(&self.db)
+         .await
+         .map_err(|_e| WalletError::DbError)?;
+
+        Ok(balance.unwrap_or(0))
+    }
+
+    async fn grant_allowance(&self, user_id: Uuid, amount: i64) -> Result<(), WalletError>
+    {
+        // The unique allowance day makes a concurrent second grant fail as a whole.
+        sqlx::query!(
+                     r#"
+            WITH account AS (
+                INSERT INTO coin_accounts (user_id, balance)
+                SELECT $1, $2
+                WHERE NOT EXISTS (
+                    SELECT 1 FROM coin_ledger
+                    WHERE user_id = $1 AND allowance_day = (now() AT TIME ZONE 'UTC')::date
+                )
+                ON CONFLICT (user_id)
+                DO UPDATE SET balance = coin_accounts.balance + EXCLUDED.balance
+                RETURNING balance
+            )
+            INSERT INTO coin_ledger (user_id, amount, balance, reason, allowance_day)
+            SELECT $1, $2, balance, 'allowance', (now() AT TIME ZONE 'UTC')::date FROM account
+            "#,
+                     user_id,
+                     amount,
+        ).execute(&self.db)
+         .await
+         .map_err(|_e| WalletError::DbError)?;
+
+        Ok(())
+    }
+
+    async fn open_wager(&self, players: [Uuid; 2], stake: u32) -> Result<(), WalletError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| WalletError::DbError)?;
+
+        let balances = sqlx::query_scalar!(
+                                           r#"
+            SELECT balance FROM coin_accounts
+            WHERE user_id = ANY($1)
+            ORDER BY user_id
+            FOR UPDATE
+            "#,
+                                           &players[..]
+        ).fetch_all(&mut *tx)
+         .await
+         .map_err(|_e| WalletError::DbError)?;
+        if balances.len() < 2 || balances.iter().any(|b| *b < stake as i64) {
+            return Err(WalletError::InsufficientFunds);
+        }
+
+        let wager_id = sqlx::query_scalar!(
+                                           r#"
+            INSERT INTO coin_wagers (player1, player2, stake)
+            VALUES ($1, $2, $3)
+            RETURNING id
+            "#,
+                                           players[0],
+                                           players[1],
+                                           stake as i32,
+        ).fetch_one(&mut *tx)
+         .await
+         .map_err(|_e| WalletError::DbError)?;
+
+        for user_id in players {
+            post_entry(&mut tx, user_id, -(stake as i64), LedgerReason::Stake, Some(wager_id))
+                .await?;
+        }
+
+        tx.commit().await.map_err(|_e| WalletError::DbError)
+    }
+
+    async fn refund_wager(&self, players: [Uuid; 2]) -> Result<(), WalletError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| WalletError::DbError)?;
+
+        let wager = sqlx::query!(
+                                 r#"
+            SELECT id, stake FROM coin_wagers
+            WHERE status = 'open'
+              AND LEAST(player1, player2) = LEAST($1::uuid, $2::uuid)
+              AND GREATEST(player1, player2) = GREATEST($1::uuid, $2::uuid)
+            FOR UPDATE
+            "#,
+                                 players[0],
+                                 players[1],
+        ).fetch_optional(&mut *tx)
+         .await
+         .map_err(|_e| WalletError::DbError)?;
+
+        if let Some(wager) = wager {
+            refund(&mut tx, wager.id, players, wager.stake).await?;
+        }
+
+        tx.commit().await.map_err(|_e| WalletError::DbError)
+    }
+
+    async fn refund_stale(&self, age: Duration) -> Result<(), WalletError>
+    {
+        let mut tx = self.db.begin().await.map_err(|_e| WalletError::DbError)?;
+
+        let wagers = sqlx::query!(
+                                  r#"
+            SELECT id, player1, player2, stake FROM coin_wagers
+            WHERE status = 'open' AND created_at < $1
+            FOR UPDATE
+            "#,
+                                  Utc::now() - age
+        ).fetch_all(&mut *tx)
+         .await
+         .map_err(|_e| WalletError::DbError)?;
+
+        for wager in wagers {
+            refund(&mut tx, wager.id, [wager.player1, wager.player2], wager.stake).await?;
+        }
+
+        tx.commit().await.map_err(|_e| WalletError::DbError)
+    }
+}
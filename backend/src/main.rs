@@ -1,9 +1,15 @@
 use actix::Actor;
 use actix_web::{web, App, HttpServer};
 use dotenvy::dotenv;
+use serde::{de::DeserializeOwned, Serialize};
+use shared::{game::GameKind, royale::RoyaleKind, rps_game::BestOf};
 use sqlx::PgPool;
+use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
 
 pub mod application;
 pub mod domain;
@@ -11,11 +17,38 @@ pub mod infrastructure;
 pub mod ws;
 
 use crate::application::game_handler::GameHandler;
-use crate::application::{auth_handler::*, forum_handler::*};
-use crate::domain::rps_model::RpsGame;
+use crate::application::game_registry::GameRegistry;
+use crate::application::{
+    achievement_handler::AchievementHandler, auth_handler::*, collusion_handler::CollusionHandler,
+    fair_play_handler::FairPlayHandler, forum_handler::*, history_handler::HistoryHandler,
+    leaderboard_handler::LeaderboardHandler, profile_handler::ProfileHandler,
+    quest_handler::QuestHandler, room_handler::RoomHandler, royale_handler::RoyaleHandler,
+    season_handler::SeasonHandler, tournament_handler::TournamentHandler,
+    wallet_handler::WalletHandler,
+};
+use crate::domain::fair_rps_model::FairRpsGame;
+use crate::domain::game_model::{ActiveGame, Bot, GameService, PlayerQueue};
+use crate::domain::rooms_actor::RoomsActor;
+use crate::domain::rps_bots::*;
+use crate::domain::rps_model::RpsMatch;
+use crate::domain::tic_tac_toe_model::TicTacToe;
 use crate::domain::users_actor::UsersActor;
-use crate::infrastructure::{auth::*, forum::*, game::*};
-use crate::ws::ws_route;
+use crate::infrastructure::{
+    achievement::*, auth::*, collusion::*, forum::*, game::*, leaderboard::*, profile::*, quest::*,
+    room::*, season::*, tournament::*, wallet::*,
+};
+use crate::ws::{ws_route, WsHandlers};
+
+/// Active games live in memory unless `GAME_STORE=postgres`, which keeps them in the
+/// database, under the game's kind id, so they survive a restart.
+fn game_service<G>(pool: &PgPool, move_time: chrono::Duration) -> Arc<dyn GameService<G>>
+    where G: ActiveGame + Serialize + DeserializeOwned + 'static
+{
+    match env::var("GAME_STORE").as_deref() {
+        Ok("postgres") => PsqlGameService::<G>::new(pool.clone(), G::Kind::KIND, move_time),
+        _ => InMemoryGameService::<G>::new(move_time),
+    }
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()>
@@ -33,28 +66,186 @@ async fn main() -> std::io::Result<()>
     let forum_service = Arc::new(PsqlForumService { db: pool.clone() });
     let forum_handler = web::Data::new(ForumHandler { forum_service });
 
+    let leaderboard_service = Arc::new(PsqlLeaderboardService { db: pool.clone() });
+    let leaderboard_handler = web::Data::new(LeaderboardHandler { leaderboard_service });
+
+    let season_service = Arc::new(PsqlSeasonService { db: pool.clone() });
+    let season_handler = web::Data::new(SeasonHandler { season_service });
+    season_handler.run();
+
+    let collusion_service = Arc::new(PsqlCollusionService { db: pool.clone() });
+    let collusion_handler = web::Data::new(CollusionHandler { collusion_service });
+    collusion_handler.run();
+
     let users_actor = UsersActor::new().start();
     let sh_users_actor = web::Data::new(users_actor.clone());
 
-    let players_actor = PlayersQueueActor::new().start();
-    let rps_player_qu = Arc::new(ActorPlayerQueue::new(players_actor));
-    let rps_service = InMemoryGameService::<RpsGame>::new();
+    let mut rps_matches = Vec::new();
+    let rps_player_qus: HashMap<_, Arc<dyn PlayerQueue>> =
+        BestOf::ALL.into_iter()
+                   .map(|best_of| {
+                       let (matches_tx, matches_rx) = mpsc::unbounded_channel();
+                       rps_matches.push((best_of, matches_rx));
+
+                       let players_actor = PlayersQueueActor::new(matches_tx).start();
+                       let queue: Arc<dyn PlayerQueue> =
+                           Arc::new(ActorPlayerQueue::new(players_actor));
+                       (best_of, queue)
+                   })
+                   .collect();
+    let move_time = env::var("MOVE_TIME_SECS").ok()
+                                              .and_then(|s| s.parse().ok())
+                                              .unwrap_or(30);
+    let move_time = chrono::Duration::seconds(move_time);
+    let rps_service = game_service::<RpsMatch>(&pool, move_time);
     let notifier = Arc::new(WsGameNotifier::new(users_actor));
     let game_recorder = Arc::new(PsqlGameRecorder { db: pool.clone() });
 
-    let rps_game_handler = web::Data::new(GameHandler::<RpsGame>::new(rps_service,
-                                                                      rps_player_qu,
-                                                                      notifier,
-                                                                      game_recorder));
+    let rps_bots: Vec<Arc<dyn Bot<RpsMatch>>> =
+        vec![Arc::new(RpsBot::new(Uuid::from_u128(0xb0_01), "RandomBot", UniformRandom)),
+             Arc::new(RpsBot::new(Uuid::from_u128(0xb0_02), "CounterBot", FrequencyCounter)),
+             Arc::new(RpsBot::new(Uuid::from_u128(0xb0_03), "MarkovBot", MarkovPredictor)),
+             Arc::new(RpsBot::new(Uuid::from_u128(0xb0_04), "MirrorBot", BeatLast)),];
+    let rps_bots = match register_bot_accounts(&pool, rps_bots).await {
+        Ok(bots) => bots,
+        Err(e) => {
+            eprintln!("Failed to register bot accounts, bots are disabled: {e}");
+            Vec::new()
+        }
+    };
+    let bot_wait = env::var("BOT_WAIT_SECS").ok()
+                                            .and_then(|s| s.parse().ok())
+                                            .unwrap_or(15);
+    let reconnect_grace = env::var("RECONNECT_GRACE_SECS").ok()
+                                                          .and_then(|s| s.parse().ok())
+                                                          .map(Duration::from_secs)
+                                                          .unwrap_or(Duration::from_secs(30));
+
+    let achievement_service = Arc::new(PsqlAchievementService { db: pool.clone() });
+    let achievement_handler =
+        web::Data::new(AchievementHandler::new(achievement_service, notifier.clone()));
+
+    let quest_service = Arc::new(PsqlQuestService { db: pool.clone() });
+    let quest_handler = web::Data::new(QuestHandler::new(quest_service, notifier.clone()));
+    quest_handler.run();
+
+    let wallet_service = Arc::new(PsqlWalletService { db: pool.clone() });
+    let wallet_handler = web::Data::new(WalletHandler::new(wallet_service.clone(),
+                                                           notifier.clone()));
+    wallet_handler.run();
+
+    let (results_tx, results_rx) = mpsc::unbounded_channel();
+    let (room_results_tx, room_results_rx) = mpsc::unbounded_channel();
+    let (achievement_results_tx, achievement_results_rx) = mpsc::unbounded_channel();
+    let (wallet_results_tx, wallet_results_rx) = mpsc::unbounded_channel();
+    let rps_recorder = quest_handler.rps_recorder(game_recorder.clone());
+    let rps_game_handler = web::Data::new(GameHandler::<RpsMatch>::new(rps_service,
+                                                                       rps_player_qus,
+                                                                       notifier.clone(),
+                                                                       rps_recorder)
+                                          .with_ratings(game_recorder.clone())
+                                          .with_reconnect_grace(reconnect_grace)
+                                          .with_results(results_tx)
+                                          .with_results(room_results_tx)
+                                          .with_results(achievement_results_tx)
+                                          .with_results(wallet_results_tx)
+                                          .with_wallet(wallet_service)
+                                          .with_bots(rps_bots, Duration::from_secs(bot_wait)));
+    rps_game_handler.watch_deadlines();
+    for (best_of, matches_rx) in rps_matches {
+        rps_game_handler.listen_matches(best_of, matches_rx);
+    }
+    achievement_handler.follow(achievement_results_rx);
+    wallet_handler.follow(wallet_results_rx);
+
+    let tournament_service = Arc::new(PsqlTournamentService { db: pool.clone() });
+    let tournament_handler =
+        web::Data::new(TournamentHandler::new(tournament_service, rps_game_handler.get_ref().clone()));
+    tournament_handler.run(results_rx);
+
+    let room_service = Arc::new(PsqlRoomService { db: pool.clone() });
+    let room_handler = Arc::new(RoomHandler::new(room_service,
+                                                 RoomsActor::new().start(),
+                                                 rps_game_handler.get_ref().clone()));
+    room_handler.run(room_results_rx).await;
+
+    let (fair_matches_tx, fair_matches_rx) = mpsc::unbounded_channel();
+    let fair_queue: Arc<dyn PlayerQueue> =
+        Arc::new(ActorPlayerQueue::new(PlayersQueueActor::new(fair_matches_tx).start()));
+    let fair_service = game_service::<FairRpsGame>(&pool, move_time);
+    let (fair_results_tx, fair_results_rx) = mpsc::unbounded_channel();
+
+    let fair_game_handler = GameHandler::new(fair_service,
+                                             HashMap::from([((), fair_queue)]),
+                                             notifier.clone(),
+                                             quest_handler.rps_recorder(game_recorder.clone()))
+                            .with_ratings(game_recorder.clone())
+                            .with_reconnect_grace(reconnect_grace)
+                            .with_results(fair_results_tx);
+    fair_game_handler.watch_deadlines();
+    fair_game_handler.listen_matches((), fair_matches_rx);
+    achievement_handler.follow(fair_results_rx);
+
+    let (ttt_matches_tx, ttt_matches_rx) = mpsc::unbounded_channel();
+    let ttt_queue: Arc<dyn PlayerQueue> =
+        Arc::new(ActorPlayerQueue::new(PlayersQueueActor::new(ttt_matches_tx).start()));
+    let ttt_service = game_service::<TicTacToe>(&pool, move_time);
+
+    let ttt_game_handler = GameHandler::new(ttt_service,
+                                            HashMap::from([((), ttt_queue)]),
+                                            notifier.clone(),
+                                            quest_handler.recorder(game_recorder.clone()))
+                           .with_reconnect_grace(reconnect_grace);
+    ttt_game_handler.watch_deadlines();
+    ttt_game_handler.listen_matches((), ttt_matches_rx);
+
+    let royale_handler = Arc::new(RoyaleHandler::new(notifier.clone(),
+                                                     game_recorder.clone(),
+                                                     move_time));
+    royale_handler.run();
+
+    // Games played through `GameMsg`; registering one here is all the websocket needs.
+    let games = GameRegistry::new().register_with_rooms(rps_game_handler.get_ref().clone(),
+                                                        room_handler)
+                                   .register(fair_game_handler)
+                                   .register(ttt_game_handler)
+                                   .register_endpoint::<RoyaleKind>(royale_handler);
+
+    let ws_handlers = web::Data::new(WsHandlers { games,
+                                                  quests: quest_handler.clone(),
+                                                  wallet: wallet_handler.clone() });
+
+    let fair_play_handler = web::Data::new(FairPlayHandler { archive: game_recorder.clone() });
+    let history_handler = web::Data::new(HistoryHandler { archive: game_recorder.clone() });
+    let profile_service = Arc::new(PsqlProfileService { db: pool.clone() });
+    let profile_handler = web::Data::new(ProfileHandler { profile_service,
+                                                          archive: game_recorder.clone() });
 
     HttpServer::new(move || {
         App::new().app_data(auth_handler.clone())
                   .app_data(forum_handler.clone())
+                  .app_data(achievement_handler.clone())
+                  .app_data(quest_handler.clone())
                   .app_data(sh_users_actor.clone())
-                  .app_data(rps_game_handler.clone())
+                  .app_data(ws_handlers.clone())
+                  .app_data(fair_play_handler.clone())
+                  .app_data(history_handler.clone())
+                  .app_data(profile_handler.clone())
+                  .app_data(tournament_handler.clone())
+                  .app_data(leaderboard_handler.clone())
+                  .app_data(season_handler.clone())
+                  .app_data(collusion_handler.clone())
+                  .app_data(wallet_handler.clone())
                   .service(web::scope("/api").configure(configure_auth)
+                                             .configure(configure_games)
+                                             .configure(configure_seasons)
                                              .service(ws_route)
-                                             .service(forum_control))
+                                             .service(forum_control)
+                                             .service(tournament_control)
+                                             .service(leaderboard)
+                                             .service(collusion_control)
+                                             .service(move_analytics)
+                                             .service(user_profile))
     }).disable_signals()
       .bind("127.0.0.1:8081")?
       .run()
@@ -2,21 +2,35 @@ use actix::prelude::*;
 use actix_web::{get, rt, web, HttpRequest, HttpResponse, Responder};
 use actix_ws::AggregatedMessage;
 use futures_util::StreamExt;
-use shared::{rps_game::RpsGameReq, ws_messages::*};
+use shared::{
+    quest::{QuestEvent, QuestReq},
+    wallet::{WalletEvent, WalletReq},
+    ws_messages::*,
+};
 use tokio::sync::mpsc;
 use tokio::time::{interval, Duration, Instant, MissedTickBehavior};
 use uuid::Uuid;
 
 use crate::application::auth_handler::AuthHandler;
-use crate::application::game_handler::GameHandler;
-use crate::domain::rps_model::RpsGame;
+use crate::application::game_registry::GameRegistry;
+use crate::application::quest_handler::QuestHandler;
+use crate::application::wallet_handler::WalletHandler;
 use crate::domain::users_actor::{self, UsersActor};
 use crate::infrastructure::auth::extract_id;
 
+/// Everything a websocket connection talks to; games are reached through the registry.
+#[derive(Clone)]
+pub struct WsHandlers
+{
+    pub games: GameRegistry,
+    pub quests: web::Data<QuestHandler>,
+    pub wallet: web::Data<WalletHandler>,
+}
+
 #[get("/ws")]
 pub async fn ws_route(req: HttpRequest,
                       body: web::Payload,
-                      rps_handler: web::Data<GameHandler<RpsGame>>,
+                      handlers: web::Data<WsHandlers>,
                       users_actor: web::Data<Addr<UsersActor>>,
                       auth_handler: web::Data<AuthHandler>)
                       -> actix_web::Result<impl Responder>
@@ -36,6 +50,7 @@ pub async fn ws_route(req: HttpRequest,
     };
 
     let username = userinfo.username;
+    let name = username.clone();
 
     let (response, mut session, stream) = actix_ws::handle(&req, body)?;
 
@@ -51,7 +66,8 @@ pub async fn ws_route(req: HttpRequest,
                              .await
                              .unwrap();
 
-    let gh = rps_handler.clone();
+    let handlers = handlers.get_ref().clone();
+    handlers.games.reconnected(user_id).await;
 
     rt::spawn(async move {
         let mut hb = interval(Duration::from_secs(10));
@@ -89,7 +105,7 @@ pub async fn ws_route(req: HttpRequest,
                                         text.to_string(),
                                         user_id,
                                         &users_actor,
-                                        &gh,
+                                        &handlers,
                                         &mut session)
                                         .await {break;}
                                 },
@@ -111,20 +127,29 @@ pub async fn ws_route(req: HttpRequest,
             }
         }
 
-        users_actor.do_send(users_actor::Disconnected { conn_id, user_id });
-        // Drop from queue / game if still present.
+        let _ = users_actor.send(users_actor::Disconnected { conn_id, user_id })
+                           .await;
+        // Drop from queues right away, games are held until the grace period ends.
+        let online = users_actor.send(users_actor::IsOnline { user_id }).await;
+        if !online.unwrap_or(false) {
+            handlers.games.disconnected(user_id, &name).await;
+        }
     });
 
-    return Ok(response);
+    Ok(response)
 }
 
 async fn handle_client_text(text: String,
                             user_id: Uuid,
                             users_actor: &Addr<UsersActor>,
-                            rps_handler: &GameHandler<RpsGame>,
+                            handlers: &WsHandlers,
                             session: &mut actix_ws::Session)
                             -> bool
 {
+    let WsHandlers { games,
+                     quests: quest_handler,
+                     wallet: wallet_handler } = handlers;
+
     let parsed = match serde_json::from_str::<ClientMsg>(&text) {
         Ok(m) => m,
         Err(_) => {
@@ -142,37 +167,34 @@ async fn handle_client_text(text: String,
             let out = serde_json::to_string(&serv_msg).unwrap();
             session.text(out).await.is_ok()
         }
-        ClientMsg::RpsGameMsg(game_req) => match game_req {
-            RpsGameReq::Start => {
-                if let Err(err) = rps_handler.join(user_id).await {
-                    let msg = ServerMsg::GameErrorMsg(err);
-                    let out = serde_json::to_string(&msg).unwrap();
-                    session.text(out).await.is_ok()
-                } else {
-                    true
-                }
+        ClientMsg::GameMsg(envelope) => {
+            if let Err(err) = games.handle(user_id, &envelope).await {
+                let msg = ServerMsg::GameErrorMsg(err);
+                let out = serde_json::to_string(&msg).unwrap();
+                session.text(out).await.is_ok()
+            } else {
+                true
             }
-
-            RpsGameReq::Submit(mv) => {
-                if let Err(err) = rps_handler.submit(user_id, mv).await {
-                    let msg = ServerMsg::GameErrorMsg(err);
-                    let out = serde_json::to_string(&msg).unwrap();
-                    session.text(out).await.is_ok()
-                } else {
-                    true
-                }
+        }
+        ClientMsg::QuestMsg(QuestReq::List) => match quest_handler.quests(user_id).await {
+            Ok(quests) => {
+                let msg = ServerMsg::QuestMsg(QuestEvent::List(quests));
+                let out = serde_json::to_string(&msg).unwrap();
+                session.text(out).await.is_ok()
             }
-
-            RpsGameReq::Leave => {
-                if let Err(err) = rps_handler.leave(user_id).await {
-                    let msg = ServerMsg::GameErrorMsg(err);
-                    let out = serde_json::to_string(&msg).unwrap();
-                    session.text(out).await.is_ok()
-                } else {
-                    true
-                }
+            Err(err) => {
+                let msg = ServerMsg::GameErrorMsg(err);
+                let out = serde_json::to_string(&msg).unwrap();
+                session.text(out).await.is_ok()
             }
         },
+        ClientMsg::WalletMsg(WalletReq::Balance) => {
+            let result = wallet_handler.balance(user_id).await.map(WalletEvent::Balance);
+
+            let msg = ServerMsg::WalletMsg(result.unwrap_or_else(WalletEvent::Error));
+            let out = serde_json::to_string(&msg).unwrap();
+            session.text(out).await.is_ok()
+        }
         _ => true,
     }
 }
@@ -30,8 +30,21 @@ auth-home-title = Dashboard
 auth-home-welcome = Welcome, { $username }!
 auth-home-online = Users online: { $count }
 auth-home-created-at = Account created at: { $date }
+auth-home-rating = Rating: { $rating } ± { $deviation }
 auth-home-play = Play
 auth-home-logout = Logout
+quests-title = Quests
+quests-daily = Today
+quests-weekly = This week
+quests-empty = No quests right now.
+quest-progress = { $progress } / { $target }
+quest-completed-toast = Quest completed: { $quest }
+quest-play-games = Play games: { $count }
+quest-win-games = Win games: { $count }
+quest-win-with-rock = Win rounds with rock: { $count }
+quest-win-with-paper = Win rounds with paper: { $count }
+quest-win-with-scissors = Win rounds with scissors: { $count }
+quest-post-in-forum = Post in the forum: { $count }
 unauth-home-title = Welcome!
 unauth-home-subtitle = Please log in or register
 unauth-home-login = Login
@@ -39,6 +52,16 @@ unauth-home-register = Register
 games-hub-title = Games
 games-hub-subtitle = Choose a game to play:
 games-hub-rps = Rock-Paper-Scissors
+games-hub-fair-rps = Rock-Paper-Scissors (fair play)
+games-hub-tic-tac-toe = Tic-tac-toe
+games-hub-royale = Battle royale RPS
+games-hub-rooms = Game rooms
+games-hub-spectate = Watch live games
+games-hub-tournaments = Tournaments
+games-hub-leaderboard = Leaderboard
+games-hub-seasons = Seasons
+games-hub-collusion = Collusion review
+games-hub-history = Game history
 games-hub-home = { -home-label }
 rps-waiting = Waiting for opponent...
 rps-playing-against-label = Playing against:
@@ -50,10 +73,233 @@ rps-result-draw = Draw
 rps-finished = Game finished! { $result }
 rps-opponent-played-label = played:
 rps-next-game = Next game!
-rps-leave = Leave
+rps-time-left = Time to move: { $seconds } s
+rps-timed-out = Nobody moved in time, the game was abandoned
+rps-you-forfeited = You ran out of time and forfeited
+rps-opponent-forfeited = { $username } ran out of time and forfeited
 rps-other-games = Other games
 rps-home = { -home-label }
 rps-opponent-disconnected = Opponent disconnected!
+rps-opponent-away = Opponent disconnected, they have { $seconds } s to come back
+rps-opponent-back = Opponent is back!
+rps-best-of = Bo{ $rounds }
+rps-stake-label = Stake:
+rps-stake-none = None
+rps-stake-coins = { $coins } coins
+rps-stake-held = { $coins } coins are staked on this game
+rps-stake-insufficient = You don't have enough coins for that stake
+rps-score = Round { $round } · { $score } (best of { $rounds })
+rps-last-round = Last round:
+rps-final-score = Final score: { $score }
+rps-rematch = Rematch
+rps-rematch-accept = Accept rematch
+rps-rematch-waiting = Waiting for opponent...
+rps-rematch-decline = Decline
+rps-rematch-offered = Opponent wants a rematch!
+rps-rematch-declined = Rematch declined.
+fair-rps-title = Fair play RPS
+fair-rps-explainer = Your move is sent as a hash first and revealed only after both players committed, so nobody can peek or change it.
+fair-rps-opponent-committed = (committed)
+fair-rps-committed = Move committed, waiting for the opponent...
+fair-rps-revealing = Revealing moves...
+fair-rps-opponent-revealed = (revealed)
+fair-rps-your-commitment = Your commitment:
+fair-rps-commitment = Opponent's commitment:
+fair-rps-nonce = Opponent's nonce:
+fair-rps-verified = Opponent's move matches their commitment
+fair-rps-mismatch = Opponent's move does NOT match their commitment!
+fair-rps-public-record = Public record of this game
+fair-rps-record-title = Fair game record
+fair-rps-record-not-found = No such recorded game.
+fair-rps-record-forfeited = Forfeited before revealing.
+fair-rps-record-commitment = Commitment:
+fair-rps-record-nonce = Nonce:
+fair-rps-record-verified = Move matches the commitment
+fair-rps-record-unverified = Move was not revealed or does not match the commitment
+ttt-title = Tic-tac-toe
+ttt-you-play = You play { $mark }
+ttt-your-turn = Your turn
+ttt-opponent-turn = Opponent's turn
+ttt-invalid-move = That move is not allowed
+royale-title = Battle royale
+royale-explainer = Everyone throws at once. If exactly two different moves are thrown, the players of the beaten one are out. The last player standing wins.
+royale-lobby = Lobby: { $count } / { $max } players
+royale-waiting = Waiting for at least { $min } players...
+royale-starts-in = Starting in { $seconds } s
+royale-round = Round { $round }
+royale-thrown = (thrown)
+royale-out-in = out in round { $round }
+royale-you-are-out = You are out! Stay to watch the rest of the game.
+royale-finished = Game over after { $rounds } rounds
+rooms-title = Game rooms
+rooms-empty = No rooms yet. Create the first one!
+rooms-password = Password for private rooms
+rooms-members = { $count } in the room
+rooms-private = private
+rooms-join = Join
+rooms-leave = Leave the room
+rooms-owner = created by { $username }
+rooms-playing = Playing now:
+rooms-waiting = Waiting for the next game...
+rooms-line = Line-up (winner stays on)
+rooms-streak = { $count } in a row
+rooms-recent = Recent results
+rooms-no-results = No games played yet
+rooms-winner = { $username } won
+rooms-create-title = Create a room
+rooms-name = Room name
+rooms-password-optional = Password (optional)
+rooms-create = Create
+room-error-not-found = Room not found
+room-error-wrong-password = Wrong room password
+room-error-invalid-name = Room name must be 1 to 32 characters
+room-error-name-taken = A room with this name already exists
+room-error-db = Something went wrong, try again later
+spectate-title = Live games
+spectate-empty = Nobody is playing right now.
+spectate-watch = Watch
+spectate-refresh = Refresh
+spectate-back = Back to the list
+spectate-not-found = That game is already over.
+spectate-winner = { $username } won!
+tournaments-title = Tournaments
+tournaments-empty = No tournaments yet.
+tournaments-create-title = New tournament
+tournaments-name = Name
+tournaments-format-elimination = Single elimination
+tournaments-format-swiss = Swiss
+tournaments-starts-at = Starts at
+tournaments-create = Create
+tournaments-players = Players: { $count }
+tournaments-status-open = Sign-up open
+tournaments-status-running = Running
+tournaments-status-finished = Finished
+tournaments-status-cancelled = Cancelled
+tournament-starts = Starts { $date }
+tournament-round = Round { $round }
+tournament-round-of = Round { $round } of { $rounds }
+tournament-join = Join
+tournament-leave = Leave
+tournament-cancel = Cancel tournament
+tournament-refresh = Refresh
+tournament-back = All tournaments
+tournament-matches = Matches
+tournament-bye = bye
+tournament-vs = vs
+tournament-draw = draw
+tournament-standings = Standings
+tournament-col-player = Player
+tournament-col-record = W / D / L
+tournament-col-points = Points
+tournament-error-db = Server error, try again later.
+tournament-error-not-found = Tournament not found.
+tournament-error-not-open = Sign-up is closed.
+tournament-error-invalid-name = Name must be 1 to 64 characters.
+tournament-error-invalid-start = Pick a start time in the future, at most 30 days ahead.
+tournament-error-full = The tournament is full.
+tournament-error-forbidden = Only the creator or an admin can do that.
+tournament-error-network = Network error.
+tournament-started-toast = { $name } has started!
+tournament-match-ready-toast = { $name }, round { $round }: your match against { $opponent } is ready!
+tournament-finished-toast = { $name } is over, { $winner } won!
+tournament-finished-no-winner = { $name } is over.
+leaderboard-title = Leaderboard
+leaderboard-sort-wins = Most wins
+leaderboard-sort-win-rate = Best win rate
+leaderboard-sort-rating = Highest rating
+leaderboard-window-all-time = All time
+leaderboard-window-month = Last 30 days
+leaderboard-window-week = Last 7 days
+leaderboard-window-day = Last 24 hours
+leaderboard-min-games = Only players with at least { $count } games are ranked.
+leaderboard-empty = Nobody has played a ranked game in this period.
+leaderboard-error = Could not load the leaderboard.
+leaderboard-col-player = Player
+leaderboard-col-record = W / D / L
+leaderboard-col-win-rate = Win rate
+leaderboard-col-rating = Rating
+leaderboard-prev = Previous
+leaderboard-next = Next
+seasons-title = Seasons
+seasons-error = Could not load the seasons.
+seasons-none = No season has been scheduled yet.
+seasons-empty = Nobody has played a ranked game this season.
+seasons-live = In progress, standings by current rating
+seasons-archived = Final standings
+collusion-title = Suspicious pairs
+collusion-empty = Nothing has been flagged.
+collusion-games = Games: { $count }
+collusion-signal-head-to-head = Often play each other
+collusion-signal-one-sided = One-sided results
+collusion-signal-move-pattern = Unlikely move pattern
+collusion-status-open = Waiting for review
+collusion-status-dismissed = Dismissed
+collusion-status-excluded = Excluded from ratings
+collusion-dismiss = Dismiss
+collusion-exclude = Exclude from ratings
+collusion-error-db = Database error.
+collusion-error-not-found = Flag not found.
+collusion-error-forbidden = Only moderators can do that.
+collusion-error-network = Network error.
+history-title = Your games
+history-opponent = Opponent
+history-any-result = Any result
+history-filter = Filter
+history-empty = No games found.
+history-error = Could not load your games.
+history-more = Load more
+history-unranked = unranked
+profile-not-found = No such player.
+profile-joined = Joined { $date }
+profile-games = Games
+profile-record = W / D / L
+profile-streak = Win streak
+profile-streak-value = { $current } now, { $best } best
+profile-favourite-move = Favourite move
+profile-forum-posts = Forum posts
+profile-moves = Moves played
+profile-recent = Recent games
+profile-achievements = Achievements
+profile-habits = Your habits
+profile-habits-failed = Failed to analyse your moves.
+profile-habits-empty = Play a few rounds of RPS to see your habits.
+profile-habits-rounds = From your last { $count } rounds. Only you can see this.
+profile-habits-overall = Overall
+profile-habits-after-win = After a win
+profile-habits-after-draw = After a draw
+profile-habits-after-loss = After a loss
+profile-habits-predictability = Predictability
+profile-habits-entropy = Entropy { $bits } bits, 1.58 being perfectly random
+profile-habits-predictor = Against a predictor
+profile-habits-predictor-record = A bot reading your past moves would have won { $wins }, drawn { $draws } and lost { $losses }.
+profile-habits-exploitable = It beats you more than it loses, so mix your moves up.
+profile-habits-unreadable = It can't read you, keep it up.
+achievements-empty = No achievements yet.
+achievement-unlocked-toast = Achievement unlocked: { $name }!
+achievement-unlocked-on = since { $date }
+achievement-first-win = First win
+achievement-first-win-desc = Win a game.
+achievement-win-streak = On fire
+achievement-win-streak-desc = Win 10 games in a row.
+achievement-veteran = Veteran
+achievement-veteran-desc = Play 100 games.
+achievement-all-rounder = All-rounder
+achievement-all-rounder-desc = Win a round with rock, with paper and with scissors.
+achievement-crowd-favourite = Crowd favourite
+achievement-crowd-favourite-desc = Get 10 likes on a forum post.
+challenge-form-label = Challenge a player:
+challenge-form-placeholder = Username
+challenge-form-submit = Challenge
+challenge-incoming = challenges you (best of { $rounds })
+challenge-incoming-toast = { $username } challenges you!
+challenge-waiting = Waiting (best of { $rounds }) for
+challenge-accept = Accept
+challenge-decline = Decline
+challenge-cancel = Cancel challenge
+challenge-accepted-toast = { $username } accepted your challenge!
+challenge-declined-toast = { $username } declined your challenge.
+challenge-expired-toast = Challenge to { $username } expired.
+challenge-unavailable = This player is offline or busy.
 deck-about = About
 deck-source = Source code
 deck-contact = Contact
@@ -62,6 +308,8 @@ navbar-logo-alt = Logo
 navbar-toggle-theme = Toggle theme
 navbar-toggle-forum = Toggle forum
 navbar-toggle-settings = Toggle settings
+navbar-coins = Coins, topped up daily
+navbar-coins-count = { $coins } ¢
 settings-title = Settings
 settings-admin-controls = Admin Controls
 settings-language-alt = Language
@@ -30,11 +30,34 @@ auth-home-title = Главная
 auth-home-welcome = Добро пожаловать, { $username }!
 auth-home-online = Пользователей онлайн: { $count }
 auth-home-created-at = Аккаунт создан: { $date }
+auth-home-rating = Рейтинг: { $rating } ± { $deviation }
 auth-home-play = Играть
 auth-home-logout = Выйти
+quests-title = Задания
+quests-daily = Сегодня
+quests-weekly = На этой неделе
+quests-empty = Сейчас заданий нет.
+quest-progress = { $progress } / { $target }
+quest-completed-toast = Задание выполнено: { $quest }
+quest-play-games = Сыграйте игр: { $count }
+quest-win-games = Выиграйте игр: { $count }
+quest-win-with-rock = Выиграйте раундов камнем: { $count }
+quest-win-with-paper = Выиграйте раундов бумагой: { $count }
+quest-win-with-scissors = Выиграйте раундов ножницами: { $count }
+quest-post-in-forum = Напишите на форуме: { $count }
 games-hub-title = Игры
 games-hub-subtitle = Выберите игру:
 games-hub-rps = Камень-ножницы-бумага
+games-hub-fair-rps = Камень-ножницы-бумага (честная игра)
+games-hub-tic-tac-toe = Крестики-нолики
+games-hub-royale = Королевская битва
+games-hub-rooms = Игровые комнаты
+games-hub-spectate = Смотреть игры
+games-hub-tournaments = Турниры
+games-hub-leaderboard = Рейтинг игроков
+games-hub-seasons = Сезоны
+games-hub-collusion = Проверка сговоров
+games-hub-history = История игр
 games-hub-home = { -home-label }
 rps-waiting = Ожидание соперника...
 rps-playing-against-label = Игра против:
@@ -46,10 +69,233 @@ rps-result-draw = Ничья
 rps-finished = Игра окончена! { $result }
 rps-opponent-played-label = сыграл:
 rps-next-game = Следующая игра
-rps-leave = Покинуть
+rps-time-left = Время на ход: { $seconds } с
+rps-timed-out = Никто не сделал ход вовремя, игра отменена
+rps-you-forfeited = Время вышло, вам засчитано поражение
+rps-opponent-forfeited = У { $username } вышло время, ему засчитано поражение
 rps-other-games = Другие игры
 rps-home = { -home-label }
 rps-opponent-disconnected = Соперник отключился!
+rps-opponent-away = Соперник отключился, у него { $seconds } с, чтобы вернуться
+rps-opponent-back = Соперник вернулся!
+rps-best-of = До { $rounds }
+rps-stake-label = Ставка:
+rps-stake-none = Без ставки
+rps-stake-coins = { $coins } монет
+rps-stake-held = На эту игру поставлено { $coins } монет
+rps-stake-insufficient = Недостаточно монет для такой ставки
+rps-score = Раунд { $round } · { $score } (до { $rounds })
+rps-last-round = Прошлый раунд:
+rps-final-score = Итоговый счёт: { $score }
+rps-rematch = Реванш
+rps-rematch-accept = Принять реванш
+rps-rematch-waiting = Ждём соперника...
+rps-rematch-decline = Отказаться
+rps-rematch-offered = Соперник предлагает реванш!
+rps-rematch-declined = Реванш отклонён.
+fair-rps-title = Честная игра КНБ
+fair-rps-explainer = Сначала отправляется хеш вашего хода, а сам ход раскрывается только когда оба игрока сделали выбор, поэтому подсмотреть или изменить его нельзя.
+fair-rps-opponent-committed = (выбрал)
+fair-rps-committed = Ход зафиксирован, ждём соперника...
+fair-rps-revealing = Раскрываем ходы...
+fair-rps-opponent-revealed = (раскрыл)
+fair-rps-your-commitment = Ваш хеш:
+fair-rps-commitment = Хеш соперника:
+fair-rps-nonce = Nonce соперника:
+fair-rps-verified = Ход соперника совпадает с его хешем
+fair-rps-mismatch = Ход соперника НЕ совпадает с его хешем!
+fair-rps-public-record = Публичная запись этой игры
+fair-rps-record-title = Запись честной игры
+fair-rps-record-not-found = Такой записанной игры нет.
+fair-rps-record-forfeited = Проиграл, не раскрыв ход.
+fair-rps-record-commitment = Хеш:
+fair-rps-record-nonce = Nonce:
+fair-rps-record-verified = Ход совпадает с хешем
+fair-rps-record-unverified = Ход не раскрыт или не совпадает с хешем
+ttt-title = Крестики-нолики
+ttt-you-play = Вы играете за { $mark }
+ttt-your-turn = Ваш ход
+ttt-opponent-turn = Ход соперника
+ttt-invalid-move = Такой ход невозможен
+royale-title = Королевская битва
+royale-explainer = Все бросают одновременно. Если выброшены ровно два разных хода, игроки с побеждённым ходом выбывают. Побеждает последний оставшийся.
+royale-lobby = Лобби: { $count } / { $max } игроков
+royale-waiting = Ожидание хотя бы { $min } игроков...
+royale-starts-in = Старт через { $seconds } с
+royale-round = Раунд { $round }
+royale-thrown = (сделал ход)
+royale-out-in = выбыл в раунде { $round }
+royale-you-are-out = Вы выбыли! Можете досмотреть игру.
+royale-finished = Игра окончена за { $rounds } раундов
+rooms-title = Игровые комнаты
+rooms-empty = Комнат пока нет. Создайте первую!
+rooms-password = Пароль для закрытых комнат
+rooms-members = В комнате: { $count }
+rooms-private = закрытая
+rooms-join = Войти
+rooms-leave = Выйти из комнаты
+rooms-owner = создал(а) { $username }
+rooms-playing = Сейчас играют:
+rooms-waiting = Ожидание следующей игры...
+rooms-line = Очередь (победитель остаётся)
+rooms-streak = { $count } подряд
+rooms-recent = Последние результаты
+rooms-no-results = Игр ещё не было
+rooms-winner = Победа: { $username }
+rooms-create-title = Создать комнату
+rooms-name = Название комнаты
+rooms-password-optional = Пароль (необязательно)
+rooms-create = Создать
+room-error-not-found = Комната не найдена
+room-error-wrong-password = Неверный пароль комнаты
+room-error-invalid-name = Название должно быть от 1 до 32 символов
+room-error-name-taken = Комната с таким названием уже есть
+room-error-db = Что-то пошло не так, попробуйте позже
+spectate-title = Текущие игры
+spectate-empty = Сейчас никто не играет.
+spectate-watch = Смотреть
+spectate-refresh = Обновить
+spectate-back = К списку игр
+spectate-not-found = Эта игра уже закончилась.
+spectate-winner = Победил { $username }!
+tournaments-title = Турниры
+tournaments-empty = Турниров пока нет.
+tournaments-create-title = Новый турнир
+tournaments-name = Название
+tournaments-format-elimination = На выбывание
+tournaments-format-swiss = Швейцарская система
+tournaments-starts-at = Начало
+tournaments-create = Создать
+tournaments-players = Игроков: { $count }
+tournaments-status-open = Идёт запись
+tournaments-status-running = Идёт
+tournaments-status-finished = Завершён
+tournaments-status-cancelled = Отменён
+tournament-starts = Начало { $date }
+tournament-round = Раунд { $round }
+tournament-round-of = Раунд { $round } из { $rounds }
+tournament-join = Участвовать
+tournament-leave = Выйти
+tournament-cancel = Отменить турнир
+tournament-refresh = Обновить
+tournament-back = Все турниры
+tournament-matches = Матчи
+tournament-bye = пропуск
+tournament-vs = против
+tournament-draw = ничья
+tournament-standings = Таблица
+tournament-col-player = Игрок
+tournament-col-record = П / Н / П
+tournament-col-points = Очки
+tournament-error-db = Ошибка сервера, попробуйте позже.
+tournament-error-not-found = Турнир не найден.
+tournament-error-not-open = Запись закрыта.
+tournament-error-invalid-name = Название должно быть от 1 до 64 символов.
+tournament-error-invalid-start = Выберите время в будущем, не позже чем через 30 дней.
+tournament-error-full = Турнир заполнен.
+tournament-error-forbidden = Это может сделать только создатель или администратор.
+tournament-error-network = Ошибка сети.
+tournament-started-toast = Турнир { $name } начался!
+tournament-match-ready-toast = { $name }, раунд { $round }: ваш матч против { $opponent } готов!
+tournament-finished-toast = Турнир { $name } завершён, победил { $winner }!
+tournament-finished-no-winner = Турнир { $name } завершён.
+leaderboard-title = Рейтинг игроков
+leaderboard-sort-wins = Больше побед
+leaderboard-sort-win-rate = Лучший процент побед
+leaderboard-sort-rating = Высший рейтинг
+leaderboard-window-all-time = За всё время
+leaderboard-window-month = За 30 дней
+leaderboard-window-week = За 7 дней
+leaderboard-window-day = За 24 часа
+leaderboard-min-games = Учитываются игроки, сыгравшие не меньше { $count } игр.
+leaderboard-empty = За этот период никто не сыграл рейтинговых игр.
+leaderboard-error = Не удалось загрузить рейтинг.
+leaderboard-col-player = Игрок
+leaderboard-col-record = П / Н / П
+leaderboard-col-win-rate = Процент побед
+leaderboard-col-rating = Рейтинг
+leaderboard-prev = Назад
+leaderboard-next = Вперёд
+seasons-title = Сезоны
+seasons-error = Не удалось загрузить сезоны.
+seasons-none = Сезоны ещё не запланированы.
+seasons-empty = В этом сезоне никто не сыграл рейтинговых игр.
+seasons-live = Идёт сейчас, места по текущему рейтингу
+seasons-archived = Итоговые места
+collusion-title = Подозрительные пары
+collusion-empty = Ничего не найдено.
+collusion-games = Игр: { $count }
+collusion-signal-head-to-head = Часто играют друг с другом
+collusion-signal-one-sided = Односторонние результаты
+collusion-signal-move-pattern = Маловероятные ходы
+collusion-status-open = Ждёт проверки
+collusion-status-dismissed = Отклонено
+collusion-status-excluded = Исключено из рейтинга
+collusion-dismiss = Отклонить
+collusion-exclude = Исключить из рейтинга
+collusion-error-db = Ошибка базы данных.
+collusion-error-not-found = Отметка не найдена.
+collusion-error-forbidden = Это могут делать только модераторы.
+collusion-error-network = Ошибка сети.
+history-title = Ваши игры
+history-opponent = Соперник
+history-any-result = Любой результат
+history-filter = Найти
+history-empty = Игр не найдено.
+history-error = Не удалось загрузить ваши игры.
+history-more = Загрузить ещё
+history-unranked = без рейтинга
+profile-not-found = Такого игрока нет.
+profile-joined = С нами с { $date }
+profile-games = Игры
+profile-record = П / Н / П
+profile-streak = Серия побед
+profile-streak-value = сейчас { $current }, лучшая { $best }
+profile-favourite-move = Любимый ход
+profile-forum-posts = Сообщения на форуме
+profile-moves = Сыгранные ходы
+profile-recent = Последние игры
+profile-achievements = Достижения
+profile-habits = Ваши привычки
+profile-habits-failed = Не удалось проанализировать ходы.
+profile-habits-empty = Сыграйте несколько раундов, чтобы увидеть свои привычки.
+profile-habits-rounds = По последним { $count } раундам. Видно только вам.
+profile-habits-overall = В целом
+profile-habits-after-win = После победы
+profile-habits-after-draw = После ничьей
+profile-habits-after-loss = После поражения
+profile-habits-predictability = Предсказуемость
+profile-habits-entropy = Энтропия { $bits } бит, 1.58 — полностью случайно
+profile-habits-predictor = Против предсказателя
+profile-habits-predictor-record = Бот, читающий ваши прошлые ходы, выиграл бы { $wins }, сыграл вничью { $draws } и проиграл { $losses }.
+profile-habits-exploitable = Он чаще выигрывает, чем проигрывает, так что разнообразьте ходы.
+profile-habits-unreadable = Он не может вас прочитать, так держать.
+achievements-empty = Достижений пока нет.
+achievement-unlocked-toast = Новое достижение: { $name }!
+achievement-unlocked-on = с { $date }
+achievement-first-win = Первая победа
+achievement-first-win-desc = Выиграйте игру.
+achievement-win-streak = В ударе
+achievement-win-streak-desc = Выиграйте 10 игр подряд.
+achievement-veteran = Ветеран
+achievement-veteran-desc = Сыграйте 100 игр.
+achievement-all-rounder = Универсал
+achievement-all-rounder-desc = Выиграйте раунд камнем, бумагой и ножницами.
+achievement-crowd-favourite = Любимец публики
+achievement-crowd-favourite-desc = Наберите 10 лайков под сообщением на форуме.
+challenge-form-label = Вызвать игрока:
+challenge-form-placeholder = Имя пользователя
+challenge-form-submit = Вызвать
+challenge-incoming = вызывает вас (до { $rounds })
+challenge-incoming-toast = { $username } вызывает вас!
+challenge-waiting = Ожидание ответа (до { $rounds }) от
+challenge-accept = Принять
+challenge-decline = Отклонить
+challenge-cancel = Отменить вызов
+challenge-accepted-toast = { $username } принял ваш вызов!
+challenge-declined-toast = { $username } отклонил ваш вызов.
+challenge-expired-toast = Вызов игроку { $username } истёк.
+challenge-unavailable = Игрок не в сети или занят.
 deck-about = О проекте
 deck-source = Исходный код
 deck-contact = Контакты
@@ -58,6 +304,8 @@ navbar-logo-alt = Логотип
 navbar-toggle-theme = Переключить тему
 navbar-toggle-forum = Показать/скрыть форум
 navbar-toggle-settings = Открыть настройки
+navbar-coins = Монеты, пополняются каждый день
+navbar-coins-count = { $coins } ¢
 settings-title = Настройки
 settings-admin-controls = Админ-панель
 settings-language-alt = Язык
@@ -0,0 +1,38 @@
+use gloo_net::http::{Request, Response};
+use serde::de::DeserializeOwned;
+use shared::collusion::*;
+
+pub async fn fetch_collusion_flags() -> Result<Vec<CollusionFlag>, CollusionError>
+{
+    run_collusion_cmd(CollusionCmd::List).await
+}
+
+pub async fn dismiss_collusion_flag(id: i64) -> Result<(), CollusionError>
+{
+    run_collusion_cmd(CollusionCmd::Dismiss { id }).await
+}
+
+pub async fn exclude_collusion_flag(id: i64) -> Result<(), CollusionError>
+{
+    run_collusion_cmd(CollusionCmd::Exclude { id }).await
+}
+
+async fn run_collusion_cmd<T>(cmd: CollusionCmd) -> Result<T, CollusionError>
+    where T: DeserializeOwned
+{
+    let response = send_collusion_cmd(cmd).await
+                                          .ok_or(CollusionError::NetworkError)?;
+
+    response.json::<Result<T, CollusionError>>()
+            .await
+            .map_err(|_| CollusionError::NetworkError)?
+}
+
+async fn send_collusion_cmd(cmd: CollusionCmd) -> Option<Response>
+{
+    Request::post("/api/collusion").json(&cmd)
+                                   .unwrap()
+                                   .send()
+                                   .await
+                                   .ok()
+}
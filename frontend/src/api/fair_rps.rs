@@ -0,0 +1,12 @@
+use gloo_net::http::Request;
+use shared::fair_rps::FairRpsRecord;
+
+pub async fn fetch_fair_game(id: i64) -> Option<FairRpsRecord>
+{
+    let response = Request::get(&format!("/api/games/rps/fair/{id}")).send().await.ok()?;
+    if !response.ok() {
+        return None;
+    }
+
+    response.json::<FairRpsRecord>().await.ok()
+}
@@ -7,7 +7,7 @@ pub async fn fetch_posts() -> Option<Vec<UserForumPost>>
     let result = response.json::<Result<Vec<UserForumPost>, ForumError>>()
                          .await
                          .ok()?;
-    return result.ok();
+    result.ok()
 }
 
 pub async fn fetch_posts_by(start_id: i64, end_id: i64) -> Option<Vec<UserForumPost>>
@@ -16,7 +16,7 @@ pub async fn fetch_posts_by(start_id: i64, end_id: i64) -> Option<Vec<UserForumP
     let result = response.json::<Result<Vec<UserForumPost>, ForumError>>()
                          .await
                          .ok()?;
-    return result.ok();
+    result.ok()
 }
 
 pub async fn create_post(msg: String) -> Result<ForumPost, ForumError>
@@ -24,11 +24,9 @@ pub async fn create_post(msg: String) -> Result<ForumPost, ForumError>
     let response = send_forum_cmd(ForumCmd::MakePost(msg)).await
                                                           .ok_or(ForumError::NetworkError)?;
 
-    let result = response.json::<Result<ForumPost, ForumError>>()
-                         .await
-                         .map_err(|_| ForumError::NetworkError)?;
-
-    return result;
+    response.json::<Result<ForumPost, ForumError>>()
+            .await
+            .map_err(|_| ForumError::NetworkError)?
 }
 
 pub async fn like_post(post_id: i64) -> Result<(), ForumError>
@@ -36,11 +34,9 @@ pub async fn like_post(post_id: i64) -> Result<(), ForumError>
     let response = send_forum_cmd(ForumCmd::LikePost { post_id }).await
                                                                  .ok_or(ForumError::NetworkError)?;
 
-    let result = response.json::<Result<(), ForumError>>()
-                         .await
-                         .map_err(|_| ForumError::NetworkError)?;
-
-    return result;
+    response.json::<Result<(), ForumError>>()
+            .await
+            .map_err(|_| ForumError::NetworkError)?
 }
 
 pub async fn dislike_post(post_id: i64) -> Result<(), ForumError>
@@ -49,11 +45,9 @@ pub async fn dislike_post(post_id: i64) -> Result<(), ForumError>
         send_forum_cmd(ForumCmd::DislikePost { post_id }).await
                                                          .ok_or(ForumError::NetworkError)?;
 
-    let result = response.json::<Result<(), ForumError>>()
-                         .await
-                         .map_err(|_| ForumError::NetworkError)?;
-
-    return result;
+    response.json::<Result<(), ForumError>>()
+            .await
+            .map_err(|_| ForumError::NetworkError)?
 }
 
 pub async fn undo_reaction(post_id: i64) -> Result<(), ForumError>
@@ -62,11 +56,9 @@ pub async fn undo_reaction(post_id: i64) -> Result<(), ForumError>
         send_forum_cmd(ForumCmd::UndoReaction { post_id }).await
                                                           .ok_or(ForumError::NetworkError)?;
 
-    let result = response.json::<Result<(), ForumError>>()
-                         .await
-                         .map_err(|_| ForumError::NetworkError)?;
-
-    return result;
+    response.json::<Result<(), ForumError>>()
+            .await
+            .map_err(|_| ForumError::NetworkError)?
 }
 
 async fn send_forum_cmd(cmd: ForumCmd) -> Option<Response>
@@ -84,9 +76,7 @@ pub async fn delete_post(post_id: i64) -> Result<(), ForumError>
         send_forum_cmd(ForumCmd::DeletePost { post_id }).await
                                                         .ok_or(ForumError::NetworkError)?;
 
-    let result = response.json::<Result<(), ForumError>>()
-                         .await
-                         .map_err(|_| ForumError::NetworkError)?;
-
-    return result;
+    response.json::<Result<(), ForumError>>()
+            .await
+            .map_err(|_| ForumError::NetworkError)?
 }
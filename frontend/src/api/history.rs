@@ -0,0 +1,31 @@
+use gloo_net::http::Request;
+use shared::history::*;
+
+use super::query_value;
+
+pub async fn fetch_history(query: HistoryQuery) -> Option<HistoryPage>
+{
+    let mut params = Vec::new();
+    if let Some(username) = query.username {
+        params.push(("username", username));
+    }
+    if let Some(opponent) = query.opponent {
+        params.push(("opponent", opponent));
+    }
+    if let Some(result) = query.result {
+        params.push(("result", query_value(result)));
+    }
+    if let Some(before) = query.before {
+        params.push(("before", before.to_string()));
+    }
+
+    let response = Request::get("/api/games/rps/history").query(params)
+                                                         .send()
+                                                         .await
+                                                         .ok()?;
+    if !response.ok() {
+        return None;
+    }
+
+    response.json::<HistoryPage>().await.ok()
+}
@@ -0,0 +1,25 @@
+use gloo_net::http::Request;
+use shared::leaderboard::*;
+
+use super::query_value;
+
+pub async fn fetch_leaderboard(query: LeaderboardQuery)
+                               -> Result<LeaderboardPage, LeaderboardError>
+{
+    let mut params = vec![("sort", query_value(query.sort)),
+                          ("window", query_value(query.window)),
+                          ("page", query.page.to_string())];
+    if let Some(min_games) = query.min_games {
+        params.push(("min_games", min_games.to_string()));
+    }
+
+    let response = Request::get("/api/leaderboard").query(params)
+                                                   .send()
+                                                   .await
+                                                   .map_err(|_| LeaderboardError::NetworkError)?;
+
+    response.json::<Result<LeaderboardPage, LeaderboardError>>()
+            .await
+            .map_err(|_| LeaderboardError::NetworkError)?
+}
+
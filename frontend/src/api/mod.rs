@@ -1,7 +1,30 @@
 mod auth;
+mod collusion;
+mod fair_rps;
 mod forum;
+mod history;
+mod leaderboard;
+mod profile;
+mod seasons;
+mod tournaments;
 
 pub use auth::{
     fetch_user_info, login_user, register_user,
 };
+pub use collusion::*;
+pub use fair_rps::*;
 pub use forum::*;
+pub use history::*;
+pub use leaderboard::*;
+pub use profile::*;
+pub use seasons::*;
+pub use tournaments::*;
+
+/// Query string spelling of an enum, the same the server deserializes it with.
+fn query_value<T: serde::Serialize>(value: T) -> String
+{
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
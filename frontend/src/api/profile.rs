@@ -0,0 +1,23 @@
+use gloo_net::http::Request;
+use shared::profile::{MoveAnalytics, PlayerProfile};
+
+pub async fn fetch_profile(username: &str) -> Option<PlayerProfile>
+{
+    let url = format!("/api/users/{}", String::from(js_sys::encode_uri_component(username)));
+    let response = Request::get(&url).send().await.ok()?;
+    if !response.ok() {
+        return None;
+    }
+
+    response.json::<PlayerProfile>().await.ok()
+}
+
+pub async fn fetch_move_analytics() -> Option<MoveAnalytics>
+{
+    let response = Request::get("/api/moves").send().await.ok()?;
+    if !response.ok() {
+        return None;
+    }
+
+    response.json::<MoveAnalytics>().await.ok()
+}
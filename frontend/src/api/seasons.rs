@@ -0,0 +1,25 @@
+use gloo_net::http::Request;
+use shared::season::*;
+
+pub async fn fetch_seasons() -> Result<Vec<SeasonSummary>, SeasonError>
+{
+    let response = Request::get("/api/seasons").send()
+                                               .await
+                                               .map_err(|_| SeasonError::NetworkError)?;
+
+    response.json::<Result<Vec<SeasonSummary>, SeasonError>>()
+            .await
+            .map_err(|_| SeasonError::NetworkError)?
+}
+
+pub async fn fetch_season(id: i64) -> Result<SeasonDetails, SeasonError>
+{
+    let url = format!("/api/seasons/{id}");
+    let response = Request::get(&url).send()
+                                     .await
+                                     .map_err(|_| SeasonError::NetworkError)?;
+
+    response.json::<Result<SeasonDetails, SeasonError>>()
+            .await
+            .map_err(|_| SeasonError::NetworkError)?
+}
@@ -0,0 +1,53 @@
+use gloo_net::http::{Request, Response};
+use serde::de::DeserializeOwned;
+use shared::tournament::*;
+
+pub async fn fetch_tournaments() -> Result<Vec<TournamentSummary>, TournamentError>
+{
+    run_tournament_cmd(TournamentCmd::List).await
+}
+
+pub async fn fetch_tournament(id: i64) -> Result<TournamentDetails, TournamentError>
+{
+    run_tournament_cmd(TournamentCmd::Fetch { id }).await
+}
+
+pub async fn create_tournament(new: NewTournament) -> Result<i64, TournamentError>
+{
+    run_tournament_cmd(TournamentCmd::Create(new)).await
+}
+
+pub async fn join_tournament(id: i64) -> Result<(), TournamentError>
+{
+    run_tournament_cmd(TournamentCmd::Join { id }).await
+}
+
+pub async fn leave_tournament(id: i64) -> Result<(), TournamentError>
+{
+    run_tournament_cmd(TournamentCmd::Leave { id }).await
+}
+
+pub async fn cancel_tournament(id: i64) -> Result<(), TournamentError>
+{
+    run_tournament_cmd(TournamentCmd::Cancel { id }).await
+}
+
+async fn run_tournament_cmd<T>(cmd: TournamentCmd) -> Result<T, TournamentError>
+    where T: DeserializeOwned
+{
+    let response = send_tournament_cmd(cmd).await
+                                           .ok_or(TournamentError::NetworkError)?;
+
+    response.json::<Result<T, TournamentError>>()
+            .await
+            .map_err(|_| TournamentError::NetworkError)?
+}
+
+async fn send_tournament_cmd(cmd: TournamentCmd) -> Option<Response>
+{
+    Request::post("/api/tournaments").json(&cmd)
+                                     .unwrap()
+                                     .send()
+                                     .await
+                                     .ok()
+}
@@ -46,14 +46,16 @@ pub fn App() -> impl IntoView
 {
     provide_toaster();
 
-    let info = LocalResource::new(move || fetch_user_info());
+    let info = LocalResource::new(fetch_user_info);
     provide_context(UserResCtx(info));
 
     let (new_posts, set_new_posts) = signal(false);
     let (visible_forum, set_visible_forum) = signal(false);
+    let (coins, set_coins) = signal(None);
 
     provide_context(NavBarCtx { visible_forum: (visible_forum, set_visible_forum),
-                                new_posts: (new_posts, set_new_posts) });
+                                new_posts: (new_posts, set_new_posts),
+                                coins: (coins, set_coins) });
 
     let (admin, set_admin) = signal(false);
 
@@ -162,6 +164,11 @@ fn AuthView(user_info: UserInfo) -> impl IntoView
     set_authed.set(true);
 
     view! {
+        <Challenges />
+        <TournamentAlerts />
+        <AchievementAlerts />
+        <QuestAlerts />
+        <WalletUpdates />
         <Routes transition=true fallback=|| "Not found.">
             <Route path=path!("/") view=AuthHome/>
             <Route path=path!("/login") view=|| {view! {<Redirect path="/" />}} />
@@ -171,6 +178,21 @@ fn AuthView(user_info: UserInfo) -> impl IntoView
             <ParentRoute path=path!("/games") view=|| {view! {<Outlet />}} >
                 <Route path=path!("") view=GamesHub />
                 <Route path=path!("rps") view=RpsGame />
+                <Route path=path!("rps-fair") view=FairRpsGame />
+                <Route path=path!("rps-fair/:id") view=FairRpsVerify />
+                <Route path=path!("tic-tac-toe") view=TicTacToeGame />
+                <Route path=path!("royale") view=Royale />
+                <Route path=path!("rooms") view=Rooms />
+                <Route path=path!("spectate") view=Spectate />
+                <Route path=path!("leaderboard") view=Leaderboard />
+                <Route path=path!("seasons") view=Seasons />
+                <Route path=path!("collusion") view=CollusionReview />
+                <Route path=path!("history") view=History />
+            </ParentRoute>
+            <Route path=path!("/users/:username") view=Profile />
+            <ParentRoute path=path!("/tournaments") view=|| {view! {<Outlet />}} >
+                <Route path=path!("") view=Tournaments />
+                <Route path=path!(":id") view=TournamentPage />
             </ParentRoute>
         </Routes>
     }
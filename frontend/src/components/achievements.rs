@@ -0,0 +1,87 @@
+use chrono::Local;
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use shared::{
+    achievement::{Achievement, UnlockedAchievement},
+    ws_messages::ServerMsg,
+};
+
+use crate::hooks::{MyToaster, WebsocketContext};
+
+fn achievement_name(achievement: Achievement) -> String
+{
+    match achievement {
+        Achievement::FirstWin => tr!("achievement-first-win"),
+        Achievement::WinStreak => tr!("achievement-win-streak"),
+        Achievement::Veteran => tr!("achievement-veteran"),
+        Achievement::AllRounder => tr!("achievement-all-rounder"),
+        Achievement::CrowdFavourite => tr!("achievement-crowd-favourite"),
+    }
+}
+
+fn achievement_description(achievement: Achievement) -> String
+{
+    match achievement {
+        Achievement::FirstWin => tr!("achievement-first-win-desc"),
+        Achievement::WinStreak => tr!("achievement-win-streak-desc"),
+        Achievement::Veteran => tr!("achievement-veteran-desc"),
+        Achievement::AllRounder => tr!("achievement-all-rounder-desc"),
+        Achievement::CrowdFavourite => tr!("achievement-crowd-favourite-desc"),
+    }
+}
+
+/// Toast for every achievement unlocked while online.
+#[component]
+pub fn AchievementAlerts() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let toaster = MyToaster::new();
+
+    Effect::new(move |_| {
+        let Some(ServerMsg::AchievementMsg(unlocked)) = ws.message.get() else {
+            return;
+        };
+
+        toaster.success(&tr!("achievement-unlocked-toast", {
+                            "name" => achievement_name(unlocked.achievement)
+                        }));
+    });
+}
+
+/// Badges of the achievements a player has unlocked.
+#[component]
+pub fn AchievementBadges(achievements: Vec<UnlockedAchievement>) -> impl IntoView
+{
+    view! {
+        <Show
+        when={
+            let empty = achievements.is_empty();
+            move || !empty
+        }
+        fallback=|| view! { <p>{ move || tr!("achievements-empty") }</p> }
+        >
+            <ul class="achievement-list">
+                { achievements.iter()
+                              .map(|unlocked| {
+                                  let achievement = unlocked.achievement;
+                                  let date = unlocked.unlocked_at
+                                                     .with_timezone(&Local)
+                                                     .format("%d.%m.%Y")
+                                                     .to_string();
+                                  view! {
+                                      <li
+                                      class="achievement-badge"
+                                      title=move || achievement_description(achievement)
+                                      >
+                                          <strong>{ move || achievement_name(achievement) }</strong>
+                                          <span style="color: var(--muted);">
+                                              { move_tr!("achievement-unlocked-on", {"date" => date.clone()}) }
+                                          </span>
+                                      </li>
+                                  }
+                              })
+                              .collect::<Vec<_>>() }
+            </ul>
+        </Show>
+    }
+}
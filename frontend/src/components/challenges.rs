@@ -0,0 +1,206 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_router::hooks::use_navigate;
+use shared::{
+    challenge::{ChallengeEvent, ChallengeReq},
+    game::GameError,
+    rps_game::{BestOf, RpsGameReq, RpsKind},
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use crate::hooks::{MyToaster, WebsocketContext};
+
+fn challenge_msg(req: ChallengeReq<BestOf>) -> ClientMsg
+{
+    ClientMsg::game::<RpsKind>(RpsGameReq::Challenge(req))
+}
+
+/// Floating panel with incoming and outgoing direct challenges.
+#[component]
+pub fn Challenges() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let toaster = MyToaster::new();
+    let navigate = use_navigate();
+
+    let (incoming, set_incoming) = signal(Vec::<(String, BestOf)>::new());
+    let (outgoing, set_outgoing) = signal::<Option<(String, BestOf)>>(None);
+
+    Effect::new({
+        let ws = ws.clone();
+        let navigate = navigate.clone();
+
+        move |_| {
+            let Some(msg) = ws.message.get() else {
+                return;
+            };
+            let event = match msg {
+                ServerMsg::ChallengeMsg(envelope) => envelope.open_challenge::<RpsKind>(),
+                ServerMsg::GameErrorMsg(GameError::UserUnavailable) => {
+                    toaster.error(&tr!("challenge-unavailable"));
+                    None
+                }
+                _ => None,
+            };
+            let Some(event) = event else {
+                return;
+            };
+
+            match event {
+                ChallengeEvent::Incoming { from, settings: best_of, .. } => {
+                    toaster.info(&tr!("challenge-incoming-toast", {"username" => from.clone()}));
+                    set_incoming.update(|v| {
+                                    v.retain(|(name, _)| *name != from);
+                                    v.push((from, best_of));
+                                });
+                }
+                ChallengeEvent::Sent { to, settings: best_of, .. } => {
+                    set_outgoing.set(Some((to, best_of)));
+                }
+                ChallengeEvent::Accepted { by } => {
+                    set_outgoing.set(None);
+                    toaster.success(&tr!("challenge-accepted-toast", {"username" => by}));
+                    navigate("/games/rps", Default::default());
+                }
+                ChallengeEvent::Declined { by } => {
+                    set_outgoing.set(None);
+                    toaster.error(&tr!("challenge-declined-toast", {"username" => by}));
+                }
+                ChallengeEvent::Cancelled { by } => {
+                    set_incoming.update(|v| v.retain(|(name, _)| *name != by));
+                }
+                ChallengeEvent::Expired { with } => {
+                    set_incoming.update(|v| v.retain(|(name, _)| *name != with));
+                    if outgoing.get_untracked().is_some_and(|(to, _)| to == with) {
+                        set_outgoing.set(None);
+                        toaster.info(&tr!("challenge-expired-toast", {"username" => with}));
+                    }
+                }
+            }
+        }
+    });
+
+    let respond = {
+        let ws = ws.clone();
+        move |from: String, accept: bool| {
+            set_incoming.update(|v| v.retain(|(name, _)| *name != from));
+            if accept {
+                ws.send(challenge_msg(ChallengeReq::Accept { from }));
+                navigate("/games/rps", Default::default());
+            } else {
+                ws.send(challenge_msg(ChallengeReq::Decline { from }));
+            }
+        }
+    };
+
+    let cancel = {
+        let ws = ws.clone();
+        move |_| {
+            ws.send(challenge_msg(ChallengeReq::Cancel));
+            set_outgoing.set(None);
+        }
+    };
+
+    view! {
+        <div
+        class="card stack challenges"
+        class:el-hide=move || incoming.with(|v| v.is_empty()) && outgoing.with(|o| o.is_none())
+        >
+            <For
+            each=move || incoming.get()
+            key=|(from, best_of)| (from.clone(), *best_of)
+            children=move |(from, best_of)| {
+                let accept = {
+                    let respond = respond.clone();
+                    let from = from.clone();
+                    move |_| respond(from.clone(), true)
+                };
+                let decline = {
+                    let respond = respond.clone();
+                    let from = from.clone();
+                    move |_| respond(from.clone(), false)
+                };
+
+                view! {
+                    <p>
+                        <span class="mention-name">{from.clone()}</span>{" "}
+                        { move_tr!("challenge-incoming", {"rounds" => best_of.rounds()}) }
+                    </p>
+                    <div class="cluster" style="--cluster-justify: center;">
+                        <button on:click=accept>{ move || tr!("challenge-accept") }</button>
+                        <button class="secondary destructive" on:click=decline>
+                            { move || tr!("challenge-decline") }
+                        </button>
+                    </div>
+                }
+            }
+            />
+            { move || outgoing.get().map(|(to, best_of)| view! {
+                <p>
+                    { move_tr!("challenge-waiting", {"rounds" => best_of.rounds()}) }{" "}
+                    <span class="mention-name">{to}</span>
+                </p>
+                <button class="secondary destructive" on:click=cancel.clone()>
+                    { move || tr!("challenge-cancel") }
+                </button>
+            }) }
+        </div>
+    }
+}
+
+/// Form for challenging a specific user to an RPS match.
+#[component]
+pub fn ChallengeForm() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+
+    let (username, set_username) = signal(String::new());
+    let (best_of, set_best_of) = signal(BestOf::One);
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let username = username.get().trim().to_string();
+        if !username.is_empty() {
+            let req = ChallengeReq::Send { username,
+                                           settings: best_of.get() };
+            ws.send(challenge_msg(req));
+            set_username.set(String::new());
+        }
+    };
+
+    view! {
+        <form class="stack" on:submit=on_submit>
+            <label for="challenge-username" style="font-weight: 700;">
+                { move || tr!("challenge-form-label") }
+            </label>
+            <div class="cluster" style="--cluster-justify: center;">
+                <input
+                id="challenge-username"
+                type="text"
+                placeholder=move || tr!("challenge-form-placeholder")
+                prop:value=username
+                on:input=move |ev| set_username.set(event_target_value(&ev))
+                />
+                <select on:change=move |ev| {
+                    let series = match event_target_value(&ev).as_str() {
+                        "3" => BestOf::Three,
+                        "5" => BestOf::Five,
+                        "7" => BestOf::Seven,
+                        _ => BestOf::One,
+                    };
+                    set_best_of.set(series);
+                }>
+                    { BestOf::ALL.into_iter()
+                                 .map(|series| view! {
+                                     <option value=series.rounds().to_string()>
+                                         { move_tr!("rps-best-of", {"rounds" => series.rounds()}) }
+                                     </option>
+                                 })
+                                 .collect::<Vec<_>>() }
+                </select>
+                <button type="submit">{ move || tr!("challenge-form-submit") }</button>
+            </div>
+        </form>
+    }
+}
@@ -17,6 +17,7 @@ use crate::{
         create_post, delete_post, dislike_post, fetch_posts, fetch_posts_by, like_post,
         undo_reaction,
     },
+    components::ProfileLink,
     hooks::{MyToaster, NavBarCtx, SettingsCtx, StateCtx, UserResCtx, WebsocketContext},
 };
 
@@ -65,10 +66,12 @@ fn render_mentions(text: &str) -> impl IntoView + use<>
                     out.push(text[last_byte..mention_start].to_string().into_any());
                 }
 
+                // Skip the '@' for the profile address.
                 out.push(view! {
-                             <span class="mention-name">
-                                 { &text[mention_start..mention_end] }
-                             </span>
+                             <ProfileLink
+                             username=&text[mention_start + 1..mention_end]
+                             label=&text[mention_start..mention_end]
+                             />
                          }.into_any());
 
                 last_byte = mention_end;
@@ -133,7 +136,7 @@ fn ForumAuth() -> impl IntoView
 
     let toaster = MyToaster::new();
 
-    let forum_res = LocalResource::new(move || fetch_posts());
+    let forum_res = LocalResource::new(fetch_posts);
 
     let (message, set_message) = signal(String::new());
 
@@ -185,22 +188,21 @@ fn ForumAuth() -> impl IntoView
             let has_posts = posts_sig.with(|v| !v.is_empty());
             let already = scrolled_once.get();
 
-            if has_posts && !already {
-                if let Some(el) = forum_elem.get() {
-                    let bottom = (el.scroll_height() - el.client_height()) as f64;
-                    set_y(bottom);
-                    set_scrolled_once.set(true);
-                }
+            if has_posts
+               && !already
+               && let Some(el) = forum_elem.get()
+            {
+                let bottom = (el.scroll_height() - el.client_height()) as f64;
+                set_y(bottom);
+                set_scrolled_once.set(true);
             }
 
             let y_coord = y.get_untracked();
-            if already {
-                if let Some(el) = forum_elem.get() {
-                    let bottom = (el.scroll_height() - el.client_height()) as f64;
+            if already && let Some(el) = forum_elem.get() {
+                let bottom = (el.scroll_height() - el.client_height()) as f64;
 
-                    if (bottom - y_coord) <= (5.0 * (bottom / 26.0)) {
-                        set_y(bottom);
-                    }
+                if (bottom - y_coord) <= (5.0 * (bottom / 26.0)) {
+                    set_y(bottom);
                 }
             }
         }
@@ -264,18 +266,13 @@ fn ForumAuth() -> impl IntoView
 
     // listen to new posts
     Effect::new(move |_| {
-        if let Some(msg) = ws.message.get() {
-            match msg {
-                ServerMsg::NewPostMsg(post) => {
-                    set_posts_sig.update(move |posts| {
-                                     posts.push(UserForumPost { post,
-                                                                liked: false,
-                                                                disliked: false })
-                                 });
-                }
-                _ => {}
-            }
-        };
+        if let Some(ServerMsg::NewPostMsg(post)) = ws.message.get() {
+            set_posts_sig.update(move |posts| {
+                             posts.push(UserForumPost { post,
+                                                        liked: false,
+                                                        disliked: false })
+                         });
+        }
     });
 
     // update last seen counter
@@ -283,19 +280,17 @@ fn ForumAuth() -> impl IntoView
         let opt_id = posts_sig.with(|v| v.last().map(|up| up.post.id));
         let y_coord = y.get();
 
-        if visible_forum.get() {
-            if let Some(last_id) = opt_id {
-                if let Some(el) = forum_elem.get_untracked() {
-                    let text_el = textarea_elem.get().unwrap();
-                    let bottom = (el.scroll_height()
-                                  - el.client_height()
-                                  - text_el.client_height()
-                                  - text_el.scroll_height())
-                                 as f64;
-                    if (bottom - y_coord) <= 40.0 {
-                        set_last_seen.update(|ls| *ls = (*ls).max(last_id));
-                    }
-                }
+        if visible_forum.get()
+           && let Some(last_id) = opt_id
+           && let Some(el) = forum_elem.get_untracked()
+        {
+            let text_el = textarea_elem.get().unwrap();
+            let bottom = (el.scroll_height()
+                          - el.client_height()
+                          - text_el.client_height()
+                          - text_el.scroll_height()) as f64;
+            if (bottom - y_coord) <= 40.0 {
+                set_last_seen.update(|ls| *ls = (*ls).max(last_id));
             }
         }
     });
@@ -399,7 +394,7 @@ fn ForumAuth() -> impl IntoView
                                 on_author=Callback::new(move |s: String| {
 
                                     set_message.update(|mes| {
-                                        mes.push_str(&format!("@{}, ", s))
+                                        mes.push_str(&format!("@{s}, "))
                                     });
 
                                     if let Some(el) = textarea_elem.get() {
@@ -638,7 +633,7 @@ fn display_time(dt_utc: DateTime<Utc>, lang_code: &str) -> String
     let m = dt.minute();
 
     if dt.date_naive() == now.date_naive() {
-        format!("{:02}:{:02}", h, m)
+        format!("{h:02}:{m:02}")
     } else if dt.year() == now.year() {
         let month = months[(dt.month() - 1) as usize];
         format!("{} {} {:02}:{:02}", dt.day(), month, h, m)
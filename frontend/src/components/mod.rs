@@ -1,9 +1,21 @@
+mod achievements;
+mod challenges;
 mod deck;
 mod forum;
 mod navbar;
+mod profile_link;
+mod quests;
 mod settings;
+mod tournament_alerts;
+mod wallet;
 
+pub use achievements::{AchievementAlerts, AchievementBadges};
+pub use challenges::{ChallengeForm, Challenges};
 pub use deck::Deck;
 pub use forum::Forum;
 pub use navbar::NavBar;
+pub use profile_link::ProfileLink;
+pub use quests::{QuestAlerts, QuestList};
 pub use settings::Settings;
+pub use tournament_alerts::TournamentAlerts;
+pub use wallet::WalletUpdates;
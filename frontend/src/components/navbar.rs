@@ -13,6 +13,7 @@ pub fn NavBar() -> impl IntoView
     let navctx = expect_context::<NavBarCtx>();
     let (visible_forum, set_visible_forum) = navctx.visible_forum;
     let (new_posts, _) = navctx.new_posts;
+    let (coins, _) = navctx.coins;
 
     let UseColorModeReturn { mode, set_mode, .. } =
         use_color_mode_with_options(UseColorModeOptions::default().initial_value(ColorMode::Dark)
@@ -47,26 +48,24 @@ pub fn NavBar() -> impl IntoView
             class="cluster"
             style="--cluster-gap: 0;"
             >
+            { move || coins.get().map(|coins| view! {
+                <span class="navbar-coins" title=move || tr!("navbar-coins")>
+                    { tr!("navbar-coins-count", {"coins" => coins}) }
+                </span>
+            }) }
             <button
                 class="icon-btn navbar-icon"
                 title=move || tr!("navbar-toggle-theme")
                 aria-label=move || tr!("navbar-toggle-theme")
                 on:click=move |_| next_theme()
             >
-            { move || match mode.get() {
-                ColorMode::Dark => view! {
-                    <svg class="icon" alt=move || tr!("navbar-toggle-theme")>
-                        <use href="/icons.svg#sun"></use>
-                    </svg>
-                }.into_any(),
-
-                ColorMode::Light => view! {
+            { move || {
+                let icon = if mode.get() == ColorMode::Dark { "sun" } else { "moon" };
+                view! {
                     <svg class="icon" alt=move || tr!("navbar-toggle-theme")>
-                        <use href="/icons.svg#moon"></use>
+                        <use href=format!("/icons.svg#{icon}")></use>
                     </svg>
-                }.into_any(),
-
-                _ => view! {}.into_any(),
+                }
             }
             }
             </button>
@@ -0,0 +1,16 @@
+use leptos::prelude::*;
+
+/// Highlighted username that opens the player's profile.
+#[component]
+pub fn ProfileLink(#[prop(into)] username: String,
+                   /// Text shown instead of the bare username, e.g. a forum `@mention`.
+                   #[prop(optional, into)]
+                   label: Option<String>)
+                   -> impl IntoView
+{
+    let href = format!("/users/{}", String::from(js_sys::encode_uri_component(&username)));
+
+    view! {
+        <a class="mention-name" href=href>{label.unwrap_or(username)}</a>
+    }
+}
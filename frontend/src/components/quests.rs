@@ -0,0 +1,120 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_use::core::ConnectionReadyState;
+use shared::{
+    quest::*,
+    rps_game::RpsMove,
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use crate::hooks::{MyToaster, WebsocketContext};
+
+fn quest_label(quest: &QuestInfo) -> String
+{
+    let count = quest.target;
+    match quest.goal {
+        QuestGoal::PlayGames => tr!("quest-play-games", {"count" => count}),
+        QuestGoal::WinGames => tr!("quest-win-games", {"count" => count}),
+        QuestGoal::WinWith(RpsMove::Rock) => tr!("quest-win-with-rock", {"count" => count}),
+        QuestGoal::WinWith(RpsMove::Paper) => tr!("quest-win-with-paper", {"count" => count}),
+        QuestGoal::WinWith(RpsMove::Scissors) => {
+            tr!("quest-win-with-scissors", {"count" => count})
+        }
+        QuestGoal::PostInForum => tr!("quest-post-in-forum", {"count" => count}),
+    }
+}
+
+/// Toast for every quest completed while online.
+#[component]
+pub fn QuestAlerts() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let toaster = MyToaster::new();
+
+    Effect::new(move |_| {
+        let Some(ServerMsg::QuestMsg(QuestEvent::Completed(quest))) = ws.message.get() else {
+            return;
+        };
+
+        toaster.success(&tr!("quest-completed-toast", {"quest" => quest_label(&quest)}));
+    });
+}
+
+/// Current daily and weekly quests with the player's progress, kept up to date.
+#[component]
+pub fn QuestList() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let (quests, set_quests) = signal(Vec::<QuestInfo>::new());
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            let Some(ServerMsg::QuestMsg(event)) = ws.message.get() else {
+                return;
+            };
+
+            match event {
+                QuestEvent::List(list) => set_quests.set(list),
+                QuestEvent::Progress(quest) | QuestEvent::Completed(quest) => {
+                    set_quests.update(|quests| {
+                                  if let Some(q) = quests.iter_mut().find(|q| q.id == quest.id) {
+                                      *q = quest;
+                                  }
+                              });
+                }
+            }
+        }
+    });
+
+    Effect::new(move |_| {
+        if ws.state.get() == ConnectionReadyState::Open {
+            ws.send(ClientMsg::QuestMsg(QuestReq::List));
+        }
+    });
+
+    let section = move |period: QuestPeriod| {
+        let title = move || match period {
+            QuestPeriod::Daily => tr!("quests-daily"),
+            QuestPeriod::Weekly => tr!("quests-weekly"),
+        };
+
+        view! {
+            <h3>{title}</h3>
+            <ul class="stack quest-list">
+                <For
+                each=move || quests.get().into_iter().filter(move |q| q.period == period)
+                key=|q| (q.id, q.progress)
+                children=move |quest: QuestInfo| {
+                    let share = quest.progress as f64 / quest.target.max(1) as f64 * 100.0;
+                    view! {
+                        <li class="quest" class:quest-done=quest.completed>
+                            <span>{ let quest = quest.clone(); move || quest_label(&quest) }</span>
+                            <span
+                            class="quest-bar"
+                            style=format!("--progress: {share:.0}%;")
+                            ></span>
+                            <span style="color: var(--muted);">
+                                { move_tr!("quest-progress", {
+                                    "progress" => quest.progress,
+                                    "target" => quest.target
+                                }) }
+                            </span>
+                        </li>
+                    }
+                }
+                />
+            </ul>
+        }
+    };
+
+    view! {
+        <Show
+        when=move || !quests.with(|q| q.is_empty())
+        fallback=|| view! { <p>{ move || tr!("quests-empty") }</p> }
+        >
+            { section(QuestPeriod::Daily) }
+            { section(QuestPeriod::Weekly) }
+        </Show>
+    }
+}
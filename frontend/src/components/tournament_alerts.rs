@@ -0,0 +1,46 @@
+use leptos::prelude::*;
+use leptos_fluent::tr;
+use leptos_router::hooks::use_navigate;
+use shared::{tournament::TournamentEvent, ws_messages::ServerMsg};
+
+use crate::hooks::{MyToaster, WebsocketContext};
+
+/// Toasts for tournament progress; takes you to the board when your match is ready.
+#[component]
+pub fn TournamentAlerts() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let toaster = MyToaster::new();
+    let navigate = use_navigate();
+
+    Effect::new(move |_| {
+        let Some(ServerMsg::TournamentMsg(event)) = ws.message.get() else {
+            return;
+        };
+
+        match event {
+            TournamentEvent::Started { name, .. } => {
+                toaster.info(&tr!("tournament-started-toast", {"name" => name}));
+            }
+            TournamentEvent::MatchReady { name,
+                                          round,
+                                          opponent,
+                                          .. } => {
+                toaster.success(&tr!("tournament-match-ready-toast", {
+                                    "name" => name,
+                                    "round" => round,
+                                    "opponent" => opponent
+                                }));
+                navigate("/games/rps", Default::default());
+            }
+            TournamentEvent::Finished { name,
+                                        winner: Some(winner),
+                                        .. } => {
+                toaster.info(&tr!("tournament-finished-toast", {"name" => name, "winner" => winner}));
+            }
+            TournamentEvent::Finished { name, .. } => {
+                toaster.info(&tr!("tournament-finished-no-winner", {"name" => name}));
+            }
+        }
+    });
+}
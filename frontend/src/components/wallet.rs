@@ -0,0 +1,26 @@
+use leptos::prelude::*;
+use shared::{auth::UserInfo, wallet::WalletEvent, ws_messages::ServerMsg};
+
+use crate::hooks::{NavBarCtx, WebsocketContext};
+
+/// Keeps the coin balance in the navbar current while logged in.
+#[component]
+pub fn WalletUpdates() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let user_info = expect_context::<UserInfo>();
+    let (_, set_coins) = expect_context::<NavBarCtx>().coins;
+
+    set_coins.set(Some(user_info.coins));
+    on_cleanup(move || set_coins.set(None));
+
+    Effect::new(move |_| {
+        match ws.message.get() {
+            Some(ServerMsg::WalletMsg(WalletEvent::Balance(balance)))
+            | Some(ServerMsg::WalletMsg(WalletEvent::Staked { balance, .. })) => {
+                set_coins.set(Some(balance));
+            }
+            _ => {}
+        }
+    });
+}
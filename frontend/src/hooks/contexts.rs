@@ -8,6 +8,8 @@ pub struct NavBarCtx
 {
     pub visible_forum: (ReadSignal<bool>, WriteSignal<bool>),
     pub new_posts: (ReadSignal<bool>, WriteSignal<bool>),
+    /// Coin balance, known only while logged in.
+    pub coins: (ReadSignal<Option<i64>>, WriteSignal<Option<i64>>),
 }
 
 #[derive(Clone, Copy)]
@@ -0,0 +1,138 @@
+use chrono::Local;
+use leptos::{prelude::*, reactive::spawn_local};
+use leptos_fluent::tr;
+use shared::collusion::*;
+
+use crate::api::{dismiss_collusion_flag, exclude_collusion_flag, fetch_collusion_flags};
+use crate::hooks::MyToaster;
+
+fn error_label(err: CollusionError) -> String
+{
+    match err {
+        CollusionError::DbError => tr!("collusion-error-db"),
+        CollusionError::NotFound => tr!("collusion-error-not-found"),
+        CollusionError::Forbidden => tr!("collusion-error-forbidden"),
+        CollusionError::NetworkError => tr!("collusion-error-network"),
+    }
+}
+
+fn signal_label(signal: CollusionSignal) -> String
+{
+    match signal {
+        CollusionSignal::HeadToHead => tr!("collusion-signal-head-to-head"),
+        CollusionSignal::OneSided => tr!("collusion-signal-one-sided"),
+        CollusionSignal::MovePattern => tr!("collusion-signal-move-pattern"),
+    }
+}
+
+fn status_label(status: FlagStatus) -> String
+{
+    match status {
+        FlagStatus::Open => tr!("collusion-status-open"),
+        FlagStatus::Dismissed => tr!("collusion-status-dismissed"),
+        FlagStatus::Excluded => tr!("collusion-status-excluded"),
+    }
+}
+
+/// Pairs flagged by the collusion analysis, for moderators.
+#[component]
+pub fn CollusionReview() -> impl IntoView
+{
+    let toaster = MyToaster::new();
+    let flags = LocalResource::new(fetch_collusion_flags);
+
+    let (failure, set_failure) = signal(None::<CollusionError>);
+    Effect::new(move |_| {
+        if let Some(err) = failure.get() {
+            toaster.error(&error_label(err));
+        }
+    });
+
+    let review = move |id: i64, exclude: bool| {
+        spawn_local(async move {
+            let res = if exclude {
+                exclude_collusion_flag(id).await
+            } else {
+                dismiss_collusion_flag(id).await
+            };
+            match res {
+                Ok(()) => flags.refetch(),
+                Err(err) => set_failure.set(Some(err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("collusion-title") }</h1>
+
+            { move || match flags.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(Err(err)) => view! {
+                    <p style="color: var(--error);">{error_label(err)}</p>
+                }.into_any(),
+                Some(Ok(list)) if list.is_empty() => view! {
+                    <p>{ tr!("collusion-empty") }</p>
+                }.into_any(),
+                Some(Ok(list)) => view! {
+                    <ul class="collusion-list stack">
+                        { list.into_iter()
+                              .map(|flag| flag_item(flag, review))
+                              .collect::<Vec<_>>() }
+                    </ul>
+                }.into_any(),
+            } }
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+fn flag_item(flag: CollusionFlag,
+             review: impl Fn(i64, bool) + Copy + Send + Sync + 'static)
+             -> impl IntoView
+{
+    let id = flag.id;
+    let open = flag.status == FlagStatus::Open;
+    let excluded = flag.status == FlagStatus::Excluded;
+    let signals = flag.signals
+                      .iter()
+                      .map(|s| signal_label(*s))
+                      .collect::<Vec<_>>()
+                      .join(", ");
+    let [first, second] = flag.players;
+    let status = match flag.reviewed_by {
+        Some(by) => format!("{} · {by}", status_label(flag.status)),
+        None => status_label(flag.status),
+    };
+
+    view! {
+        <li class="collusion-flag stack" class:collusion-open=open>
+            <strong>{format!("{first} – {second}")}</strong>
+            <span>
+                { tr!("collusion-games", {"count" => flag.games}) }
+                {format!(" · {} : {}", flag.wins[0], flag.wins[1])}
+                {format!(" · {:.0}% · χ² {:.1}", flag.head_to_head * 100.0, flag.move_chi2)}
+            </span>
+            <span>{signals}</span>
+            <span style="color: var(--muted);">
+                {format!("{} · {status}",
+                         flag.flagged_at.with_timezone(&Local).format("%d.%m.%Y %H:%M"))}
+            </span>
+            <div class="cluster">
+                <Show when=move || open>
+                    <button class="secondary" on:click=move |_| review(id, false)>
+                        { move || tr!("collusion-dismiss") }
+                    </button>
+                </Show>
+                <Show when=move || !excluded>
+                    <button on:click=move |_| review(id, true)>
+                        { move || tr!("collusion-exclude") }
+                    </button>
+                </Show>
+            </div>
+        </li>
+    }
+}
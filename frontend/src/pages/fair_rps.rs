@@ -0,0 +1,320 @@
+use chrono::{Local, Utc};
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_router::hooks::use_params_map;
+use leptos_use::{core::ConnectionReadyState, use_interval_fn};
+use shared::{
+    auth::UserInfo,
+    fair_rps::{commitment, FairRpsInfo, FairRpsKind, FairRpsMove, FairRpsReq, FairRpsState},
+    game::{ConnectionEvent, GameError, GameResult},
+    rps_game::RpsMove,
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use super::rps_game::mv_into_view;
+use crate::api::fetch_fair_game;
+use crate::hooks::{MyToaster, WebsocketContext};
+
+/// Random hex nonce that keeps the commitment from being brute forced over the three moves.
+fn new_nonce() -> String
+{
+    (0..4).map(|_| format!("{:08x}", (js_sys::Math::random() * u32::MAX as f64) as u32))
+          .collect()
+}
+
+#[component]
+pub fn FairRpsGame() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let user_info = expect_context::<UserInfo>();
+    let toaster = MyToaster::new();
+
+    let (curr_game, set_curr_game) = signal::<Option<FairRpsState>>(None);
+    // move and nonce behind our commitment, kept until the reveal phase
+    let (secret, set_secret) = signal::<Option<(RpsMove, String)>>(None);
+
+    let (now, set_now) = signal(Utc::now());
+    use_interval_fn(move || set_now.set(Utc::now()), 1_000);
+
+    let me = {
+        let username = user_info.username.clone();
+        move |players: &[String; 2]| if players[0] == username { 0 } else { 1 }
+    };
+
+    Effect::new({
+        let ws = ws.clone();
+        let me = me.clone();
+
+        move |_| {
+            let Some(msg) = ws.message.get() else {
+                return;
+            };
+
+            match msg {
+                ServerMsg::GameMsg(envelope) => {
+                    let Some(state) = envelope.open_state::<FairRpsKind>() else {
+                        return;
+                    };
+                    if let FairRpsState::Reveal { players, revealed, .. } = &state
+                       && !revealed[me(players)]
+                       && let Some((mv, nonce)) = secret.get_untracked()
+                    {
+                        let reveal = FairRpsMove::Reveal { mv, nonce };
+                        ws.send(ClientMsg::game::<FairRpsKind>(FairRpsReq::Submit(reveal)));
+                    }
+                    set_curr_game.set(Some(state));
+                }
+                ServerMsg::ConnectionMsg(ConnectionEvent::OpponentAway { grace_secs }) => {
+                    toaster.info(&tr!("rps-opponent-away", {"seconds" => grace_secs}));
+                }
+                ServerMsg::ConnectionMsg(ConnectionEvent::OpponentBack) => {
+                    toaster.success(&tr!("rps-opponent-back"));
+                }
+                ServerMsg::GameErrorMsg(GameError::Disconnected) => {
+                    set_curr_game.set(None);
+                    set_secret.set(None);
+                    toaster.error(&tr!("rps-opponent-disconnected"));
+                    ws.send(ClientMsg::game::<FairRpsKind>(FairRpsReq::Start(())));
+                }
+                ServerMsg::GameErrorMsg(GameError::TimedOut) => {
+                    set_curr_game.set(None);
+                    set_secret.set(None);
+                    toaster.error(&tr!("rps-timed-out"));
+                    ws.send(ClientMsg::game::<FairRpsKind>(FairRpsReq::Start(())));
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<FairRpsKind>(FairRpsReq::Start(())));
+            }
+        }
+    });
+
+    let commit = {
+        let ws = ws.clone();
+        move |mv: RpsMove| {
+            let nonce = new_nonce();
+            let hash = commitment(mv, &nonce);
+            set_secret.set(Some((mv, nonce)));
+            ws.send(ClientMsg::game::<FairRpsKind>(FairRpsReq::Submit(FairRpsMove::Commit(hash))));
+        }
+    };
+
+    let next_btn = {
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<FairRpsKind>(FairRpsReq::Start(())));
+                set_curr_game.set(None);
+                set_secret.set(None);
+            }
+        }
+    };
+
+    let countdown = move |deadline: chrono::DateTime<Utc>| {
+        move || tr!("rps-time-left", {"seconds" => (deadline - now.get()).num_seconds().max(0)})
+    };
+
+    view! {
+        <div class="stack fill-page card">
+        <h1>{ move || tr!("fair-rps-title") }</h1>
+        <p style="color: var(--muted);">{ move || tr!("fair-rps-explainer") }</p>
+
+        { move || match curr_game.get() {
+            None => view! {
+                <p>{ tr!("rps-waiting") }</p>
+                <div class="loading-spinner" style="margin-top: auto; margin-bottom: auto;"></div>
+            }.into_any(),
+
+            Some(FairRpsState::Commit { players, ratings, committed, deadline }) => {
+                let i = me(&players);
+                let commit = commit.clone();
+
+                view! {
+                    <h3>{ tr!("rps-playing-against-label") }{" "}
+                        <span class="mention-name">{players[1 - i].clone()}</span>
+                        { ratings[1 - i].map(|rating| view! {
+                            <span style="color: var(--muted);">{format!(" ({rating})")}</span>
+                        }) }
+                        <span style="color: var(--muted);">
+                            { committed[1 - i].then(|| format!(" {}", tr!("fair-rps-opponent-committed"))) }
+                        </span>
+                    </h3>
+                    <p>{ countdown(deadline) }</p>
+                    <div
+                    class="cluster"
+                    class:el-hide=committed[i]
+                    style="--cluster-justify: center; margin-top: auto; margin-bottom: auto;"
+                    >
+                        { RpsMove::ALL.into_iter()
+                                      .map(|mv| {
+                                          let commit = commit.clone();
+                                          view! {
+                                              <button class="icon-btn" on:click=move |_| commit(mv)>
+                                                  {mv_into_view(mv, 4)}
+                                              </button>
+                                          }
+                                      })
+                                      .collect::<Vec<_>>() }
+                    </div>
+                    <p class:el-hide=!committed[i]>{ tr!("fair-rps-committed") }</p>
+                }.into_any()
+            }
+
+            Some(FairRpsState::Reveal { players, commitments, revealed, deadline, .. }) => {
+                let i = me(&players);
+
+                view! {
+                    <h3>{ tr!("fair-rps-revealing") }</h3>
+                    <p>{ countdown(deadline) }</p>
+                    <p class="fair-hash">
+                        <span class="mention-name">{players[1 - i].clone()}</span>{": "}
+                        {commitments[1 - i].clone()}
+                        { revealed[1 - i].then(|| format!(" {}", tr!("fair-rps-opponent-revealed"))) }
+                    </p>
+                    <p class="fair-hash">{ tr!("fair-rps-your-commitment") }{" "}{commitments[i].clone()}</p>
+                }.into_any()
+            }
+
+            Some(FairRpsState::Finished(mut info)) => {
+                if me(&info.players) == 1 {
+                    info.reverse();
+                }
+                let res = info.resolve();
+                let [_, opp_ok] = info.verify();
+
+                let result_text = match res {
+                    GameResult::Win => tr!("rps-result-win"),
+                    GameResult::Defeat => tr!("rps-result-defeat"),
+                    GameResult::Draw => tr!("rps-result-draw"),
+                };
+                let forfeit_text = match info.forfeited {
+                    [true, _] => Some(tr!("rps-you-forfeited")),
+                    [_, true] => Some(tr!("rps-opponent-forfeited", {"username" => info.players[1].clone()})),
+                    _ => None,
+                };
+
+                view! {
+                    <h3 style=match res {
+                        GameResult::Win => "color: var(--success);",
+                        GameResult::Defeat => "color: var(--error);",
+                        GameResult::Draw => "",
+                    }>
+                        { move_tr!("rps-finished", {"result" => result_text.clone()}) }
+                    </h3>
+                    { forfeit_text.map(|text| view! { <p style="color: var(--muted);">{text}</p> }) }
+                    { info.moves[1].map(|mv| view! {
+                        <p><span class="mention-name">{info.players[1].clone()}</span>{" "}
+                        { tr!("rps-opponent-played-label") }{" "}{mv_into_view(mv, 3)}</p>
+                    }) }
+                    { info.moves[0].map(|mv| view! {
+                        <p>{ tr!("rps-you-played") }{" "}{mv_into_view(mv, 3)}</p>
+                    }) }
+                    <div class="stack fair-proof" class:el-hide=info.moves[1].is_none()>
+                        <p class="fair-hash">{ tr!("fair-rps-commitment") }{" "}{info.commitments[1].clone()}</p>
+                        <p class="fair-hash">{ tr!("fair-rps-nonce") }{" "}{info.nonces[1].clone()}</p>
+                        <p style=if opp_ok { "color: var(--success);" } else { "color: var(--error);" }>
+                            { if opp_ok { tr!("fair-rps-verified") } else { tr!("fair-rps-mismatch") } }
+                        </p>
+                    </div>
+                    { info.id.map(|id| view! {
+                        <a href=format!("/games/rps-fair/{id}")>{ tr!("fair-rps-public-record") }</a>
+                    }) }
+                }.into_any()
+            }
+        } }
+
+            <div class="stack" style="margin-top: auto; --stack-gap: var(--s0);">
+            <button
+            class:el-hide=move || !curr_game.get().is_some_and(|g| matches!(g, FairRpsState::Finished(_)))
+            on:click=next_btn>
+                { move || tr!("rps-next-game") }
+            </button>
+            <a href = "/games" class="button secondary">
+                { move || tr!("rps-other-games") }
+            </a>
+            </div>
+        </div>
+    }
+}
+
+/// Public record of a recorded fair game, with both proofs checked in the browser.
+#[component]
+pub fn FairRpsVerify() -> impl IntoView
+{
+    let params = use_params_map();
+    let id = move || params.read().get("id").and_then(|id| id.parse::<i64>().ok());
+
+    let record = LocalResource::new(move || {
+        let id = id();
+        async move {
+            match id {
+                Some(id) => fetch_fair_game(id).await,
+                None => None,
+            }
+        }
+    });
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("fair-rps-record-title") }</h1>
+
+            { move || match record.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(None) => view! {
+                    <p style="color: var(--error);">{ tr!("fair-rps-record-not-found") }</p>
+                }.into_any(),
+                Some(Some(record)) => view! {
+                    <p style="color: var(--muted);">
+                        {format!("#{} · {}",
+                                 record.id,
+                                 record.created_at.with_timezone(&Local).format("%d.%m.%Y %H:%M"))}
+                    </p>
+                    {proof(record.info)}
+                }.into_any(),
+            } }
+
+            <a href = "/games/rps-fair" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+fn proof(info: FairRpsInfo) -> impl IntoView
+{
+    let verified = info.verify();
+
+    (0..2).map(|i| {
+              let ok = verified[i];
+              view! {
+                  <div class="stack fair-proof">
+                      <h3>{info.players[i].clone()}</h3>
+                      { info.moves[i].map(|mv| mv_into_view(mv, 3)) }
+                      { info.forfeited[i].then(|| view! {
+                          <p style="color: var(--muted);">{ tr!("fair-rps-record-forfeited") }</p>
+                      }) }
+                      <p class="fair-hash">
+                          { tr!("fair-rps-record-commitment") }{" "}{info.commitments[i].clone()}
+                      </p>
+                      <p class="fair-hash">
+                          { tr!("fair-rps-record-nonce") }{" "}{info.nonces[i].clone()}
+                      </p>
+                      <p style=if ok { "color: var(--success);" } else { "color: var(--error);" }>
+                          { if ok {
+                              tr!("fair-rps-record-verified")
+                          } else {
+                              tr!("fair-rps-record-unverified")
+                          } }
+                      </p>
+                  </div>
+              }
+          })
+          .collect::<Vec<_>>()
+}
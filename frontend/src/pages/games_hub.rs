@@ -1,9 +1,14 @@
 use leptos::prelude::*;
 use leptos_fluent::tr;
 
+use crate::components::ChallengeForm;
+use crate::hooks::SettingsCtx;
+
 #[component]
 pub fn GamesHub() -> impl IntoView
 {
+    let (admin, _) = expect_context::<SettingsCtx>().admin_control;
+
     view! {
         <div class="stack fill-page card">
             <h1>{ move || tr!("games-hub-title") }</h1>
@@ -13,6 +18,39 @@ pub fn GamesHub() -> impl IntoView
             >
                 { move || tr!("games-hub-rps") }
             </a>
+            <a href = "/games/rps-fair" class="button secondary">
+                { move || tr!("games-hub-fair-rps") }
+            </a>
+            <a href = "/games/tic-tac-toe" class="button secondary">
+                { move || tr!("games-hub-tic-tac-toe") }
+            </a>
+            <a href = "/games/royale" class="button secondary">
+                { move || tr!("games-hub-royale") }
+            </a>
+            <a href = "/games/rooms" class="button secondary">
+                { move || tr!("games-hub-rooms") }
+            </a>
+            <a href = "/games/spectate" class="button secondary">
+                { move || tr!("games-hub-spectate") }
+            </a>
+            <a href = "/tournaments" class="button secondary">
+                { move || tr!("games-hub-tournaments") }
+            </a>
+            <a href = "/games/leaderboard" class="button secondary">
+                { move || tr!("games-hub-leaderboard") }
+            </a>
+            <a href = "/games/seasons" class="button secondary">
+                { move || tr!("games-hub-seasons") }
+            </a>
+            <Show when=move || admin.get()>
+                <a href = "/games/collusion" class="button secondary">
+                    { move || tr!("games-hub-collusion") }
+                </a>
+            </Show>
+            <a href = "/games/history" class="button secondary">
+                { move || tr!("games-hub-history") }
+            </a>
+            <ChallengeForm />
             <a href = "/" class="button secondary" style ="margin-block-start: var(--s1); margin-top: auto;">
                 { move || tr!("games-hub-home") }
             </a>
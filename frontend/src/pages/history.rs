@@ -0,0 +1,142 @@
+use chrono::Local;
+use leptos::{prelude::*, reactive::spawn_local};
+use leptos_fluent::tr;
+use shared::{
+    game::GameResult,
+    history::{HistoryEntry, HistoryQuery},
+};
+
+use super::rps_game::mv_into_view;
+use crate::api::fetch_history;
+use crate::components::ProfileLink;
+
+#[component]
+pub fn History() -> impl IntoView
+{
+    let (entries, set_entries) = signal(Vec::<HistoryEntry>::new());
+    let (next, set_next) = signal::<Option<i64>>(None);
+    let (loading, set_loading) = signal(false);
+    let (failed, set_failed) = signal(false);
+
+    let (opponent, set_opponent) = signal(String::new());
+    let (result, set_result) = signal::<Option<GameResult>>(None);
+
+    // `before: None` starts over with the current filters, a cursor appends the next page.
+    let load = move |before: Option<i64>| {
+        let opponent = opponent.get_untracked().trim().to_string();
+        let query = HistoryQuery { username: None,
+                                   opponent: (!opponent.is_empty()).then_some(opponent),
+                                   result: result.get_untracked(),
+                                   before };
+
+        set_loading.set(true);
+        spawn_local(async move {
+            match fetch_history(query).await {
+                Some(page) => {
+                    if before.is_some() {
+                        set_entries.update(|e| e.extend(page.entries));
+                    } else {
+                        set_entries.set(page.entries);
+                    }
+                    set_next.set(page.next);
+                    set_failed.set(false);
+                }
+                None => set_failed.set(true),
+            }
+            set_loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        result.track();
+        load(None);
+    });
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        load(None);
+    };
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("history-title") }</h1>
+
+            <form class="cluster" style="--cluster-justify: center;" on:submit=on_submit>
+                <input
+                type="text"
+                placeholder=move || tr!("history-opponent")
+                prop:value=opponent
+                on:input=move |ev| set_opponent.set(event_target_value(&ev))
+                />
+                <select on:change=move |ev| {
+                    let value = match event_target_value(&ev).as_str() {
+                        "win" => Some(GameResult::Win),
+                        "defeat" => Some(GameResult::Defeat),
+                        "draw" => Some(GameResult::Draw),
+                        _ => None,
+                    };
+                    set_result.set(value);
+                }>
+                    <option value="">{ move || tr!("history-any-result") }</option>
+                    <option value="win">{ move || tr!("rps-result-win") }</option>
+                    <option value="defeat">{ move || tr!("rps-result-defeat") }</option>
+                    <option value="draw">{ move || tr!("rps-result-draw") }</option>
+                </select>
+                <button type="submit">{ move || tr!("history-filter") }</button>
+            </form>
+
+            <Show when=move || failed.get()>
+                <p style="color: var(--error);">{ move || tr!("history-error") }</p>
+            </Show>
+            <Show when=move || !failed.get() && !loading.get() && entries.with(|e| e.is_empty())>
+                <p>{ move || tr!("history-empty") }</p>
+            </Show>
+
+            <ul class="stack history-list">
+                <For
+                each=move || entries.get()
+                key=|entry| entry.id
+                children=move |entry| view! { <HistoryRow entry=entry /> }
+                />
+            </ul>
+
+            <Show when=move || loading.get()>
+                <div class="loading-spinner"></div>
+            </Show>
+            <Show when=move || next.get().is_some() && !loading.get()>
+                <button class="secondary" on:click=move |_| load(next.get_untracked())>
+                    { move || tr!("history-more") }
+                </button>
+            </Show>
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+#[component]
+pub(crate) fn HistoryRow(entry: HistoryEntry) -> impl IntoView
+{
+    let [mine, theirs] = entry.moves;
+    let (result, style) = match entry.result {
+        GameResult::Win => (tr!("rps-result-win"), "color: var(--success);"),
+        GameResult::Defeat => (tr!("rps-result-defeat"), "color: var(--error);"),
+        GameResult::Draw => (tr!("rps-result-draw"), ""),
+    };
+
+    view! {
+        <li class="history-row">
+            <span style="color: var(--muted);">
+                {entry.played_at.with_timezone(&Local).format("%d.%m.%Y %H:%M").to_string()}
+            </span>
+            <ProfileLink username=entry.opponent />
+            <span>{mv_into_view(mine, 2)}{" – "}{mv_into_view(theirs, 2)}</span>
+            <span style=style>{result}</span>
+            <Show when=move || !entry.ranked>
+                <span style="color: var(--muted);">{ move || tr!("history-unranked") }</span>
+            </Show>
+        </li>
+    }
+}
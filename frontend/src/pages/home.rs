@@ -7,6 +7,7 @@ use leptos_use::use_interval_fn;
 use shared::auth::UserInfo;
 use shared::ws_messages::*;
 
+use crate::components::QuestList;
 use crate::hooks::WebsocketContext;
 
 #[component]
@@ -17,11 +18,9 @@ pub fn AuthHome() -> impl IntoView
     let (online_count, set_online_count) = signal::<u32>(0);
 
     Effect::new(move |_| {
-        if let Some(msg) = ws.message.get() {
-            if let ServerMsg::StatsMsg(stats_info) = msg {
-                set_online_count.set(stats_info.online);
-            }
-        };
+        if let Some(ServerMsg::StatsMsg(stats_info)) = ws.message.get() {
+            set_online_count.set(stats_info.online);
+        }
     });
 
     Effect::new({
@@ -53,6 +52,13 @@ pub fn AuthHome() -> impl IntoView
                              .with_timezone(&Local)
                              .format("%d.%m.%Y %H:%M")
                              .to_string()}) }</p>
+        <p>{ move_tr!("auth-home-rating", {
+            "rating" => user_info.rating.rating.round() as i32,
+            "deviation" => user_info.rating.deviation.round() as i32
+        }) }</p>
+
+        <h2>{ move || tr!("quests-title") }</h2>
+        <QuestList />
 
         <a href = "/games" class="button" style ="margin-block-start: var(--s1); margin-top: auto;">
             { move || tr!("auth-home-play") }
@@ -0,0 +1,145 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use shared::{auth::UserInfo, leaderboard::*};
+
+use crate::api::fetch_leaderboard;
+
+#[component]
+pub fn Leaderboard() -> impl IntoView
+{
+    let user_info = expect_context::<UserInfo>();
+    let me = user_info.username;
+
+    let (sort, set_sort) = signal(LeaderboardSort::Wins);
+    let (window, set_window) = signal(LeaderboardWindow::AllTime);
+    let (page, set_page) = signal(0u32);
+
+    let board = LocalResource::new(move || {
+        fetch_leaderboard(LeaderboardQuery { sort: sort.get(),
+                                             window: window.get(),
+                                             page: page.get(),
+                                             min_games: None })
+    });
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("leaderboard-title") }</h1>
+
+            <div class="cluster" style="--cluster-justify: center;">
+                <select on:change=move |ev| {
+                    let value = match event_target_value(&ev).as_str() {
+                        "win_rate" => LeaderboardSort::WinRate,
+                        "rating" => LeaderboardSort::Rating,
+                        _ => LeaderboardSort::Wins,
+                    };
+                    set_page.set(0);
+                    set_sort.set(value);
+                }>
+                    <option value="wins">{ move || tr!("leaderboard-sort-wins") }</option>
+                    <option value="win_rate">{ move || tr!("leaderboard-sort-win-rate") }</option>
+                    <option value="rating">{ move || tr!("leaderboard-sort-rating") }</option>
+                </select>
+                <select on:change=move |ev| {
+                    let value = match event_target_value(&ev).as_str() {
+                        "day" => LeaderboardWindow::Day,
+                        "week" => LeaderboardWindow::Week,
+                        "month" => LeaderboardWindow::Month,
+                        _ => LeaderboardWindow::AllTime,
+                    };
+                    set_page.set(0);
+                    set_window.set(value);
+                }>
+                    <option value="all_time">{ move || tr!("leaderboard-window-all-time") }</option>
+                    <option value="month">{ move || tr!("leaderboard-window-month") }</option>
+                    <option value="week">{ move || tr!("leaderboard-window-week") }</option>
+                    <option value="day">{ move || tr!("leaderboard-window-day") }</option>
+                </select>
+            </div>
+
+            { move || match board.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(Err(_)) => view! {
+                    <p style="color: var(--error);">{ tr!("leaderboard-error") }</p>
+                }.into_any(),
+                Some(Ok(board)) => {
+                    let me = me.clone();
+                    let on_page = board.entries.iter().any(|e| e.username == me);
+                    let my_row = board.me.filter(|_| !on_page);
+                    let pages = board.pages;
+                    let min_games = board.min_games;
+                    let entries = board.entries;
+                    let empty = entries.is_empty();
+
+                    view! {
+                        <Show when=move || sort.get() == LeaderboardSort::WinRate>
+                            <p style="color: var(--muted);">
+                                { move_tr!("leaderboard-min-games", {"count" => min_games}) }
+                            </p>
+                        </Show>
+                        <Show
+                        when=move || !empty
+                        fallback=|| view! { <p>{ move || tr!("leaderboard-empty") }</p> }
+                        >
+                            <table class="standings leaderboard">
+                                <thead>
+                                    <tr>
+                                        <th>"#"</th>
+                                        <th>{ move || tr!("leaderboard-col-player") }</th>
+                                        <th>{ move || tr!("leaderboard-col-record") }</th>
+                                        <th>{ move || tr!("leaderboard-col-win-rate") }</th>
+                                        <th>{ move || tr!("leaderboard-col-rating") }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    { entries.clone()
+                                             .into_iter()
+                                             .map(|entry| {
+                                                 let mine = entry.username == me;
+                                                 entry_row(entry, mine)
+                                             })
+                                             .collect::<Vec<_>>() }
+                                    { my_row.clone().map(|entry| entry_row(entry, true)) }
+                                </tbody>
+                            </table>
+                        </Show>
+                        <div class="cluster" style="--cluster-justify: center;">
+                            <button
+                            class="secondary"
+                            disabled=move || page.get() == 0
+                            on:click=move |_| set_page.update(|p| *p = p.saturating_sub(1))
+                            >
+                                { move || tr!("leaderboard-prev") }
+                            </button>
+                            <span>{ move || format!("{} / {}", page.get() + 1, pages.max(1)) }</span>
+                            <button
+                            class="secondary"
+                            disabled=move || page.get() + 1 >= pages
+                            on:click=move |_| set_page.update(|p| *p += 1)
+                            >
+                                { move || tr!("leaderboard-next") }
+                            </button>
+                        </div>
+                    }.into_any()
+                }
+            } }
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+fn entry_row(entry: LeaderboardEntry, mine: bool) -> impl IntoView
+{
+    let win_rate = entry.win_rate();
+    view! {
+        <tr class:leaderboard-me=mine>
+            <td>{entry.rank}</td>
+            <td>{entry.username}</td>
+            <td>{format!("{} / {} / {}", entry.wins, entry.draws, entry.losses)}</td>
+            <td>{format!("{:.1}%", win_rate * 100.0)}</td>
+            <td>{format!("{:.0}", entry.rating)}</td>
+        </tr>
+    }
+}
@@ -1,12 +1,36 @@
+mod collusion;
+mod fair_rps;
 mod games_hub;
+mod history;
 mod home;
 mod info;
+mod leaderboard;
 mod login;
+mod profile;
 mod register;
+mod rooms;
+mod royale;
 mod rps_game;
+mod seasons;
+mod spectate;
+mod tic_tac_toe;
+mod tournament;
+mod tournaments;
+pub use collusion::CollusionReview;
+pub use fair_rps::{FairRpsGame, FairRpsVerify};
 pub use games_hub::GamesHub;
+pub use history::History;
 pub use home::AuthHome;
 pub use info::{About, Contact};
+pub use leaderboard::Leaderboard;
 pub use login::Login;
+pub use profile::Profile;
 pub use register::Register;
+pub use rooms::Rooms;
+pub use royale::Royale;
 pub use rps_game::RpsGame;
+pub use seasons::Seasons;
+pub use spectate::Spectate;
+pub use tic_tac_toe::TicTacToeGame;
+pub use tournament::TournamentPage;
+pub use tournaments::Tournaments;
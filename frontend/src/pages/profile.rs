@@ -0,0 +1,254 @@
+use chrono::Local;
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_router::hooks::use_params_map;
+use shared::{
+    auth::UserInfo,
+    profile::{MoveAnalytics, PlayerProfile},
+    rps_game::RpsMove,
+};
+
+use super::{history::HistoryRow, rps_game::mv_into_view};
+use crate::api::{fetch_move_analytics, fetch_profile};
+use crate::components::AchievementBadges;
+
+#[component]
+pub fn Profile() -> impl IntoView
+{
+    let params = use_params_map();
+    let username = move || params.read().get("username").unwrap_or_default();
+
+    let profile = LocalResource::new(move || {
+        let username = username();
+        async move { fetch_profile(&username).await }
+    });
+
+    view! {
+        <div class="stack fill-page card">
+            { move || match profile.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(None) => view! {
+                    <p style="color: var(--error);">{ tr!("profile-not-found") }</p>
+                }.into_any(),
+                Some(Some(profile)) => view! { <ProfileView profile=profile /> }.into_any(),
+            } }
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+#[component]
+fn ProfileView(profile: PlayerProfile) -> impl IntoView
+{
+    let games = profile.games();
+    let favourite = profile.favourite_move();
+    let played: u32 = profile.move_counts.iter().sum();
+    let user = profile.user;
+    let own = user.username == expect_context::<UserInfo>().username;
+
+    view! {
+        <h1>{user.username}</h1>
+        <p style="color: var(--muted);">
+            { move_tr!("profile-joined", {
+                "date" => user.created_at.with_timezone(&Local).format("%d.%m.%Y").to_string()
+            }) }
+            { (user.role != "user").then(|| format!(" · {}", user.role)) }
+        </p>
+        <p>{ move_tr!("auth-home-rating", {
+            "rating" => user.rating.rating.round() as i32,
+            "deviation" => user.rating.deviation.round() as i32
+        }) }</p>
+
+        <dl class="profile-stats">
+            <dt>{ move || tr!("profile-games") }</dt>
+            <dd>{games}</dd>
+            <dt>{ move || tr!("profile-record") }</dt>
+            <dd>{format!("{} / {} / {}", profile.wins, profile.draws, profile.losses)}</dd>
+            <dt>{ move || tr!("profile-streak") }</dt>
+            <dd>{ move_tr!("profile-streak-value", {
+                "current" => profile.current_streak,
+                "best" => profile.best_streak
+            }) }</dd>
+            <dt>{ move || tr!("profile-favourite-move") }</dt>
+            <dd>{ match favourite {
+                Some(mv) => mv_into_view(mv, 2),
+                None => "—".into_any(),
+            } }</dd>
+            <dt>{ move || tr!("profile-forum-posts") }</dt>
+            <dd>{profile.forum_posts}</dd>
+        </dl>
+
+        <h3>{ move || tr!("profile-achievements") }</h3>
+        <AchievementBadges achievements=profile.achievements.clone() />
+
+        <h3>{ move || tr!("profile-moves") }</h3>
+        <div class="stack move-bars">
+            { RpsMove::ALL.into_iter()
+                          .zip(profile.move_counts)
+                          .map(|(mv, count)| {
+                              let share = if played == 0 {
+                                  0.0
+                              } else {
+                                  count as f64 / played as f64 * 100.0
+                              };
+                              view! {
+                                  <div class="move-bar">
+                                      {mv_into_view(mv, 2)}
+                                      <span class="move-bar-fill" style=format!("inline-size: calc({share:.0}% / 2);")></span>
+                                      <span>{format!("{share:.0}% ({count})")}</span>
+                                  </div>
+                              }
+                          })
+                          .collect::<Vec<_>>() }
+        </div>
+
+        { own.then(|| view! { <MoveHabits /> }) }
+
+        <h3>{ move || tr!("profile-recent") }</h3>
+        <Show
+        when={
+            let empty = profile.recent.is_empty();
+            move || !empty
+        }
+        fallback=|| view! { <p>{ move || tr!("history-empty") }</p> }
+        >
+            <ul class="stack history-list">
+                { profile.recent
+                         .clone()
+                         .into_iter()
+                         .map(|entry| view! { <HistoryRow entry=entry /> })
+                         .collect::<Vec<_>>() }
+            </ul>
+        </Show>
+    }
+}
+
+/// Share of `count` in `total` as a whole percentage.
+fn percent(count: u32, total: u32) -> u32
+{
+    if total == 0 {
+        0
+    } else {
+        (count as f64 / total as f64 * 100.0).round() as u32
+    }
+}
+
+/// One bar split between the moves, in `RpsMove::ALL` order.
+fn move_split(counts: [u32; 3]) -> impl IntoView
+{
+    let total = counts.iter().sum();
+    view! {
+        <div class="habit-split">
+            { RpsMove::ALL.into_iter()
+                          .zip(counts)
+                          .enumerate()
+                          .filter(|(_, (_, count))| *count > 0)
+                          .map(|(i, (mv, count))| view! {
+                              <span class=format!("habit-seg habit-seg-{i}") style=format!("flex-grow: {count};")>
+                                  {mv_into_view(mv, 2)}
+                                  {format!("{}%", percent(count, total))}
+                              </span>
+                          })
+                          .collect::<Vec<_>>() }
+        </div>
+    }
+}
+
+/// The player's own move habits, only shown to them.
+#[component]
+fn MoveHabits() -> impl IntoView
+{
+    let analytics = LocalResource::new(fetch_move_analytics);
+
+    view! {
+        <h3>{ move || tr!("profile-habits") }</h3>
+        { move || match analytics.get() {
+            None => view! { <div class="loading-spinner"></div> }.into_any(),
+            Some(None) => view! {
+                <p style="color: var(--error);">{ tr!("profile-habits-failed") }</p>
+            }.into_any(),
+            Some(Some(analytics)) if analytics.rounds == 0 => view! {
+                <p>{ tr!("profile-habits-empty") }</p>
+            }.into_any(),
+            Some(Some(analytics)) => view! { <HabitsView analytics=analytics /> }.into_any(),
+        } }
+    }
+}
+
+#[component]
+fn HabitsView(analytics: MoveAnalytics) -> impl IntoView
+{
+    let predictability = (analytics.predictability() * 100.0).round() as u32;
+    let entropy = format!("{:.2}", analytics.entropy);
+    let [wins, draws, losses] = analytics.predictor;
+    let exploitable = percent(wins, analytics.rounds) > percent(losses, analytics.rounds);
+
+    let after_result = analytics.after_result
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, counts)| {
+                                    let label = move || match i {
+                                        0 => tr!("profile-habits-after-win"),
+                                        1 => tr!("profile-habits-after-draw"),
+                                        _ => tr!("profile-habits-after-loss"),
+                                    };
+                                    view! {
+                                        <dt>{label}</dt>
+                                        <dd>{ if counts.iter().sum::<u32>() == 0 {
+                                            "—".into_any()
+                                        } else {
+                                            move_split(counts).into_any()
+                                        } }</dd>
+                                    }
+                                })
+                                .collect::<Vec<_>>();
+
+    view! {
+        <p style="color: var(--muted);">
+            { move_tr!("profile-habits-rounds", {"count" => analytics.rounds}) }
+        </p>
+
+        <dl class="habit-chart">
+            <dt>{ move || tr!("profile-habits-overall") }</dt>
+            <dd>{move_split(analytics.move_counts)}</dd>
+            {after_result}
+        </dl>
+
+        <h4>{ move || tr!("profile-habits-predictability") }</h4>
+        <div class="move-bar">
+            <span class="move-bar-fill" style=format!("inline-size: calc({predictability}% / 2);")></span>
+            <span>{format!("{predictability}%")}</span>
+        </div>
+        <p style="color: var(--muted);">
+            { move_tr!("profile-habits-entropy", {"bits" => entropy.clone()}) }
+        </p>
+
+        <h4>{ move || tr!("profile-habits-predictor") }</h4>
+        <div class="habit-split">
+            { [wins, draws, losses].into_iter()
+                                   .enumerate()
+                                   .filter(|(_, count)| *count > 0)
+                                   .map(|(i, count)| view! {
+                                       <span class=format!("habit-seg habit-seg-{i}") style=format!("flex-grow: {count};")>
+                                           {format!("{}%", percent(count, analytics.rounds))}
+                                       </span>
+                                   })
+                                   .collect::<Vec<_>>() }
+        </div>
+        <p>{ move_tr!("profile-habits-predictor-record", {
+            "wins" => wins,
+            "draws" => draws,
+            "losses" => losses
+        }) }</p>
+        <p style="color: var(--muted);">
+            { move || if exploitable {
+                tr!("profile-habits-exploitable")
+            } else {
+                tr!("profile-habits-unreadable")
+            } }
+        </p>
+    }
+}
@@ -0,0 +1,287 @@
+use chrono::Local;
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_router::hooks::use_navigate;
+use leptos_use::core::ConnectionReadyState;
+use shared::{
+    auth::UserInfo,
+    room::*,
+    rps_game::{BestOf, RpsGameReq, RpsKind},
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use crate::components::ProfileLink;
+use crate::hooks::{MyToaster, WebsocketContext};
+
+fn error_label(err: RoomError) -> String
+{
+    match err {
+        RoomError::NotFound => tr!("room-error-not-found"),
+        RoomError::WrongPassword => tr!("room-error-wrong-password"),
+        RoomError::InvalidName => tr!("room-error-invalid-name"),
+        RoomError::NameTaken => tr!("room-error-name-taken"),
+        RoomError::DbError => tr!("room-error-db"),
+    }
+}
+
+#[component]
+pub fn Rooms() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let user_info = expect_context::<UserInfo>();
+    let toaster = MyToaster::new();
+    let navigate = use_navigate();
+    let me = user_info.username;
+
+    let (rooms, set_rooms) = signal(Vec::<RoomSummary<BestOf>>::new());
+    let (room, set_room) = signal::<Option<RoomState<BestOf>>>(None);
+    let (password, set_password) = signal(String::new());
+
+    Effect::new({
+        let ws = ws.clone();
+        let me = me.clone();
+
+        move |_| {
+            let Some(ServerMsg::RoomMsg(envelope)) = ws.message.get() else {
+                return;
+            };
+            let Some(event) = envelope.open_room::<RpsKind>() else {
+                return;
+            };
+
+            match event {
+                RoomEvent::List(list) => set_rooms.set(list),
+                RoomEvent::State(state) => {
+                    // our turn at the table: the game itself is played on the RPS page
+                    if state.playing.as_ref().is_some_and(|p| p.contains(&me)) {
+                        navigate("/games/rps", Default::default());
+                    }
+                    set_room.set(Some(state));
+                }
+                RoomEvent::Left => {
+                    set_room.set(None);
+                    ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Room(RoomReq::List)));
+                }
+                RoomEvent::Error(err) => toaster.error(&error_label(err)),
+            }
+        }
+    });
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Room(RoomReq::List)));
+            }
+        }
+    });
+
+    let join = {
+        let ws = ws.clone();
+        move |room_id: i64, private: bool| {
+            let password = private.then(|| password.get_untracked());
+            let req = RoomReq::Join { room_id, password };
+            ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Room(req)));
+        }
+    };
+
+    let leave = {
+        let ws = ws.clone();
+        move |_| ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Room(RoomReq::Leave)))
+    };
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("rooms-title") }</h1>
+
+            { move || match room.get() {
+                Some(state) => view! {
+                    <RoomView state=state me=me.clone() />
+                    <button class="secondary" on:click=leave.clone()>{ tr!("rooms-leave") }</button>
+                }.into_any(),
+
+                None => {
+                    let join = join.clone();
+                    view! {
+                        <Show
+                        when=move || !rooms.with(|r| r.is_empty())
+                        fallback=|| view! { <p>{ move || tr!("rooms-empty") }</p> }
+                        >
+                            <input
+                            type="password"
+                            placeholder=move || tr!("rooms-password")
+                            prop:value=password
+                            on:input=move |ev| set_password.set(event_target_value(&ev))
+                            />
+                            <ul class="stack tournament-list">
+                                <For
+                                each=move || rooms.get()
+                                key=|r| (r.id, r.members)
+                                children={
+                                    let join = join.clone();
+                                    move |r: RoomSummary<BestOf>| {
+                                        let join = join.clone();
+                                        view! {
+                                            <li class="tournament-link">
+                                                <strong>{r.name}</strong>
+                                                <span style="color: var(--muted);">
+                                                    { move_tr!("rps-best-of", {"rounds" => r.settings.rounds()}) }
+                                                    {" · "}
+                                                    { move_tr!("rooms-members", {"count" => r.members}) }
+                                                    { r.private.then(|| format!(" · {}", tr!("rooms-private"))) }
+                                                </span>
+                                                <button class="secondary" on:click=move |_| join(r.id, r.private)>
+                                                    { move || tr!("rooms-join") }
+                                                </button>
+                                            </li>
+                                        }
+                                    }
+                                }
+                                />
+                            </ul>
+                        </Show>
+                        <CreateRoom />
+                    }.into_any()
+                }
+            } }
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+#[component]
+fn RoomView(state: RoomState<BestOf>, me: String) -> impl IntoView
+{
+    let champion = state.champion.clone();
+    let summary = state.summary;
+
+    view! {
+        <h2>{summary.name}</h2>
+        <p style="color: var(--muted);">
+            { move_tr!("rps-best-of", {"rounds" => summary.settings.rounds()}) }
+            { summary.owner.map(|owner| format!(" · {}", tr!("rooms-owner", {"username" => owner}))) }
+        </p>
+
+        { match state.playing {
+            Some([a, b]) => view! {
+                <p>{ tr!("rooms-playing") }{" "}<ProfileLink username=a />{" – "}<ProfileLink username=b /></p>
+            }.into_any(),
+            None => view! { <p style="color: var(--muted);">{ tr!("rooms-waiting") }</p> }.into_any(),
+        } }
+
+        <h3>{ move || tr!("rooms-line") }</h3>
+        <ol class="stack room-members">
+            { state.members
+                   .into_iter()
+                   .map(|name| {
+                       let streak = champion.as_ref()
+                                            .filter(|(champ, _)| *champ == name)
+                                            .map(|(_, streak)| *streak);
+                       let mine = name == me;
+                       view! {
+                           <li class:leaderboard-me=mine>
+                               <ProfileLink username=name />
+                               { streak.map(|streak| view! {
+                                   <span style="color: var(--success);">
+                                       {format!(" {}", tr!("rooms-streak", {"count" => streak}))}
+                                   </span>
+                               }) }
+                           </li>
+                       }
+                   })
+                   .collect::<Vec<_>>() }
+        </ol>
+
+        <h3>{ move || tr!("rooms-recent") }</h3>
+        <Show
+        when={
+            let empty = state.recent.is_empty();
+            move || !empty
+        }
+        fallback=|| view! { <p>{ move || tr!("rooms-no-results") }</p> }
+        >
+            <ul class="stack history-list">
+                { state.recent
+                       .clone()
+                       .into_iter()
+                       .map(|r| {
+                           let [a, b] = r.players;
+                           view! {
+                               <li class="history-row">
+                                   <span style="color: var(--muted);">
+                                       {r.played_at.with_timezone(&Local).format("%H:%M").to_string()}
+                                   </span>
+                                   <span>{format!("{a} – {b}")}</span>
+                                   <span>{ match r.winner {
+                                       Some(winner) => tr!("rooms-winner", {"username" => winner}),
+                                       None => tr!("rps-result-draw"),
+                                   } }</span>
+                               </li>
+                           }
+                       })
+                       .collect::<Vec<_>>() }
+            </ul>
+        </Show>
+    }
+}
+
+#[component]
+fn CreateRoom() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+
+    let (name, set_name) = signal(String::new());
+    let (password, set_password) = signal(String::new());
+    let (best_of, set_best_of) = signal(BestOf::Three);
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        let password = password.get();
+        let req = RoomReq::Create { name: name.get(),
+                                    password: (!password.is_empty()).then_some(password),
+                                    settings: best_of.get() };
+        ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Room(req)));
+    };
+
+    view! {
+        <form class="stack" on:submit=on_submit>
+            <h3>{ move || tr!("rooms-create-title") }</h3>
+            <input
+            type="text"
+            maxlength="32"
+            placeholder=move || tr!("rooms-name")
+            prop:value=name
+            on:input=move |ev| set_name.set(event_target_value(&ev))
+            />
+            <input
+            type="password"
+            placeholder=move || tr!("rooms-password-optional")
+            prop:value=password
+            on:input=move |ev| set_password.set(event_target_value(&ev))
+            />
+            <select on:change=move |ev| {
+                let series = event_target_value(&ev).parse()
+                                                    .ok()
+                                                    .and_then(BestOf::from_rounds)
+                                                    .unwrap_or_default();
+                set_best_of.set(series);
+            }>
+                { BestOf::ALL.into_iter()
+                             .map(|series| view! {
+                                 <option
+                                 value=series.rounds().to_string()
+                                 selected=series == BestOf::Three
+                                 >
+                                     { move_tr!("rps-best-of", {"rounds" => series.rounds()}) }
+                                 </option>
+                             })
+                             .collect::<Vec<_>>() }
+            </select>
+            <button type="submit">{ move || tr!("rooms-create") }</button>
+        </form>
+    }
+}
@@ -0,0 +1,178 @@
+use chrono::Utc;
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_use::{core::ConnectionReadyState, use_interval_fn};
+use shared::{
+    auth::UserInfo,
+    royale::*,
+    rps_game::RpsMove,
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use super::rps_game::mv_into_view;
+use crate::components::ProfileLink;
+use crate::hooks::WebsocketContext;
+
+#[component]
+pub fn Royale() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let user_info = expect_context::<UserInfo>();
+    let me = user_info.username;
+
+    let (event, set_event) = signal::<Option<RoyaleEvent>>(None);
+
+    let (now, set_now) = signal(Utc::now());
+    use_interval_fn(move || set_now.set(Utc::now()), 1_000);
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            if let Some(ServerMsg::GameMsg(envelope)) = ws.message.get()
+               && let Some(msg) = envelope.open_state::<RoyaleKind>()
+            {
+                set_event.set(Some(msg));
+            }
+        }
+    });
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<RoyaleKind>(RoyaleReq::Start(())));
+            }
+        }
+    });
+
+    on_cleanup({
+        let ws = ws.clone();
+        move || ws.send(ClientMsg::game::<RoyaleKind>(RoyaleReq::Leave))
+    });
+
+    let throw = {
+        let ws = ws.clone();
+        move |mv: RpsMove| ws.send(ClientMsg::game::<RoyaleKind>(RoyaleReq::Submit(mv)))
+    };
+
+    let next_btn = {
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                set_event.set(None);
+                ws.send(ClientMsg::game::<RoyaleKind>(RoyaleReq::Start(())));
+            }
+        }
+    };
+
+    view! {
+        <div class="stack fill-page card">
+        <h1>{ move || tr!("royale-title") }</h1>
+        <p style="color: var(--muted);">{ move || tr!("royale-explainer") }</p>
+
+        { move || match event.get() {
+            None => view! {
+                <div class="loading-spinner" style="margin-top: auto; margin-bottom: auto;"></div>
+            }.into_any(),
+
+            Some(RoyaleEvent::Lobby { players, starts_at }) => view! {
+                <h3>{ tr!("royale-lobby", {
+                    "count" => players.len(),
+                    "max" => ROYALE_MAX_PLAYERS
+                }) }</h3>
+                <p>{ move || match starts_at {
+                    Some(t) => tr!("royale-starts-in", {"seconds" => (t - now.get()).num_seconds().max(0)}),
+                    None => tr!("royale-waiting", {"min" => ROYALE_MIN_PLAYERS}),
+                } }</p>
+                <ul class="royale-players">
+                    { players.into_iter()
+                             .map(|name| view! { <li><ProfileLink username=name /></li> })
+                             .collect::<Vec<_>>() }
+                </ul>
+            }.into_any(),
+
+            Some(RoyaleEvent::Round { round, players, deadline }) => {
+                let mine = players.iter().find(|p| p.name == me).cloned();
+                let can_throw = mine.as_ref().is_some_and(|p| p.eliminated_in.is_none() && !p.thrown);
+                let out = mine.as_ref().is_some_and(|p| p.eliminated_in.is_some());
+                let throw = throw.clone();
+
+                view! {
+                    <h3>{ tr!("royale-round", {"round" => round}) }</h3>
+                    <p>{ move || tr!("rps-time-left", {"seconds" => (deadline - now.get()).num_seconds().max(0)}) }</p>
+                    <ul class="royale-players">
+                        { players.into_iter()
+                                 .map(|p| view! {
+                                     <li class:royale-out=p.eliminated_in.is_some()>
+                                         <ProfileLink username=p.name.clone() />
+                                         { p.last_throw.map(|mv| mv_into_view(mv, 2)) }
+                                         <span style="color: var(--muted);">
+                                             { match (p.eliminated_in, p.thrown) {
+                                                 (Some(r), _) => tr!("royale-out-in", {"round" => r}),
+                                                 (None, true) => tr!("royale-thrown"),
+                                                 (None, false) => String::new(),
+                                             } }
+                                         </span>
+                                     </li>
+                                 })
+                                 .collect::<Vec<_>>() }
+                    </ul>
+                    <p class:el-hide=!out style="color: var(--error);">{ tr!("royale-you-are-out") }</p>
+                    <div
+                    class="cluster"
+                    class:el-hide=!can_throw
+                    style="--cluster-justify: center; margin-top: auto; margin-bottom: auto;"
+                    >
+                        { RpsMove::ALL.into_iter()
+                                      .map(|mv| {
+                                          let throw = throw.clone();
+                                          view! {
+                                              <button class="icon-btn" on:click=move |_| throw(mv)>
+                                                  {mv_into_view(mv, 4)}
+                                              </button>
+                                          }
+                                      })
+                                      .collect::<Vec<_>>() }
+                    </div>
+                }.into_any()
+            }
+
+            Some(RoyaleEvent::Finished { rounds, placements }) => view! {
+                <h3>{ move_tr!("royale-finished", {"rounds" => rounds}) }</h3>
+                <table class="standings">
+                    <thead>
+                        <tr>
+                            <th>"#"</th>
+                            <th>{ move || tr!("leaderboard-col-player") }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        { placements.into_iter()
+                                    .map(|p| {
+                                        let is_me = p.name == me;
+                                        view! {
+                                            <tr class:leaderboard-me=is_me>
+                                                <td>{p.place}</td>
+                                                <td><ProfileLink username=p.name /></td>
+                                            </tr>
+                                        }
+                                    })
+                                    .collect::<Vec<_>>() }
+                    </tbody>
+                </table>
+            }.into_any(),
+        } }
+
+            <div class="stack" style="margin-top: auto; --stack-gap: var(--s0);">
+            <button
+            class:el-hide=move || !event.get().is_some_and(|e| matches!(e, RoyaleEvent::Finished { .. }))
+            on:click=next_btn>
+                { move || tr!("rps-next-game") }
+            </button>
+            <a href = "/games" class="button secondary">
+                { move || tr!("rps-other-games") }
+            </a>
+            </div>
+        </div>
+    }
+}
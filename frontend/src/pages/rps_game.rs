@@ -1,16 +1,23 @@
+use chrono::Utc;
+use codee::string::JsonSerdeCodec;
 use leptos::prelude::*;
 use leptos_fluent::{move_tr, tr};
-use leptos_use::{core::ConnectionReadyState, use_timeout_fn, UseTimeoutFnReturn};
+use leptos_use::{core::ConnectionReadyState, storage::use_local_storage, use_interval_fn};
 use shared::{
     auth::UserInfo,
-    game::{GameError, GameResult},
-    rps_game::{RpsGameReq, RpsGameState, RpsMove},
+    game::{ConnectionEvent, GameError, GameResult, RematchEvent},
+    rps_game::{BestOf, RpsGameReq, RpsGameState, RpsKind, RpsMove},
+    wallet::{WalletError, WalletEvent},
     ws_messages::{ClientMsg, ServerMsg},
 };
 
+use crate::components::ProfileLink;
 use crate::hooks::{MyToaster, WebsocketContext};
 
-fn mv_into_view(mv: RpsMove, size: usize) -> AnyView
+/// Coins a player can put on a game, 0 to play for rating only.
+const STAKES: [u32; 4] = [0, 10, 25, 50];
+
+pub(crate) fn mv_into_view(mv: RpsMove, size: usize) -> AnyView
 {
     match mv {
         RpsMove::Rock => view! {
@@ -45,46 +52,75 @@ pub fn RpsGame() -> impl IntoView
 
     let (curr_game, set_curr_game) = signal::<Option<RpsGameState>>(None);
     let (curr_mv, set_curr_mv) = signal::<Option<RpsMove>>(None);
-    let (can_leave, set_can_leave) = signal(false);
+    let (best_of, set_best_of, _) = use_local_storage::<BestOf, JsonSerdeCodec>("rps_best_of");
+    let (stake, set_stake, _) = use_local_storage::<u32, JsonSerdeCodec>("rps_stake");
 
-    let toaster = MyToaster::new();
+    let (rematch_sent, set_rematch_sent) = signal(false);
+    let (rematch_offered, set_rematch_offered) = signal(false);
+    let (rematch_closed, set_rematch_closed) = signal(false);
 
-    let UseTimeoutFnReturn { start: timer_start, .. } = {
-        let user_info = user_info.clone();
-        use_timeout_fn(move |()| {
-                           if let Some(RpsGameState::Game { players, submitted }) = curr_game.get()
-                           {
-                               let opp_sub = if players[0] == user_info.username {
-                                   submitted[1]
-                               } else {
-                                   submitted[0]
-                               };
+    let toaster = MyToaster::new();
 
-                               if !opp_sub {
-                                   set_can_leave.set(true);
-                               }
-                           }
-                       },
-                       20_000.0)
-    };
+    // ticks once a second so the move countdown stays current
+    let (now, set_now) = signal(Utc::now());
+    use_interval_fn(move || set_now.set(Utc::now()), 1_000);
 
     Effect::new({
         let ws = ws.clone();
 
         move |_| {
             if let Some(msg) = ws.message.get() {
-                if let ServerMsg::RpsGameMsg(rps_state) = msg {
-                    if matches!(rps_state, RpsGameState::Game { .. }) {
-                        set_can_leave.set(false);
-                        timer_start(()); // <<—— correct place
+                if let ServerMsg::GameMsg(envelope) = &msg
+                   && let Some(rps_state) = envelope.open_state::<RpsKind>()
+                {
+                    if !matches!(rps_state, RpsGameState::Game { .. }) {
+                        set_rematch_sent.set(false);
+                        set_rematch_offered.set(false);
+                        set_rematch_closed.set(false);
                     }
                     set_curr_game.set(Some(rps_state));
+                } else if let ServerMsg::RematchMsg(event) = msg {
+                    match event {
+                        RematchEvent::Offered => {
+                            set_rematch_offered.set(true);
+                            toaster.info(&tr!("rps-rematch-offered"));
+                        }
+                        RematchEvent::Declined => {
+                            set_rematch_closed.set(true);
+                            toaster.error(&tr!("rps-rematch-declined"));
+                        }
+                        RematchEvent::Expired => set_rematch_closed.set(true),
+                    }
+                } else if let ServerMsg::ConnectionMsg(event) = msg {
+                    match event {
+                        ConnectionEvent::OpponentAway { grace_secs } => {
+                            toaster.info(&tr!("rps-opponent-away", {"seconds" => grace_secs}));
+                        }
+                        ConnectionEvent::OpponentBack => toaster.success(&tr!("rps-opponent-back")),
+                    }
+                } else if let ServerMsg::WalletMsg(event) = msg {
+                    match event {
+                        WalletEvent::Staked { stake, .. } => {
+                            toaster.info(&tr!("rps-stake-held", {"coins" => stake}));
+                        }
+                        WalletEvent::Error(WalletError::InsufficientFunds) => {
+                            set_stake.set(0);
+                            toaster.error(&tr!("rps-stake-insufficient"));
+                        }
+                        WalletEvent::Error(WalletError::NoWagers) => set_stake.set(0),
+                        _ => {}
+                    }
                 } else if let ServerMsg::GameErrorMsg(GameError::Disconnected) = msg {
                     set_curr_game.set(None);
                     set_curr_mv.set(None);
                     let msg = tr!("rps-opponent-disconnected");
                     toaster.error(&msg);
-                    ws.send(ClientMsg::RpsGameMsg(RpsGameReq::Start));
+                    ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Start(best_of.get_untracked())));
+                } else if let ServerMsg::GameErrorMsg(GameError::TimedOut) = msg {
+                    set_curr_game.set(None);
+                    set_curr_mv.set(None);
+                    toaster.error(&tr!("rps-timed-out"));
+                    ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Start(best_of.get_untracked())));
                 }
             };
         }
@@ -94,34 +130,106 @@ pub fn RpsGame() -> impl IntoView
         let ws = ws.clone();
         move |_| {
             if ws.state.get() == ConnectionReadyState::Open {
-                ws.send(ClientMsg::RpsGameMsg(RpsGameReq::Start));
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::SetStake(stake.get_untracked())));
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Start(best_of.get_untracked())));
             }
         }
     });
 
-    let leave_btn = {
+    let next_btn = {
         let ws = ws.clone();
         move |_| {
             if ws.state.get() == ConnectionReadyState::Open {
-                ws.send(ClientMsg::RpsGameMsg(RpsGameReq::Leave));
-                ws.send(ClientMsg::RpsGameMsg(RpsGameReq::Start));
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Start(best_of.get_untracked())));
                 set_curr_game.set(None);
                 set_curr_mv.set(None);
             }
         }
     };
 
-    let next_btn = {
+    let rematch_btn = {
         let ws = ws.clone();
         move |_| {
-            if ws.state.get() == ConnectionReadyState::Open {
-                ws.send(ClientMsg::RpsGameMsg(RpsGameReq::Start));
-                set_curr_game.set(None);
-                set_curr_mv.set(None);
+            ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Rematch));
+            set_rematch_sent.set(true);
+        }
+    };
+
+    let decline_rematch_btn = {
+        let ws = ws.clone();
+        move |_| {
+            ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::DeclineRematch));
+            set_rematch_closed.set(true);
+        }
+    };
+
+    // switching series length while waiting moves you to the matching queue
+    let pick_series = {
+        let ws = ws.clone();
+        move |series: BestOf| {
+            set_best_of.set(series);
+            if curr_game.get_untracked().is_none()
+               && ws.state.get_untracked() == ConnectionReadyState::Open
+            {
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Leave));
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Start(series)));
             }
         }
     };
 
+    let series_picker = move || {
+        BestOf::ALL.into_iter()
+                   .map(|series| {
+                       let pick_series = pick_series.clone();
+                       view! {
+                           <button
+                           class="secondary series-btn"
+                           class:pressed=move || best_of.get() == series
+                           on:click=move |_| pick_series(series)
+                           >
+                               { move_tr!("rps-best-of", {"rounds" => series.rounds()}) }
+                           </button>
+                       }
+                   })
+                   .collect::<Vec<_>>()
+    };
+
+    // a new stake only applies to games not yet matched, so requeue with it while waiting
+    let pick_stake = {
+        let ws = ws.clone();
+        move |coins: u32| {
+            set_stake.set(coins);
+            if ws.state.get_untracked() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::SetStake(coins)));
+                if curr_game.get_untracked().is_none() {
+                    ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Leave));
+                    ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Start(best_of.get_untracked())));
+                }
+            }
+        }
+    };
+
+    let stake_picker = move || {
+        STAKES.into_iter()
+              .map(|coins| {
+                  let pick_stake = pick_stake.clone();
+                  view! {
+                      <button
+                      class="secondary series-btn"
+                      class:pressed=move || stake.get() == coins
+                      on:click=move |_| pick_stake(coins)
+                      >
+                          { move || if coins == 0 {
+                              tr!("rps-stake-none")
+                          } else {
+                              tr!("rps-stake-coins", {"coins" => coins})
+                          } }
+                      </button>
+                  }
+              })
+              .collect::<Vec<_>>()
+    };
+
     view! {
         <div class="stack fill-page card">
 
@@ -148,30 +256,33 @@ pub fn RpsGame() -> impl IntoView
         { move || {
             match curr_game.get() {
                 None => {
-                    set_can_leave.set(false);
                     view!{
                         <p>{ tr!("rps-waiting") }</p>
                         <div class="loading-spinner" style="margin-top: auto; margin-bottom: auto;"></div>
                     }.into_any()
                 },
 
-                Some(RpsGameState::Game { players, submitted }) => {
-                    let (opp_name, opp_sub, player_sub) = if players[0] == user_info.username {
-                        (players[1].clone(), submitted[1], submitted[0])
+                Some(RpsGameState::Game { players, ratings, submitted, score, best_of, round, last_round, deadline }) => {
+                    let (opp_name, opp_rating, opp_sub, player_sub, score, last_round) = if players[0] == user_info.username {
+                        (players[1].clone(), ratings[1], submitted[1], submitted[0], score, last_round)
                     } else {
-                        (players[0].clone(), submitted[0], submitted[1])
+                        (players[0].clone(), ratings[0], submitted[0], submitted[1], [score[1], score[0]],
+                         last_round.map(|[a, b]| [b, a]))
                     };
 
                     let submit = {
                         let ws = ws.clone();
                         move |mv: RpsMove| {
-                            ws.send(ClientMsg::RpsGameMsg(RpsGameReq::Submit(mv)));
+                            ws.send(ClientMsg::game::<RpsKind>(RpsGameReq::Submit(mv)));
                             set_curr_mv.set(Some(mv));
                         }
                     };
 
                     view!{
-                        <h3>{ tr!("rps-playing-against-label") }{" "}<span class="mention-name">{opp_name}</span>
+                        <h3>{ tr!("rps-playing-against-label") }{" "}<ProfileLink username=opp_name />
+                        { opp_rating.map(|rating| view! {
+                            <span style="color: var(--muted);">{format!(" ({rating})")}</span>
+                        }) }
                         <span style="color: var(--muted);">
                         {move || {
                             if opp_sub {
@@ -181,6 +292,24 @@ pub fn RpsGame() -> impl IntoView
                             }
                         }}
                         </span></h3>
+                        <p class:el-hide=best_of == BestOf::One>
+                            { move_tr!("rps-score", {
+                                "round" => round,
+                                "score" => format!("{} : {}", score[0], score[1]),
+                                "rounds" => best_of.rounds()
+                            }) }
+                        </p>
+                        <p class:el-hide=player_sub>
+                            { move || tr!("rps-time-left", {
+                                "seconds" => (deadline - now.get()).num_seconds().max(0)
+                            }) }
+                        </p>
+                        { last_round.map(|[player_move, opp_move]| view! {
+                            <p style="color: var(--muted);">
+                                { tr!("rps-last-round") }{" "}{mv_into_view(player_move, 2)}
+                                {" – "}{mv_into_view(opp_move, 2)}
+                            </p>
+                        }) }
                         <div
                         class="cluster"
                         class:el-hide=player_sub
@@ -232,13 +361,14 @@ pub fn RpsGame() -> impl IntoView
                     }.into_any()
                 }
                 Some(RpsGameState::Finished(mut info)) => {
-                    set_can_leave.set(false);
-
-                    let (opp_name, opp_move, player_move, res) = if info.players[0] == user_info.username {
-                        (info.players[1].clone(), info.moves[1], info.moves[0], info.resolve())
-                    } else {
+                    if info.players[0] != user_info.username {
                         info.reverse();
-                        (info.players[1].clone(), info.moves[1], info.moves[0], info.resolve())
+                    }
+                    let (opp_name, moves, res) = (info.players[1].clone(), info.moves, info.resolve());
+                    let forfeit_text = match info.forfeited {
+                        [true, _] => Some(tr!("rps-you-forfeited")),
+                        [_, true] => Some(tr!("rps-opponent-forfeited", {"username" => opp_name.clone()})),
+                        _ => None,
                     };
 
                     let result_text = match res {
@@ -256,24 +386,64 @@ pub fn RpsGame() -> impl IntoView
                         >
                             { move_tr!("rps-finished", {"result" => result_text.clone()}) }
                         </h3>
-                        <p><span class="mention-name">{opp_name}</span>{" "}{ tr!("rps-opponent-played-label") }{" "}{mv_into_view(opp_move, 3)}</p>
-                        <p>{ tr!("rps-you-played") }{" "}{mv_into_view(player_move, 3)}</p>
+                        <p class:el-hide=info.best_of == BestOf::One>
+                            { move_tr!("rps-final-score", {"score" => format!("{} : {}", info.score[0], info.score[1])}) }
+                        </p>
+                        { forfeit_text.map(|text| view! { <p style="color: var(--muted);">{text}</p> }) }
+                        { moves.map(|[player_move, opp_move]| view! {
+                            <p><ProfileLink username=opp_name />{" "}{ tr!("rps-opponent-played-label") }{" "}{mv_into_view(opp_move, 3)}</p>
+                            <p>{ tr!("rps-you-played") }{" "}{mv_into_view(player_move, 3)}</p>
+                        }) }
                     }.into_any()
                 }
             }
         }}
             <div class="stack" style="margin-top: auto; --stack-gap: var(--s0);">
+            <div
+            class="cluster"
+            style="--cluster-justify: center;"
+            class:el-hide=move || curr_game.get().is_some_and(|g| matches!(g, RpsGameState::Game{..}))
+            >
+                {series_picker}
+            </div>
+            <div
+            class="cluster"
+            style="--cluster-justify: center;"
+            class:el-hide=move || curr_game.get().is_some_and(|g| matches!(g, RpsGameState::Game{..}))
+            >
+                <span style="color: var(--muted);">{ move_tr!("rps-stake-label") }</span>
+                {stake_picker}
+            </div>
+            <div
+            class="cluster"
+            style="--cluster-justify: center;"
+            class:el-hide=move || rematch_closed.get()
+                                 || !curr_game.get().is_some_and(|g| matches!(g, RpsGameState::Finished{..}))
+            >
+                <button
+                class="secondary"
+                disabled=move || rematch_sent.get()
+                on:click=rematch_btn>
+                    { move || if rematch_sent.get() {
+                        tr!("rps-rematch-waiting")
+                    } else if rematch_offered.get() {
+                        tr!("rps-rematch-accept")
+                    } else {
+                        tr!("rps-rematch")
+                    } }
+                </button>
+                <button
+                class="secondary destructive"
+                class:el-hide=move || !rematch_offered.get() || rematch_sent.get()
+                on:click=decline_rematch_btn>
+                    { move || tr!("rps-rematch-decline") }
+                </button>
+            </div>
             <button
             class:el-hide=move || !curr_game.get().is_some_and(|g| matches!(g, RpsGameState::Finished{..}))
             on:click=next_btn>
                 { move || tr!("rps-next-game") }
             </button>
-            <button
-            class="secondary destructive"
-            class:el-hide=move || {!can_leave.get()}
-            on:click=leave_btn>
-                { move || tr!("rps-leave") }
-            </button>
             <div class="cluster" style="--cluster-justify: center;">
             <a href = "/games" class="button secondary" style="width: 50%;">
                 { move || tr!("rps-other-games") }
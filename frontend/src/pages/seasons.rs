@@ -0,0 +1,131 @@
+use chrono::Local;
+use leptos::prelude::*;
+use leptos_fluent::tr;
+use shared::{auth::UserInfo, season::*};
+
+use crate::api::{fetch_season, fetch_seasons};
+
+#[component]
+pub fn Seasons() -> impl IntoView
+{
+    let user_info = expect_context::<UserInfo>();
+    let me = user_info.username;
+
+    let seasons = LocalResource::new(fetch_seasons);
+    let (picked, set_picked) = signal(None::<i64>);
+    // Newest season until another one is picked.
+    let season_id = move || {
+        picked.get().or_else(|| {
+                        seasons.get()
+                               .and_then(|s| s.ok())
+                               .and_then(|s| s.first().map(|s| s.id))
+                    })
+    };
+
+    let details = LocalResource::new(move || {
+        let id = season_id();
+        async move {
+            match id {
+                Some(id) => Some(fetch_season(id).await),
+                None => None,
+            }
+        }
+    });
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("seasons-title") }</h1>
+
+            { move || match seasons.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(Err(_)) => view! {
+                    <p style="color: var(--error);">{ tr!("seasons-error") }</p>
+                }.into_any(),
+                Some(Ok(list)) if list.is_empty() => view! {
+                    <p>{ tr!("seasons-none") }</p>
+                }.into_any(),
+                Some(Ok(list)) => view! {
+                    <select on:change=move |ev| {
+                        set_picked.set(event_target_value(&ev).parse().ok());
+                    }>
+                        { list.into_iter()
+                              .map(|s| view! {
+                                  <option value=s.id selected=move || season_id() == Some(s.id)>
+                                      { s.name }
+                                  </option>
+                              })
+                              .collect::<Vec<_>>() }
+                    </select>
+                }.into_any(),
+            } }
+
+            { move || match details.get().flatten() {
+                None => view! { <div></div> }.into_any(),
+                Some(Err(_)) => view! {
+                    <p style="color: var(--error);">{ tr!("seasons-error") }</p>
+                }.into_any(),
+                Some(Ok(details)) => {
+                    let me = me.clone();
+                    let summary = details.summary;
+                    let archived = summary.archived;
+                    let standings = details.standings;
+                    let empty = standings.is_empty();
+                    let dates = format!("{} – {}",
+                                        summary.starts_at.with_timezone(&Local).format("%d.%m.%Y"),
+                                        summary.ends_at.with_timezone(&Local).format("%d.%m.%Y"));
+
+                    view! {
+                        <p style="color: var(--muted);">
+                            { dates } " · "
+                            { move || if archived {
+                                  tr!("seasons-archived")
+                              } else {
+                                  tr!("seasons-live")
+                              } }
+                        </p>
+                        <Show
+                        when=move || !empty
+                        fallback=|| view! { <p>{ move || tr!("seasons-empty") }</p> }
+                        >
+                            <table class="standings leaderboard">
+                                <thead>
+                                    <tr>
+                                        <th>"#"</th>
+                                        <th>{ move || tr!("leaderboard-col-player") }</th>
+                                        <th>{ move || tr!("leaderboard-col-record") }</th>
+                                        <th>{ move || tr!("leaderboard-col-rating") }</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    { standings.clone()
+                                               .into_iter()
+                                               .map(|s| {
+                                                   let mine = s.username == me;
+                                                   standing_row(s, mine)
+                                               })
+                                               .collect::<Vec<_>>() }
+                                </tbody>
+                            </table>
+                        </Show>
+                    }.into_any()
+                }
+            } }
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+fn standing_row(standing: SeasonStanding, mine: bool) -> impl IntoView
+{
+    view! {
+        <tr class:leaderboard-me=mine>
+            <td>{standing.rank}</td>
+            <td>{standing.username}</td>
+            <td>{format!("{} / {} / {}", standing.wins, standing.draws, standing.losses)}</td>
+            <td>{format!("{:.0}", standing.rating)}</td>
+        </tr>
+    }
+}
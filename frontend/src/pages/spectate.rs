@@ -0,0 +1,213 @@
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_use::core::ConnectionReadyState;
+use shared::{
+    game::{GameError, GameResult},
+    rps_game::{BestOf, RpsGameReq, RpsGameState, RpsKind},
+    spectate::{LiveGame, SpectateEvent, SpectateReq},
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use super::rps_game::mv_into_view;
+use crate::hooks::{MyToaster, WebsocketContext};
+
+fn spectate_msg(req: SpectateReq) -> ClientMsg
+{
+    ClientMsg::game::<RpsKind>(RpsGameReq::Spectate(req))
+}
+
+#[component]
+pub fn Spectate() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let toaster = MyToaster::new();
+
+    let (games, set_games) = signal(Vec::<LiveGame>::new());
+    let (watching, set_watching) = signal::<Option<u64>>(None);
+    let (curr_game, set_curr_game) = signal::<Option<RpsGameState>>(None);
+
+    Effect::new({
+        let ws = ws.clone();
+
+        move |_| {
+            let Some(msg) = ws.message.get() else {
+                return;
+            };
+
+            match msg {
+                ServerMsg::SpectateMsg(SpectateEvent::Games(live)) => set_games.set(live),
+                ServerMsg::SpectateMsg(SpectateEvent::Update { game_id, msg }) => {
+                    if watching.get_untracked() == Some(game_id)
+                       && let ServerMsg::GameMsg(envelope) = *msg
+                       && let Some(state) = envelope.open_state::<RpsKind>()
+                    {
+                        set_curr_game.set(Some(state));
+                    }
+                }
+                ServerMsg::SpectateMsg(SpectateEvent::Ended { game_id })
+                    if watching.get_untracked() == Some(game_id) =>
+                {
+                    set_watching.set(None);
+                    set_curr_game.set(None);
+                    ws.send(spectate_msg(SpectateReq::List));
+                }
+                ServerMsg::GameErrorMsg(GameError::NotFound) if watching.get_untracked().is_some() => {
+                    set_watching.set(None);
+                    toaster.error(&tr!("spectate-not-found"));
+                    ws.send(spectate_msg(SpectateReq::List));
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(spectate_msg(SpectateReq::List));
+            }
+        }
+    });
+
+    on_cleanup({
+        let ws = ws.clone();
+        move || ws.send(spectate_msg(SpectateReq::Unwatch))
+    });
+
+    let refresh = {
+        let ws = ws.clone();
+        move |_| ws.send(spectate_msg(SpectateReq::List))
+    };
+
+    let watch = {
+        let ws = ws.clone();
+        move |game_id: u64| {
+            set_curr_game.set(None);
+            set_watching.set(Some(game_id));
+            ws.send(spectate_msg(SpectateReq::Watch(game_id)));
+        }
+    };
+
+    let back = {
+        let ws = ws.clone();
+        move |_| {
+            ws.send(spectate_msg(SpectateReq::Unwatch));
+            ws.send(spectate_msg(SpectateReq::List));
+            set_watching.set(None);
+            set_curr_game.set(None);
+        }
+    };
+
+    let game_view = move || match curr_game.get() {
+        None => view! {
+            <div class="loading-spinner" style="margin-top: auto; margin-bottom: auto;"></div>
+        }.into_any(),
+
+        Some(RpsGameState::Game { players, ratings, submitted, score, best_of, round, last_round, .. }) => {
+            let player_line = move |i: usize| view! {
+                <p>
+                    <span class="mention-name">{players[i].clone()}</span>
+                    { ratings[i].map(|rating| view! {
+                        <span style="color: var(--muted);">{format!(" ({rating})")}</span>
+                    }) }
+                    <span style="color: var(--muted);">
+                        { submitted[i].then(|| format!(" {}", tr!("rps-opponent-moved"))) }
+                    </span>
+                </p>
+            };
+
+            view! {
+                {player_line(0)}
+                {player_line(1)}
+                <p class:el-hide=best_of == BestOf::One>
+                    { move_tr!("rps-score", {
+                        "round" => round,
+                        "score" => format!("{} : {}", score[0], score[1]),
+                        "rounds" => best_of.rounds()
+                    }) }
+                </p>
+                { last_round.map(|[first, second]| view! {
+                    <p style="color: var(--muted);">
+                        { tr!("rps-last-round") }{" "}{mv_into_view(first, 2)}
+                        {" – "}{mv_into_view(second, 2)}
+                    </p>
+                }) }
+            }.into_any()
+        }
+
+        Some(RpsGameState::Finished(info)) => {
+            let result_text = match info.resolve() {
+                GameResult::Win => tr!("spectate-winner", {"username" => info.players[0].clone()}),
+                GameResult::Defeat => tr!("spectate-winner", {"username" => info.players[1].clone()}),
+                GameResult::Draw => tr!("rps-result-draw"),
+            };
+
+            view! {
+                <h3>{result_text}</h3>
+                <p class:el-hide=info.best_of == BestOf::One>
+                    { move_tr!("rps-final-score", {"score" => format!("{} : {}", info.score[0], info.score[1])}) }
+                </p>
+                { info.moves.map(|[first, second]| view! {
+                    <p>
+                        <span class="mention-name">{info.players[0].clone()}</span>{" "}
+                        {mv_into_view(first, 3)}{" – "}{mv_into_view(second, 3)}{" "}
+                        <span class="mention-name">{info.players[1].clone()}</span>
+                    </p>
+                }) }
+            }.into_any()
+        }
+    };
+
+    view! {
+        <div class="stack fill-page card">
+        <h1>{ move || tr!("spectate-title") }</h1>
+
+        { move || match watching.get() {
+            Some(_) => view! {
+                {game_view}
+                <button class="secondary" on:click=back.clone()>
+                    { move || tr!("spectate-back") }
+                </button>
+            }.into_any(),
+
+            None => view! {
+                <p class:el-hide=move || !games.with(|g| g.is_empty())>
+                    { move || tr!("spectate-empty") }
+                </p>
+                <ul class="stack live-games">
+                    <For
+                    each=move || games.get()
+                    key=|game| (game.id, game.players.clone())
+                    children={
+                        let watch = watch.clone();
+                        move |game| {
+                            let watch = watch.clone();
+                            view! {
+                                <li class="cluster" style="--cluster-justify: space-between;">
+                                    <span>
+                                        <span class="mention-name">{game.players[0].clone()}</span>
+                                        {" vs "}
+                                        <span class="mention-name">{game.players[1].clone()}</span>
+                                    </span>
+                                    <button class="secondary" on:click=move |_| watch(game.id)>
+                                        { move || tr!("spectate-watch") }
+                                    </button>
+                                </li>
+                            }
+                        }
+                    }
+                    />
+                </ul>
+                <button class="secondary" on:click=refresh.clone()>
+                    { move || tr!("spectate-refresh") }
+                </button>
+            }.into_any(),
+        } }
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
@@ -0,0 +1,213 @@
+use chrono::Utc;
+use leptos::prelude::*;
+use leptos_fluent::{move_tr, tr};
+use leptos_use::{core::ConnectionReadyState, use_interval_fn};
+use shared::{
+    auth::UserInfo,
+    game::{ConnectionEvent, GameError, GameResult},
+    tic_tac_toe::{winning_line, Board, Mark, TicTacToeKind, TicTacToeReq, TicTacToeState},
+    ws_messages::{ClientMsg, ServerMsg},
+};
+
+use crate::components::ProfileLink;
+use crate::hooks::{MyToaster, WebsocketContext};
+
+fn mark_label(mark: Mark) -> &'static str
+{
+    match mark {
+        Mark::X => "✕",
+        Mark::O => "◯",
+    }
+}
+
+/// 3x3 grid of cells. `play` is called with the cell index; without it the board is read only.
+fn board_view(board: Board, play: Option<impl Fn(u8) + Clone + 'static>) -> impl IntoView
+{
+    let line = winning_line(&board);
+
+    view! {
+        <div class="ttt-board">
+            { board.into_iter()
+                   .enumerate()
+                   .map(|(i, cell)| {
+                       let play = play.clone();
+                       let won = line.is_some_and(|l| l.contains(&i));
+                       view! {
+                           <button
+                           class="ttt-cell"
+                           class:ttt-won=won
+                           disabled=play.is_none() || cell.is_some()
+                           on:click=move |_| {
+                               if let Some(play) = &play {
+                                   play(i as u8);
+                               }
+                           }
+                           >
+                               {cell.map(mark_label)}
+                           </button>
+                       }
+                   })
+                   .collect::<Vec<_>>() }
+        </div>
+    }
+}
+
+#[component]
+pub fn TicTacToeGame() -> impl IntoView
+{
+    let ws = expect_context::<WebsocketContext>();
+    let user_info = expect_context::<UserInfo>();
+    let toaster = MyToaster::new();
+
+    let (curr_game, set_curr_game) = signal::<Option<TicTacToeState>>(None);
+
+    let (now, set_now) = signal(Utc::now());
+    use_interval_fn(move || set_now.set(Utc::now()), 1_000);
+
+    let me = {
+        let username = user_info.username.clone();
+        move |players: &[String; 2]| if players[0] == username { 0 } else { 1 }
+    };
+
+    Effect::new({
+        let ws = ws.clone();
+
+        move |_| {
+            let Some(msg) = ws.message.get() else {
+                return;
+            };
+
+            match msg {
+                ServerMsg::GameMsg(envelope) => {
+                    if let Some(state) = envelope.open_state::<TicTacToeKind>() {
+                        set_curr_game.set(Some(state));
+                    }
+                }
+                ServerMsg::ConnectionMsg(ConnectionEvent::OpponentAway { grace_secs }) => {
+                    toaster.info(&tr!("rps-opponent-away", {"seconds" => grace_secs}));
+                }
+                ServerMsg::ConnectionMsg(ConnectionEvent::OpponentBack) => {
+                    toaster.success(&tr!("rps-opponent-back"));
+                }
+                ServerMsg::GameErrorMsg(GameError::InvalidMove) => {
+                    toaster.error(&tr!("ttt-invalid-move"));
+                }
+                ServerMsg::GameErrorMsg(GameError::Disconnected) => {
+                    set_curr_game.set(None);
+                    toaster.error(&tr!("rps-opponent-disconnected"));
+                    ws.send(ClientMsg::game::<TicTacToeKind>(TicTacToeReq::Start(())));
+                }
+                ServerMsg::GameErrorMsg(GameError::TimedOut) => {
+                    set_curr_game.set(None);
+                    toaster.error(&tr!("rps-timed-out"));
+                    ws.send(ClientMsg::game::<TicTacToeKind>(TicTacToeReq::Start(())));
+                }
+                _ => {}
+            }
+        }
+    });
+
+    Effect::new({
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<TicTacToeKind>(TicTacToeReq::Start(())));
+            }
+        }
+    });
+
+    let play = {
+        let ws = ws.clone();
+        move |cell: u8| ws.send(ClientMsg::game::<TicTacToeKind>(TicTacToeReq::Submit(cell)))
+    };
+
+    let next_btn = {
+        let ws = ws.clone();
+        move |_| {
+            if ws.state.get() == ConnectionReadyState::Open {
+                ws.send(ClientMsg::game::<TicTacToeKind>(TicTacToeReq::Start(())));
+                set_curr_game.set(None);
+            }
+        }
+    };
+
+    view! {
+        <div class="stack fill-page card">
+        <h1>{ move || tr!("ttt-title") }</h1>
+
+        { move || match curr_game.get() {
+            None => view! {
+                <p>{ tr!("rps-waiting") }</p>
+                <div class="loading-spinner" style="margin-top: auto; margin-bottom: auto;"></div>
+            }.into_any(),
+
+            Some(TicTacToeState::Playing { players, ratings, board, turn, deadline }) => {
+                let i = me(&players);
+                let mine = Mark::of(i);
+                let my_turn = turn == mine;
+
+                view! {
+                    <h3>{ tr!("rps-playing-against-label") }{" "}
+                        <ProfileLink username=players[1 - i].clone() />
+                        { ratings[1 - i].map(|rating| view! {
+                            <span style="color: var(--muted);">{format!(" ({rating})")}</span>
+                        }) }
+                    </h3>
+                    <p>{ tr!("ttt-you-play", {"mark" => mark_label(mine)}) }</p>
+                    <p style=if my_turn { "color: var(--success);" } else { "color: var(--muted);" }>
+                        { if my_turn { tr!("ttt-your-turn") } else { tr!("ttt-opponent-turn") } }
+                        {" · "}
+                        { move || tr!("rps-time-left", {"seconds" => (deadline - now.get()).num_seconds().max(0)}) }
+                    </p>
+                    { board_view(board, my_turn.then(|| play.clone())) }
+                }.into_any()
+            }
+
+            Some(TicTacToeState::Finished(info)) => {
+                let i = me(&info.players);
+                let res = match (info.resolve(), i) {
+                    (GameResult::Win, 1) => GameResult::Defeat,
+                    (GameResult::Defeat, 1) => GameResult::Win,
+                    (res, _) => res,
+                };
+
+                let result_text = match res {
+                    GameResult::Win => tr!("rps-result-win"),
+                    GameResult::Defeat => tr!("rps-result-defeat"),
+                    GameResult::Draw => tr!("rps-result-draw"),
+                };
+                let forfeit_text = if info.forfeited[i] {
+                    Some(tr!("rps-you-forfeited"))
+                } else if info.forfeited[1 - i] {
+                    Some(tr!("rps-opponent-forfeited", {"username" => info.players[1 - i].clone()}))
+                } else {
+                    None
+                };
+
+                view! {
+                    <h3 style=match res {
+                        GameResult::Win => "color: var(--success);",
+                        GameResult::Defeat => "color: var(--error);",
+                        GameResult::Draw => "",
+                    }>
+                        { move_tr!("rps-finished", {"result" => result_text.clone()}) }
+                    </h3>
+                    { forfeit_text.map(|text| view! { <p style="color: var(--muted);">{text}</p> }) }
+                    { board_view(info.board, None::<fn(u8)>) }
+                }.into_any()
+            }
+        } }
+
+            <div class="stack" style="margin-top: auto; --stack-gap: var(--s0);">
+            <button
+            class:el-hide=move || !curr_game.get().is_some_and(|g| matches!(g, TicTacToeState::Finished(_)))
+            on:click=next_btn>
+                { move || tr!("rps-next-game") }
+            </button>
+            <a href = "/games" class="button secondary">
+                { move || tr!("rps-other-games") }
+            </a>
+            </div>
+        </div>
+    }
+}
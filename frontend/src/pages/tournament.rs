@@ -0,0 +1,238 @@
+use chrono::Local;
+use leptos::{prelude::*, reactive::spawn_local};
+use leptos_fluent::{move_tr, tr};
+use leptos_router::hooks::use_params_map;
+use shared::{
+    tournament::*,
+    ws_messages::ServerMsg,
+};
+
+use crate::api::{cancel_tournament, fetch_tournament, join_tournament, leave_tournament};
+use crate::hooks::{MyToaster, WebsocketContext};
+use crate::pages::tournaments::{error_label, format_label, status_label};
+
+#[derive(Clone, Copy)]
+enum Action
+{
+    Join,
+    Leave,
+    Cancel,
+}
+
+#[component]
+pub fn TournamentPage() -> impl IntoView
+{
+    let params = use_params_map();
+    let ws = expect_context::<WebsocketContext>();
+    let toaster = MyToaster::new();
+
+    let id = move || {
+        params.read()
+              .get("id")
+              .and_then(|id| id.parse::<i64>().ok())
+    };
+
+    let details = LocalResource::new(move || {
+        let id = id();
+        async move {
+            match id {
+                Some(id) => fetch_tournament(id).await,
+                None => Err(TournamentError::NotFound),
+            }
+        }
+    });
+
+    // Any tournament event may change the bracket, so just fetch it again.
+    Effect::new(move |_| {
+        if let Some(ServerMsg::TournamentMsg(_)) = ws.message.get() {
+            details.refetch();
+        }
+    });
+
+    let (failure, set_failure) = signal(None::<TournamentError>);
+    Effect::new(move |_| {
+        if let Some(err) = failure.get() {
+            toaster.error(&error_label(err));
+        }
+    });
+
+    let run = move |action: Action| {
+        let Some(id) = id() else {
+            return;
+        };
+        spawn_local(async move {
+            let res = match action {
+                Action::Join => join_tournament(id).await,
+                Action::Leave => leave_tournament(id).await,
+                Action::Cancel => cancel_tournament(id).await,
+            };
+            match res {
+                Ok(()) => details.refetch(),
+                Err(err) => set_failure.set(Some(err)),
+            }
+        });
+    };
+
+    view! {
+        <div class="stack fill-page card">
+            { move || match details.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(Err(err)) => view! { <p style="color: var(--error);">{error_label(err)}</p> }.into_any(),
+                Some(Ok(t)) => {
+                    let running = t.summary.status == TournamentStatus::Running;
+                    let open = t.summary.status == TournamentStatus::Open;
+                    let joined = t.joined;
+                    let can_cancel = t.can_cancel && open;
+                    let swiss = t.summary.format == TournamentFormat::Swiss;
+                    let (round, rounds) = (t.current_round, t.rounds);
+
+                    view! {
+                        <h1>{t.summary.name.clone()}</h1>
+                        <p style="color: var(--muted);">
+                            {format!("{} · ", format_label(t.summary.format))}
+                            { move_tr!("rps-best-of", {"rounds" => t.summary.best_of.rounds()}) }
+                            {format!(" · {}", status_label(t.summary.status))}
+                        </p>
+                        <p>
+                            { move_tr!("tournament-starts", {
+                                "date" => t.summary
+                                           .starts_at
+                                           .with_timezone(&Local)
+                                           .format("%d.%m.%Y %H:%M")
+                                           .to_string()
+                            }) }
+                        </p>
+                        <Show when=move || running>
+                            <p>
+                                { move_tr!("tournament-round-of", {
+                                    "round" => round,
+                                    "rounds" => rounds
+                                }) }
+                            </p>
+                        </Show>
+
+                        <div class="cluster" style="--cluster-justify: center;">
+                            <Show when=move || open && !joined>
+                                <button on:click=move |_| run(Action::Join)>
+                                    { move || tr!("tournament-join") }
+                                </button>
+                            </Show>
+                            <Show when=move || open && joined>
+                                <button class="secondary" on:click=move |_| run(Action::Leave)>
+                                    { move || tr!("tournament-leave") }
+                                </button>
+                            </Show>
+                            <Show when=move || can_cancel>
+                                <button class="secondary" on:click=move |_| run(Action::Cancel)>
+                                    { move || tr!("tournament-cancel") }
+                                </button>
+                            </Show>
+                            <button class="secondary" on:click=move |_| details.refetch()>
+                                { move || tr!("tournament-refresh") }
+                            </button>
+                        </div>
+
+                        <Bracket matches=t.matches.clone() rounds=t.current_round />
+                        <Standings standings=t.standings.clone() swiss=swiss />
+                    }.into_any()
+                }
+            } }
+
+            <a href = "/tournaments" class="button secondary" style="margin-top: auto;">
+                { move || tr!("tournament-back") }
+            </a>
+        </div>
+    }
+}
+
+#[component]
+fn Bracket(matches: Vec<TournamentMatch>, rounds: u32) -> impl IntoView
+{
+    view! {
+        <Show when=move || rounds != 0>
+            <h3>{ move || tr!("tournament-matches") }</h3>
+        </Show>
+        <div class="bracket">
+            { (1..=rounds).map(|round| {
+                              let games = matches.iter()
+                                                 .filter(|m| m.round == round)
+                                                 .cloned()
+                                                 .collect::<Vec<_>>();
+                              view! {
+                                  <div class="stack bracket-round">
+                                      <h4>{ move_tr!("tournament-round", {"round" => round}) }</h4>
+                                      { games.into_iter().map(|m| view! { <BracketMatch game=m /> }).collect::<Vec<_>>() }
+                                  </div>
+                              }
+                          })
+                          .collect::<Vec<_>>() }
+        </div>
+    }
+}
+
+#[component]
+fn BracketMatch(game: TournamentMatch) -> impl IntoView
+{
+    let [p1, p2] = game.players;
+    let draw = game.finished && game.winner.is_none();
+    let player = move |name: Option<String>| {
+        let won = game.finished && name.is_some() && name == game.winner;
+        let label = name.clone().unwrap_or_else(|| tr!("tournament-bye"));
+        view! {
+            <span class:bracket-winner=won>{label}</span>
+        }
+    };
+    view! {
+        <div class="bracket-match">
+            {player(p1)}
+            <span style="color: var(--muted);">{ move || tr!("tournament-vs") }</span>
+            {player(p2)}
+            <Show when=move || draw>
+                <span style="color: var(--muted);">{ move || tr!("tournament-draw") }</span>
+            </Show>
+        </div>
+    }
+}
+
+#[component]
+fn Standings(standings: Vec<Standing>, swiss: bool) -> impl IntoView
+{
+    view! {
+        <Show when={
+            let empty = standings.is_empty();
+            move || !empty
+        }>
+            <h3>{ move || tr!("tournament-standings") }</h3>
+        </Show>
+        <table class="standings">
+            <thead>
+                <tr>
+                    <th>"#"</th>
+                    <th>{ move || tr!("tournament-col-player") }</th>
+                    <th>{ move || tr!("tournament-col-record") }</th>
+                    <Show when=move || swiss>
+                        <th>{ move || tr!("tournament-col-points") }</th>
+                    </Show>
+                </tr>
+            </thead>
+            <tbody>
+                { standings.into_iter()
+                           .enumerate()
+                           .map(|(i, s)| {
+                               let points = s.points();
+                               view! {
+                                   <tr class:standing-out=s.eliminated>
+                                       <td>{i + 1}</td>
+                                       <td>{s.username}</td>
+                                       <td>{format!("{} / {} / {}", s.wins, s.draws, s.losses)}</td>
+                                       <Show when=move || swiss>
+                                           <td>{points}</td>
+                                       </Show>
+                                   </tr>
+                               }
+                           })
+                           .collect::<Vec<_>>() }
+            </tbody>
+        </table>
+    }
+}
@@ -0,0 +1,180 @@
+use chrono::{Local, NaiveDateTime};
+use leptos::{prelude::*, reactive::spawn_local};
+use leptos_fluent::{move_tr, tr};
+use leptos_router::hooks::use_navigate;
+use shared::{rps_game::BestOf, tournament::*};
+
+use crate::api::{create_tournament, fetch_tournaments};
+use crate::hooks::MyToaster;
+
+pub(crate) fn format_label(format: TournamentFormat) -> String
+{
+    match format {
+        TournamentFormat::SingleElimination => tr!("tournaments-format-elimination"),
+        TournamentFormat::Swiss => tr!("tournaments-format-swiss"),
+    }
+}
+
+pub(crate) fn status_label(status: TournamentStatus) -> String
+{
+    match status {
+        TournamentStatus::Open => tr!("tournaments-status-open"),
+        TournamentStatus::Running => tr!("tournaments-status-running"),
+        TournamentStatus::Finished => tr!("tournaments-status-finished"),
+        TournamentStatus::Cancelled => tr!("tournaments-status-cancelled"),
+    }
+}
+
+pub(crate) fn error_label(err: TournamentError) -> String
+{
+    match err {
+        TournamentError::DbError => tr!("tournament-error-db"),
+        TournamentError::NotFound => tr!("tournament-error-not-found"),
+        TournamentError::NotOpen => tr!("tournament-error-not-open"),
+        TournamentError::InvalidName => tr!("tournament-error-invalid-name"),
+        TournamentError::InvalidStart => tr!("tournament-error-invalid-start"),
+        TournamentError::Full => tr!("tournament-error-full"),
+        TournamentError::Forbidden => tr!("tournament-error-forbidden"),
+        TournamentError::NetworkError => tr!("tournament-error-network"),
+    }
+}
+
+#[component]
+pub fn Tournaments() -> impl IntoView
+{
+    let tournaments = LocalResource::new(fetch_tournaments);
+
+    view! {
+        <div class="stack fill-page card">
+            <h1>{ move || tr!("tournaments-title") }</h1>
+
+            { move || match tournaments.get() {
+                None => view! { <div class="loading-spinner"></div> }.into_any(),
+                Some(Err(err)) => view! { <p style="color: var(--error);">{error_label(err)}</p> }.into_any(),
+                Some(Ok(list)) if list.is_empty() => view! { <p>{ tr!("tournaments-empty") }</p> }.into_any(),
+                Some(Ok(list)) => view! {
+                    <ul class="stack tournament-list">
+                        { list.into_iter()
+                              .map(|t| view! {
+                                  <li>
+                                      <a href=format!("/tournaments/{}", t.id) class="tournament-link">
+                                          <strong>{t.name}</strong>
+                                          <span style="color: var(--muted);">
+                                              {format!(" · {} · ", format_label(t.format))}
+                                              { move_tr!("rps-best-of", {"rounds" => t.best_of.rounds()}) }
+                                              {format!(" · {} · ", status_label(t.status))}
+                                              { move_tr!("tournaments-players", {"count" => t.players}) }
+                                          </span>
+                                          <span style="color: var(--muted);">
+                                              { move_tr!("tournament-starts", {
+                                                  "date" => t.starts_at
+                                                             .with_timezone(&Local)
+                                                             .format("%d.%m.%Y %H:%M")
+                                                             .to_string()
+                                              }) }
+                                          </span>
+                                      </a>
+                                  </li>
+                              })
+                              .collect::<Vec<_>>() }
+                    </ul>
+                }.into_any(),
+            } }
+
+            <CreateTournament />
+
+            <a href = "/games" class="button secondary" style="margin-top: auto;">
+                { move || tr!("rps-other-games") }
+            </a>
+        </div>
+    }
+}
+
+#[component]
+fn CreateTournament() -> impl IntoView
+{
+    let toaster = MyToaster::new();
+    let navigate = use_navigate();
+
+    let (name, set_name) = signal(String::new());
+    let (format, set_format) = signal(TournamentFormat::SingleElimination);
+    let (best_of, set_best_of) = signal(BestOf::Three);
+    let (starts_at, set_starts_at) = signal(String::new());
+
+    let on_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+
+        // datetime-local gives the user's local time without a zone
+        let Some(starts_at) = NaiveDateTime::parse_from_str(&starts_at.get(), "%Y-%m-%dT%H:%M")
+            .ok()
+            .and_then(|t| t.and_local_timezone(Local).earliest())
+        else {
+            toaster.error(&tr!("tournament-error-invalid-start"));
+            return;
+        };
+
+        let new = NewTournament { name: name.get(),
+                                  format: format.get(),
+                                  best_of: best_of.get(),
+                                  starts_at: starts_at.to_utc() };
+        let navigate = navigate.clone();
+        let toaster = toaster.clone();
+        spawn_local(async move {
+            match create_tournament(new).await {
+                Ok(id) => navigate(&format!("/tournaments/{id}"), Default::default()),
+                Err(err) => toaster.error(&error_label(err)),
+            }
+        });
+    };
+
+    view! {
+        <form class="stack" on:submit=on_submit>
+            <h3>{ move || tr!("tournaments-create-title") }</h3>
+            <input
+            type="text"
+            maxlength="64"
+            placeholder=move || tr!("tournaments-name")
+            prop:value=name
+            on:input=move |ev| set_name.set(event_target_value(&ev))
+            />
+            <div class="cluster" style="--cluster-justify: center;">
+                <select on:change=move |ev| {
+                    let format = match event_target_value(&ev).as_str() {
+                        "swiss" => TournamentFormat::Swiss,
+                        _ => TournamentFormat::SingleElimination,
+                    };
+                    set_format.set(format);
+                }>
+                    <option value="elimination">{ move || tr!("tournaments-format-elimination") }</option>
+                    <option value="swiss">{ move || tr!("tournaments-format-swiss") }</option>
+                </select>
+                <select on:change=move |ev| {
+                    let series = event_target_value(&ev).parse()
+                                                        .ok()
+                                                        .and_then(BestOf::from_rounds)
+                                                        .unwrap_or_default();
+                    set_best_of.set(series);
+                }>
+                    { BestOf::ALL.into_iter()
+                                 .map(|series| view! {
+                                     <option
+                                     value=series.rounds().to_string()
+                                     selected=series == BestOf::Three
+                                     >
+                                         { move_tr!("rps-best-of", {"rounds" => series.rounds()}) }
+                                     </option>
+                                 })
+                                 .collect::<Vec<_>>() }
+                </select>
+            </div>
+            <label for="tournament-starts-at">{ move || tr!("tournaments-starts-at") }</label>
+            <input
+            id="tournament-starts-at"
+            type="datetime-local"
+            prop:value=starts_at
+            on:input=move |ev| set_starts_at.set(event_target_value(&ev))
+            />
+            <button type="submit">{ move || tr!("tournaments-create") }</button>
+        </form>
+    }
+}
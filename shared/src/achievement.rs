@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Achievement
+{
+    FirstWin,
+    /// Ten wins in a row.
+    WinStreak,
+    /// A hundred games played.
+    Veteran,
+    /// A round won with each of the three moves.
+    AllRounder,
+    /// A forum post with ten likes.
+    CrowdFavourite,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UnlockedAchievement
+{
+    pub achievement: Achievement,
+    pub unlocked_at: DateTime<Utc>,
+}
@@ -1,12 +1,16 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::game::RatingInfo;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct UserInfo
 {
     pub username: String,
     pub created_at: DateTime<Utc>,
     pub role: String,
+    pub rating: RatingInfo,
+    pub coins: i64,
 }
 
 #[derive(Serialize, Deserialize, Default, Clone)]
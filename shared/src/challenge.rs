@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// Direct challenge to a game of the kind the enclosing `GameReq` belongs to.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum ChallengeReq<S>
+{
+    Send
+    {
+        username: String,
+        settings: S,
+    },
+    Accept
+    {
+        from: String,
+    },
+    Decline
+    {
+        from: String,
+    },
+    Cancel,
+}
+
+/// Sent in a `GameEnvelope` of the challenged game's kind.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum ChallengeEvent<S>
+{
+    /// Someone challenged you, answer within `expires_in` seconds.
+    Incoming
+    {
+        from: String,
+        settings: S,
+        expires_in: u64,
+    },
+    Sent
+    {
+        to: String,
+        settings: S,
+        expires_in: u64,
+    },
+    Accepted
+    {
+        by: String,
+    },
+    Declined
+    {
+        by: String,
+    },
+    Cancelled
+    {
+        by: String,
+    },
+    Expired
+    {
+        with: String,
+    },
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Why a pair of players looks like it trades wins.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CollusionSignal
+{
+    /// A large share of one of them's ranked games is against the other.
+    HeadToHead,
+    /// One of them wins far more often than chance allows.
+    OneSided,
+    /// Their move pairs are far from what independent players produce.
+    MovePattern,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FlagStatus
+{
+    /// Waiting for a moderator.
+    Open,
+    /// Looked at and found fine.
+    Dismissed,
+    /// Their games against each other no longer count towards ratings.
+    Excluded,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CollusionFlag
+{
+    pub id: i64,
+    pub players: [String; 2],
+    /// Ranked rounds between them in the analysed window.
+    pub games: u32,
+    /// Rounds won by each player, draws left out.
+    pub wins: [u32; 2],
+    /// Highest share of either player's games that were against the other.
+    pub head_to_head: f64,
+    /// How unlikely the move pairs are, as a chi-square statistic.
+    pub move_chi2: f64,
+    pub signals: Vec<CollusionSignal>,
+    pub status: FlagStatus,
+    pub flagged_at: DateTime<Utc>,
+    pub reviewed_by: Option<String>,
+}
+
+/// Moderators only.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum CollusionCmd
+{
+    /// Open flags first, then the most recent.
+    List,
+    Dismiss
+    {
+        id: i64,
+    },
+    /// Take back the rating changes of the pair's games and stop rating new ones.
+    Exclude
+    {
+        id: i64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum CollusionError
+{
+    DbError,
+    NotFound,
+    Forbidden,
+    NetworkError,
+}
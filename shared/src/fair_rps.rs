@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    game::{GameKind, GameReq, GameResult},
+    rps_game::RpsMove,
+};
+
+/// Hex encoded SHA-256 of `"<move>:<nonce>"`, published before the move itself.
+pub fn commitment(mv: RpsMove, nonce: &str) -> String
+{
+    let digest = Sha256::digest(format!("{mv}:{nonce}"));
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Whether `s` looks like a value produced by [`commitment`].
+pub fn is_commitment(s: &str) -> bool
+{
+    s.len() == 64 && s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+}
+
+/// A move in the commit-reveal game: first the hash, then the move with its nonce.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum FairRpsMove
+{
+    Commit(String),
+    Reveal
+    {
+        mv: RpsMove,
+        nonce: String,
+    },
+}
+
+pub type FairRpsReq = GameReq<(), FairRpsMove>;
+
+/// Single round RPS where both moves are committed before either is revealed.
+pub struct FairRpsKind;
+
+impl GameKind for FairRpsKind
+{
+    const KIND: &'static str = "rps_fair";
+    type Settings = ();
+    type Move = FairRpsMove;
+    type State = FairRpsState;
+}
+
+/// Everything needed to check a fair game independently of the server.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FairRpsInfo
+{
+    /// Id to look the game up by in `GET /api/games/rps/fair/{id}`, once it is recorded.
+    pub id: Option<i64>,
+    pub players: [String; 2],
+    pub commitments: [Option<String>; 2],
+    pub moves: [Option<RpsMove>; 2],
+    pub nonces: [Option<String>; 2],
+    /// Which player lost by not committing or revealing before the deadline.
+    pub forfeited: [bool; 2],
+}
+
+/// A recorded fair game as served by the verification API.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FairRpsRecord
+{
+    pub id: i64,
+    pub created_at: DateTime<Utc>,
+    pub info: FairRpsInfo,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum FairRpsState
+{
+    Commit
+    {
+        players: [String; 2],
+        ratings: [Option<i32>; 2],
+        committed: [bool; 2],
+        deadline: DateTime<Utc>,
+    },
+    Reveal
+    {
+        players: [String; 2],
+        ratings: [Option<i32>; 2],
+        commitments: [String; 2],
+        revealed: [bool; 2],
+        deadline: DateTime<Utc>,
+    },
+    Finished(FairRpsInfo),
+}
+
+impl FairRpsInfo
+{
+    /// Whether each revealed move matches its commitment.
+    pub fn verify(&self) -> [bool; 2]
+    {
+        [0, 1].map(|i| match (&self.commitments[i], self.moves[i], &self.nonces[i]) {
+                  (Some(hash), Some(mv), Some(nonce)) => commitment(mv, nonce) == *hash,
+                  _ => false,
+              })
+    }
+
+    pub fn resolve(&self) -> GameResult
+    {
+        match (self.forfeited, self.moves) {
+            ([true, false], _) => GameResult::Defeat,
+            ([false, true], _) => GameResult::Win,
+            (_, [Some(your_move), Some(opp_move)]) if your_move.beats(&opp_move) => GameResult::Win,
+            (_, [Some(your_move), Some(opp_move)]) if opp_move.beats(&your_move) => {
+                GameResult::Defeat
+            }
+            _ => GameResult::Draw,
+        }
+    }
+
+    pub fn reverse(&mut self)
+    {
+        self.players.reverse();
+        self.commitments.reverse();
+        self.moves.reverse();
+        self.nonces.reverse();
+        self.forfeited.reverse();
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use RpsMove::*;
+
+    fn revealed(moves: [RpsMove; 2]) -> FairRpsInfo
+    {
+        let nonces = ["first".to_string(), "second".to_string()];
+        FairRpsInfo { id: None,
+                      players: Default::default(),
+                      commitments: [0, 1].map(|i| Some(commitment(moves[i], &nonces[i]))),
+                      moves: moves.map(Some),
+                      nonces: nonces.map(Some),
+                      forfeited: [false, false] }
+    }
+
+    #[test]
+    fn commitment_is_hex_sha256()
+    {
+        let hash = commitment(Rock, "nonce");
+
+        assert!(is_commitment(&hash));
+        assert_ne!(hash, commitment(Paper, "nonce"));
+        assert_ne!(hash, commitment(Rock, "other"));
+        assert!(!is_commitment(&hash.to_uppercase()));
+        assert!(!is_commitment(&hash[1..]));
+    }
+
+    #[test]
+    fn verifies_honest_reveals()
+    {
+        assert_eq!(revealed([Rock, Scissors]).verify(), [true, true]);
+    }
+
+    #[test]
+    fn rejects_a_swapped_move()
+    {
+        let mut info = revealed([Rock, Scissors]);
+        info.moves[1] = Some(Paper);
+
+        assert_eq!(info.verify(), [true, false]);
+    }
+
+    #[test]
+    fn missing_reveal_does_not_verify()
+    {
+        let mut info = revealed([Rock, Scissors]);
+        info.moves[0] = None;
+        info.nonces[0] = None;
+
+        assert_eq!(info.verify(), [false, true]);
+    }
+
+    #[test]
+    fn resolves_revealed_moves()
+    {
+        assert_eq!(revealed([Rock, Scissors]).resolve(), GameResult::Win);
+        assert_eq!(revealed([Rock, Paper]).resolve(), GameResult::Defeat);
+        assert_eq!(revealed([Paper, Paper]).resolve(), GameResult::Draw);
+    }
+
+    #[test]
+    fn forfeit_beats_the_moves()
+    {
+        let mut info = revealed([Rock, Scissors]);
+        info.forfeited = [true, false];
+        assert_eq!(info.resolve(), GameResult::Defeat);
+
+        let mut info = revealed([Rock, Paper]);
+        info.moves[1] = None;
+        info.forfeited = [false, true];
+        assert_eq!(info.resolve(), GameResult::Win);
+    }
+
+    #[test]
+    fn reverse_flips_the_result()
+    {
+        let mut info = revealed([Rock, Scissors]);
+        info.forfeited = [false, true];
+        info.reverse();
+
+        assert_eq!(info.resolve(), GameResult::Defeat);
+        assert_eq!(info.verify(), [true, true]);
+    }
+}
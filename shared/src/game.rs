@@ -1,7 +1,13 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Serialize, Deserialize, Clone)]
+use crate::{
+    challenge::{ChallengeEvent, ChallengeReq},
+    room::{RoomEvent, RoomReq},
+    spectate::SpectateReq,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum GameResult
 {
     Win,
@@ -17,6 +23,144 @@ pub enum GameError
     Disconnected,
     AlreadyInGame,
     DbError,
+    /// The requested user is offline, busy or yourself.
+    UserUnavailable,
+    /// Nobody moved before the deadline and the game was abandoned.
+    TimedOut,
+}
+
+/// A game played through the generic game messages, registered on the server under `KIND`.
+pub trait GameKind
+{
+    const KIND: &'static str;
+    /// Options picked when joining the queue.
+    type Settings: Serialize + DeserializeOwned;
+    type Move: Serialize + DeserializeOwned;
+    /// What players are sent as the game goes on.
+    type State: Serialize + DeserializeOwned;
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum GameReq<S, M>
+{
+    Start(S),
+    Submit(M),
+    Leave,
+    /// Offer a rematch, or accept the one the opponent offered.
+    Rematch,
+    DeclineRematch,
+    Challenge(ChallengeReq<S>),
+    /// Watch live games of this kind.
+    Spectate(SpectateReq),
+    /// Coins to stake on each of your next ranked games, 0 for none. You are only
+    /// matched with players who picked the same stake.
+    SetStake(u32),
+    Room(RoomReq<S>),
+}
+
+/// A request or state of the game of the given kind, so the websocket can route
+/// messages of any registered game without knowing its types.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GameEnvelope
+{
+    pub kind: String,
+    pub payload: serde_json::Value,
+}
+
+impl GameEnvelope
+{
+    pub fn request<K: GameKind>(req: GameReq<K::Settings, K::Move>) -> Self
+    {
+        Self::wrap::<K, _>(&req)
+    }
+
+    pub fn state<K: GameKind>(state: &K::State) -> Self
+    {
+        Self::wrap::<K, _>(state)
+    }
+
+    pub fn challenge<K: GameKind>(event: &ChallengeEvent<K::Settings>) -> Self
+    {
+        Self::wrap::<K, _>(event)
+    }
+
+    pub fn room<K: GameKind>(event: &RoomEvent<K::Settings>) -> Self
+    {
+        Self::wrap::<K, _>(event)
+    }
+
+    /// State carried by the envelope, `None` if it belongs to another game.
+    pub fn open_state<K: GameKind>(&self) -> Option<K::State>
+    {
+        self.open::<K, _>()
+    }
+
+    pub fn open_request<K: GameKind>(&self) -> Option<GameReq<K::Settings, K::Move>>
+    {
+        self.open::<K, _>()
+    }
+
+    pub fn open_challenge<K: GameKind>(&self) -> Option<ChallengeEvent<K::Settings>>
+    {
+        self.open::<K, _>()
+    }
+
+    pub fn open_room<K: GameKind>(&self) -> Option<RoomEvent<K::Settings>>
+    {
+        self.open::<K, _>()
+    }
+
+    fn wrap<K: GameKind, T: Serialize>(payload: &T) -> Self
+    {
+        Self { kind: K::KIND.to_string(),
+               payload: serde_json::to_value(payload).unwrap_or_default() }
+    }
+
+    fn open<K: GameKind, T: DeserializeOwned>(&self) -> Option<T>
+    {
+        if self.kind != K::KIND {
+            return None;
+        }
+        serde_json::from_value(self.payload.clone()).ok()
+    }
+}
+
+/// Public part of a player's Glicko-2 rating.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct RatingInfo
+{
+    pub rating: f64,
+    pub deviation: f64,
+}
+
+impl Default for RatingInfo
+{
+    fn default() -> Self
+    {
+        Self { rating: 1500.0,
+               deviation: 350.0 }
+    }
+}
+
+/// Rematch negotiation between the two players of a just finished game.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RematchEvent
+{
+    Offered,
+    Declined,
+    Expired,
+}
+
+/// Opponent's connection during a game.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum ConnectionEvent
+{
+    /// The opponent dropped and forfeits unless they are back within `grace_secs`.
+    OpponentAway
+    {
+        grace_secs: u64,
+    },
+    OpponentBack,
 }
 
 impl GameResult
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{game::GameResult, rps_game::RpsMove};
+
+/// Query string of `GET /api/games/rps/history`.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct HistoryQuery
+{
+    /// Whose games to list, yourself when left out.
+    pub username: Option<String>,
+    pub opponent: Option<String>,
+    /// From the listed player's side.
+    pub result: Option<GameResult>,
+    /// Cursor: only games older than this id.
+    pub before: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HistoryEntry
+{
+    pub id: i64,
+    pub opponent: String,
+    /// The listed player's move first.
+    pub moves: [RpsMove; 2],
+    pub result: GameResult,
+    /// Series the game was a round of, if any.
+    pub match_id: Option<i64>,
+    pub ranked: bool,
+    pub played_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HistoryPage
+{
+    /// Newest first.
+    pub entries: Vec<HistoryEntry>,
+    /// Cursor for the next page, `None` on the last one.
+    pub next: Option<i64>,
+}
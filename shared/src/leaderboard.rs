@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardSort
+{
+    #[default]
+    Wins,
+    /// Only players with at least `min_games` games are ranked.
+    WinRate,
+    Rating,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardWindow
+{
+    Day,
+    Week,
+    Month,
+    #[default]
+    AllTime,
+}
+
+/// Query string of `GET /api/leaderboard`.
+#[derive(Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct LeaderboardQuery
+{
+    pub sort: LeaderboardSort,
+    pub window: LeaderboardWindow,
+    /// Starts at 0.
+    pub page: u32,
+    pub min_games: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct LeaderboardEntry
+{
+    pub rank: u32,
+    pub username: String,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub rating: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardPage
+{
+    pub entries: Vec<LeaderboardEntry>,
+    pub page: u32,
+    pub pages: u32,
+    /// Minimum games applied to the win rate ranking.
+    pub min_games: u32,
+    /// Your own row, wherever it is, when you are logged in and ranked.
+    pub me: Option<LeaderboardEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum LeaderboardError
+{
+    DbError,
+    NetworkError,
+}
+
+impl LeaderboardEntry
+{
+    pub fn games(&self) -> u32
+    {
+        self.wins + self.losses + self.draws
+    }
+
+    /// Share of games won.
+    pub fn win_rate(&self) -> f64
+    {
+        match self.games() {
+            0 => 0.0,
+            games => self.wins as f64 / games as f64,
+        }
+    }
+}
@@ -1,5 +1,20 @@
+pub mod achievement;
 pub mod auth;
+pub mod challenge;
+pub mod collusion;
+pub mod fair_rps;
 pub mod forum;
 pub mod game;
+pub mod history;
+pub mod leaderboard;
+pub mod profile;
+pub mod quest;
+pub mod room;
+pub mod royale;
 pub mod rps_game;
+pub mod season;
+pub mod spectate;
+pub mod tic_tac_toe;
+pub mod tournament;
+pub mod wallet;
 pub mod ws_messages;
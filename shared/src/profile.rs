@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievement::UnlockedAchievement, auth::UserInfo, history::HistoryEntry, rps_game::RpsMove,
+};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PlayerProfile
+{
+    pub user: UserInfo,
+    /// Ranked results, a match counting once.
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    /// How often each move was played, in `RpsMove::ALL` order.
+    pub move_counts: [u32; 3],
+    pub recent: Vec<HistoryEntry>,
+    pub forum_posts: u32,
+    /// Oldest first.
+    pub achievements: Vec<UnlockedAchievement>,
+}
+
+impl PlayerProfile
+{
+    pub fn games(&self) -> u32
+    {
+        self.wins + self.losses + self.draws
+    }
+
+    /// Most played move, `None` before the first one.
+    pub fn favourite_move(&self) -> Option<RpsMove>
+    {
+        RpsMove::ALL.into_iter()
+                    .zip(self.move_counts)
+                    .filter(|(_, count)| *count > 0)
+                    .max_by_key(|(_, count)| *count)
+                    .map(|(mv, _)| mv)
+    }
+}
+
+/// Habits found in a player's own recent RPS rounds.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MoveAnalytics
+{
+    pub rounds: u32,
+    /// How often each move was played, in `RpsMove::ALL` order.
+    pub move_counts: [u32; 3],
+    /// Moves played in the round after a win, a draw and a loss, in that order.
+    pub after_result: [[u32; 3]; 3],
+    /// Shannon entropy of the moves in bits, `log2(3)` for a perfectly even spread.
+    pub entropy: f64,
+    /// Wins, draws and losses of a predictor that only knew the rounds before each one.
+    pub predictor: [u32; 3],
+}
+
+impl MoveAnalytics
+{
+    /// 0 when the moves are evenly spread, 1 when always the same.
+    pub fn predictability(&self) -> f64
+    {
+        1.0 - self.entropy / 3f64.log2()
+    }
+}
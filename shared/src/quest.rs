@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rps_game::RpsMove;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum QuestPeriod
+{
+    Daily,
+    Weekly,
+}
+
+/// What counts towards a quest.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum QuestGoal
+{
+    PlayGames,
+    WinGames,
+    /// Rounds of RPS won with the move.
+    WinWith(RpsMove),
+    PostInForum,
+}
+
+/// A current quest along with the player's progress on it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct QuestInfo
+{
+    pub id: i64,
+    pub period: QuestPeriod,
+    pub goal: QuestGoal,
+    pub target: u32,
+    pub progress: u32,
+    pub completed: bool,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum QuestReq
+{
+    List,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum QuestEvent
+{
+    /// Quests of the current day and week.
+    List(Vec<QuestInfo>),
+    Progress(QuestInfo),
+    Completed(QuestInfo),
+}
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// `S` is the settings of the room's game kind, which every game in the room is played with.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RoomSummary<S>
+{
+    pub id: i64,
+    pub name: String,
+    pub owner: Option<String>,
+    pub settings: S,
+    /// Joining needs the room password.
+    pub private: bool,
+    pub members: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RoomResult
+{
+    pub players: [String; 2],
+    /// `None` for a draw or an abandoned game.
+    pub winner: Option<String>,
+    pub played_at: DateTime<Utc>,
+}
+
+/// A room as seen by its members.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RoomState<S>
+{
+    pub summary: RoomSummary<S>,
+    /// Members in rotation order: whoever holds the table first, then the line.
+    pub members: Vec<String>,
+    /// Player who stays on until beaten, with their number of wins in a row.
+    pub champion: Option<(String, u32)>,
+    pub playing: Option<[String; 2]>,
+    /// Latest results first.
+    pub recent: Vec<RoomResult>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RoomReq<S>
+{
+    List,
+    Create
+    {
+        name: String,
+        password: Option<String>,
+        settings: S,
+    },
+    Join
+    {
+        room_id: i64,
+        password: Option<String>,
+    },
+    Leave,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum RoomError
+{
+    NotFound,
+    WrongPassword,
+    InvalidName,
+    NameTaken,
+    DbError,
+}
+
+/// Sent in a `GameEnvelope` of the room's game kind.
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RoomEvent<S>
+{
+    List(Vec<RoomSummary<S>>),
+    /// Sent to every member whenever the room changes.
+    State(RoomState<S>),
+    Left,
+    Error(RoomError),
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{GameKind, GameReq},
+    rps_game::RpsMove,
+};
+
+/// A battle royale game starts once this many players are in the lobby...
+pub const ROYALE_MIN_PLAYERS: usize = 3;
+/// ...and right away when it is full.
+pub const ROYALE_MAX_PLAYERS: usize = 8;
+
+pub type RoyaleReq = GameReq<(), RpsMove>;
+
+/// N-player RPS knockout. `Start` joins the lobby and each `Submit` is a round's throw.
+pub struct RoyaleKind;
+
+impl GameKind for RoyaleKind
+{
+    const KIND: &'static str = "rps_royale";
+    type Settings = ();
+    type Move = RpsMove;
+    type State = RoyaleEvent;
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RoyalePlayer
+{
+    pub name: String,
+    /// Whether the player already threw in the current round.
+    pub thrown: bool,
+    /// What the player threw in the previous round.
+    pub last_throw: Option<RpsMove>,
+    /// Round the player was knocked out in.
+    pub eliminated_in: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RoyalePlacement
+{
+    pub name: String,
+    /// Players knocked out in the same round share a place.
+    pub place: u32,
+    pub eliminated_in: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum RoyaleEvent
+{
+    /// Players waiting for the next game. It starts at `starts_at` once enough joined.
+    Lobby
+    {
+        players: Vec<String>,
+        starts_at: Option<DateTime<Utc>>,
+    },
+    Round
+    {
+        round: u32,
+        players: Vec<RoyalePlayer>,
+        deadline: DateTime<Utc>,
+    },
+    /// Placements ordered from the winner down.
+    Finished
+    {
+        rounds: u32,
+        placements: Vec<RoyalePlacement>,
+    },
+}
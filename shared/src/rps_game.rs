@@ -1,8 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, fmt, str::FromStr};
 
-use crate::game::GameResult;
+use crate::game::{GameKind, GameReq, GameResult};
 
-#[derive(Serialize, Deserialize, Clone, Copy)]
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum RpsMove
 {
     Rock,
@@ -10,19 +12,40 @@ pub enum RpsMove
     Scissors,
 }
 
-#[derive(Serialize, Deserialize, Clone)]
-pub enum RpsGameReq
+/// Length of a match series; a match is won by the first player to take a majority of rounds.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum BestOf
+{
+    #[default]
+    One,
+    Three,
+    Five,
+    Seven,
+}
+
+pub type RpsGameReq = GameReq<BestOf, RpsMove>;
+
+/// Classic rock paper scissors, played as a best-of series.
+pub struct RpsKind;
+
+impl GameKind for RpsKind
 {
-    Start,
-    Submit(RpsMove),
-    Leave,
+    const KIND: &'static str = "rps";
+    type Settings = BestOf;
+    type Move = RpsMove;
+    type State = RpsGameState;
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct RpsGameInfo
 {
     pub players: [String; 2],
-    pub moves: [RpsMove; 2],
+    /// Moves of the last round played, `None` if the game ended before any round did.
+    pub moves: Option<[RpsMove; 2]>,
+    pub score: [u8; 2],
+    pub best_of: BestOf,
+    /// Which player lost by not moving before the deadline.
+    pub forfeited: [bool; 2],
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -31,38 +54,120 @@ pub enum RpsGameState
     Game
     {
         players: [String; 2],
+        /// Rounded ratings, `None` for unrated players such as bots.
+        ratings: [Option<i32>; 2],
         submitted: [bool; 2],
+        score: [u8; 2],
+        best_of: BestOf,
+        /// Drawn rounds are replayed, so this can run past the length of the series,
+        /// though only by a bounded number of draws.
+        round: u32,
+        last_round: Option<[RpsMove; 2]>,
+        /// Moves not submitted by this time forfeit the game.
+        deadline: DateTime<Utc>,
     },
     Finished(RpsGameInfo),
 }
 
+impl BestOf
+{
+    pub const ALL: [BestOf; 4] = [BestOf::One, BestOf::Three, BestOf::Five, BestOf::Seven];
+
+    pub fn rounds(&self) -> u8
+    {
+        match self {
+            BestOf::One => 1,
+            BestOf::Three => 3,
+            BestOf::Five => 5,
+            BestOf::Seven => 7,
+        }
+    }
+
+    pub fn from_rounds(rounds: u8) -> Option<BestOf>
+    {
+        BestOf::ALL.into_iter().find(|b| b.rounds() == rounds)
+    }
+
+    pub fn wins_needed(&self) -> u8
+    {
+        self.rounds() / 2 + 1
+    }
+}
+
+impl RpsMove
+{
+    pub const ALL: [RpsMove; 3] = [RpsMove::Rock, RpsMove::Paper, RpsMove::Scissors];
+
+    pub fn beats(&self, other: &RpsMove) -> bool
+    {
+        use RpsMove::*;
+        matches!((self, other),
+                 (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper))
+    }
+
+    /// The move that beats this one.
+    pub fn counter(&self) -> RpsMove
+    {
+        match self {
+            RpsMove::Rock => RpsMove::Paper,
+            RpsMove::Paper => RpsMove::Scissors,
+            RpsMove::Scissors => RpsMove::Rock,
+        }
+    }
+}
+
 impl RpsGameInfo
 {
     pub fn resolve(&self) -> GameResult
     {
-        let [your_move, opp_move] = &self.moves;
-        use RpsMove::*;
-        match (your_move, opp_move) {
-            (Rock, Scissors) | (Paper, Rock) | (Scissors, Paper) => GameResult::Win,
-            (Rock, Paper) | (Paper, Scissors) | (Scissors, Rock) => GameResult::Defeat,
-            _ => GameResult::Draw,
+        match self.forfeited {
+            [true, false] => return GameResult::Defeat,
+            [false, true] => return GameResult::Win,
+            _ => {}
+        }
+
+        let [your_score, opp_score] = self.score;
+        match your_score.cmp(&opp_score) {
+            Ordering::Greater => GameResult::Win,
+            Ordering::Less => GameResult::Defeat,
+            Ordering::Equal => GameResult::Draw,
         }
     }
     pub fn reverse(&mut self)
     {
         self.players.reverse();
-        self.moves.reverse();
+        if let Some(moves) = &mut self.moves {
+            moves.reverse();
+        }
+        self.score.reverse();
+        self.forfeited.reverse();
     }
 }
 
-impl ToString for RpsMove
+impl fmt::Display for RpsMove
 {
-    fn to_string(&self) -> String
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
     {
-        match self {
-            RpsMove::Rock => "Rock".into(),
-            RpsMove::Paper => "Paper".into(),
-            RpsMove::Scissors => "Scissors".into(),
+        let name = match self {
+            RpsMove::Rock => "Rock",
+            RpsMove::Paper => "Paper",
+            RpsMove::Scissors => "Scissors",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for RpsMove
+{
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s {
+            "Rock" => Ok(RpsMove::Rock),
+            "Paper" => Ok(RpsMove::Paper),
+            "Scissors" => Ok(RpsMove::Scissors),
+            _ => Err(()),
         }
     }
 }
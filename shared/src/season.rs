@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NewSeason
+{
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SeasonSummary
+{
+    pub id: i64,
+    pub name: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// Ended and its standings are final.
+    pub archived: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SeasonStanding
+{
+    pub rank: u32,
+    pub username: String,
+    pub rating: f64,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SeasonDetails
+{
+    pub summary: SeasonSummary,
+    /// Best rated first. Live ratings until the season is archived.
+    pub standings: Vec<SeasonStanding>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum SeasonError
+{
+    DbError,
+    NotFound,
+    InvalidName,
+    /// Ends before it starts, or already ended.
+    InvalidDates,
+    /// Runs at the same time as another season.
+    Overlaps,
+    Forbidden,
+    NetworkError,
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ws_messages::ServerMsg;
+
+/// A game in progress that can be watched.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct LiveGame
+{
+    pub id: u64,
+    pub players: [String; 2],
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum SpectateReq
+{
+    List,
+    Watch(u64),
+    Unwatch,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum SpectateEvent
+{
+    Games(Vec<LiveGame>),
+    /// The state the players got, arrays in game order. Moves stay hidden until the round resolves.
+    Update
+    {
+        game_id: u64,
+        msg: Box<ServerMsg>,
+    },
+    /// The watched game is over and the subscription was dropped.
+    Ended
+    {
+        game_id: u64,
+    },
+}
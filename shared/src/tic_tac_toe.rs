@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{GameKind, GameReq, GameResult};
+
+pub type Board = [Option<Mark>; 9];
+
+/// Rows, columns and diagonals of the 3x3 board, by cell index.
+pub const LINES: [[usize; 3]; 8] = [[0, 1, 2],
+                                    [3, 4, 5],
+                                    [6, 7, 8],
+                                    [0, 3, 6],
+                                    [1, 4, 7],
+                                    [2, 5, 8],
+                                    [0, 4, 8],
+                                    [2, 4, 6]];
+
+/// X belongs to the first player and always moves first.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mark
+{
+    X,
+    O,
+}
+
+impl Mark
+{
+    pub fn of(player: usize) -> Self
+    {
+        if player == 0 { Mark::X } else { Mark::O }
+    }
+
+    pub fn other(self) -> Self
+    {
+        match self {
+            Mark::X => Mark::O,
+            Mark::O => Mark::X,
+        }
+    }
+}
+
+/// The completed line, if any.
+pub fn winning_line(board: &Board) -> Option<[usize; 3]>
+{
+    LINES.into_iter()
+         .find(|&[a, b, c]| board[a].is_some() && board[a] == board[b] && board[b] == board[c])
+}
+
+pub fn winner(board: &Board) -> Option<Mark>
+{
+    winning_line(board).and_then(|[a, _, _]| board[a])
+}
+
+/// Mark to be placed next: X and O simply alternate.
+pub fn next_mark(board: &Board) -> Mark
+{
+    if board.iter().flatten().count().is_multiple_of(2) { Mark::X } else { Mark::O }
+}
+
+/// Moves are the index of the cell to mark, row by row from the top left.
+pub type TicTacToeReq = GameReq<(), u8>;
+
+pub struct TicTacToeKind;
+
+impl GameKind for TicTacToeKind
+{
+    const KIND: &'static str = "tic_tac_toe";
+    type Settings = ();
+    type Move = u8;
+    type State = TicTacToeState;
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TicTacToeInfo
+{
+    pub players: [String; 2],
+    pub board: Board,
+    /// Which player lost by not moving before the deadline or leaving.
+    pub forfeited: [bool; 2],
+}
+
+impl TicTacToeInfo
+{
+    /// Result for the first player, who plays X.
+    pub fn resolve(&self) -> GameResult
+    {
+        match (self.forfeited, winner(&self.board)) {
+            ([true, false], _) => GameResult::Defeat,
+            ([false, true], _) => GameResult::Win,
+            (_, Some(Mark::X)) => GameResult::Win,
+            (_, Some(Mark::O)) => GameResult::Defeat,
+            _ => GameResult::Draw,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum TicTacToeState
+{
+    Playing
+    {
+        /// The first player plays X.
+        players: [String; 2],
+        ratings: [Option<i32>; 2],
+        board: Board,
+        turn: Mark,
+        deadline: DateTime<Utc>,
+    },
+    Finished(TicTacToeInfo),
+}
@@ -0,0 +1,149 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::rps_game::BestOf;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TournamentFormat
+{
+    /// Losers are out, winners meet in the next round until one is left.
+    SingleElimination,
+    /// Everyone plays every round against someone with a similar score.
+    Swiss,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TournamentStatus
+{
+    /// Taking sign-ups until the start time.
+    Open,
+    Running,
+    Finished,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NewTournament
+{
+    pub name: String,
+    pub format: TournamentFormat,
+    pub best_of: BestOf,
+    pub starts_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TournamentSummary
+{
+    pub id: i64,
+    pub name: String,
+    pub format: TournamentFormat,
+    pub best_of: BestOf,
+    pub status: TournamentStatus,
+    pub starts_at: DateTime<Utc>,
+    pub created_by: Option<String>,
+    pub players: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TournamentMatch
+{
+    pub round: u32,
+    /// Second player is `None` for a bye.
+    pub players: [Option<String>; 2],
+    pub winner: Option<String>,
+    pub finished: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Standing
+{
+    pub username: String,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub eliminated: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TournamentDetails
+{
+    pub summary: TournamentSummary,
+    pub rounds: u32,
+    pub current_round: u32,
+    pub matches: Vec<TournamentMatch>,
+    /// Best first.
+    pub standings: Vec<Standing>,
+    pub joined: bool,
+    /// Whether you may cancel it, as its creator or an admin.
+    pub can_cancel: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum TournamentCmd
+{
+    List,
+    Fetch
+    {
+        id: i64,
+    },
+    Create(NewTournament),
+    Join
+    {
+        id: i64,
+    },
+    Leave
+    {
+        id: i64,
+    },
+    Cancel
+    {
+        id: i64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum TournamentError
+{
+    DbError,
+    NotFound,
+    /// Sign-up is closed, the tournament already started or was cancelled.
+    NotOpen,
+    InvalidName,
+    InvalidStart,
+    Full,
+    Forbidden,
+    NetworkError,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum TournamentEvent
+{
+    Started
+    {
+        id: i64,
+        name: String,
+    },
+    /// Your next match was just started and waits on the RPS page.
+    MatchReady
+    {
+        id: i64,
+        name: String,
+        round: u32,
+        opponent: String,
+    },
+    Finished
+    {
+        id: i64,
+        name: String,
+        winner: Option<String>,
+    },
+}
+
+impl Standing
+{
+    /// Swiss score: a point per win and half a point per draw.
+    pub fn points(&self) -> f32
+    {
+        self.wins as f32 + self.draws as f32 / 2.0
+    }
+}
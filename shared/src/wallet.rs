@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum WalletReq
+{
+    Balance,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub enum WalletEvent
+{
+    Balance(i64),
+    /// Answer to `GameReq::SetStake`.
+    StakeSet(u32),
+    /// Both stakes of the game that just started are held until it ends.
+    Staked
+    {
+        stake: u32,
+        balance: i64,
+    },
+    Error(WalletError),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum WalletError
+{
+    DbError,
+    InsufficientFunds,
+    /// Stakes aren't taken in this game.
+    NoWagers,
+}
@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    achievement::UnlockedAchievement,
     forum::*,
-    game::GameError,
-    rps_game::{RpsGameReq, RpsGameState},
+    game::{ConnectionEvent, GameEnvelope, GameError, GameKind, GameReq, RematchEvent},
+    quest::{QuestEvent, QuestReq},
+    spectate::SpectateEvent,
+    tournament::TournamentEvent,
+    wallet::{WalletEvent, WalletReq},
 };
 
 #[non_exhaustive]
@@ -11,7 +15,19 @@ use crate::{
 pub enum ClientMsg
 {
     GetStats,
-    RpsGameMsg(RpsGameReq),
+    /// Request to whichever registered game the envelope names.
+    GameMsg(GameEnvelope),
+    QuestMsg(QuestReq),
+    WalletMsg(WalletReq),
+}
+
+impl ClientMsg
+{
+    /// Request to the game of kind `K`.
+    pub fn game<K: GameKind>(req: GameReq<K::Settings, K::Move>) -> Self
+    {
+        ClientMsg::GameMsg(GameEnvelope::request::<K>(req))
+    }
 }
 
 #[non_exhaustive]
@@ -20,9 +36,20 @@ pub enum ServerMsg
 {
     StatsMsg(StatsInfo),
     NewPostMsg(ForumPost),
-    RpsGameMsg(RpsGameState),
+    GameMsg(GameEnvelope),
     WsErrorMsg(WsError),
     GameErrorMsg(GameError),
+    /// `ChallengeEvent` of the enveloped game kind.
+    ChallengeMsg(GameEnvelope),
+    RematchMsg(RematchEvent),
+    SpectateMsg(SpectateEvent),
+    ConnectionMsg(ConnectionEvent),
+    TournamentMsg(TournamentEvent),
+    /// `RoomEvent` of the enveloped game kind.
+    RoomMsg(GameEnvelope),
+    AchievementMsg(UnlockedAchievement),
+    QuestMsg(QuestEvent),
+    WalletMsg(WalletEvent),
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]